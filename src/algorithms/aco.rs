@@ -1,20 +1,110 @@
 use crate::eval::Evaluation;
 use crate::shared::{Fitness, Instance, Solution, Ville};
+use crate::utils::rng::new_rng;
 
-use super::{Metaheuristic, LocalSearch};
+use super::{AlgoEvent, Elite, EliteTracker, Metaheuristic, LocalSearch};
 
 use ndarray::Array2;
 use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use rand::Rng;
 use std::collections::HashMap;
 
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum ACOVariant {
+    /// Ant System : chaque fourmi dépose des phéromones, sans bornes
+    #[default]
+    AntSystem,
+    /// MAX-MIN Ant System : seule la meilleure fourmi de l'itération dépose, les phéromones sont
+    /// bornées dans [tau_min, tau_max] et réinitialisées en cas de stagnation
+    MaxMin,
+    /// Ant Colony System : règle pseudo-aléatoire proportionnelle (q0), mise à jour locale pendant
+    /// la construction et mise à jour globale sur la meilleure solution uniquement
+    AntColonySystem,
+}
+
+/// Paramètres de la mise à jour des phéromones, communs puis propres aux variantes de l'ACO
+///
+/// - `evaporation_rate`: taux d'évaporation des phéromones à chaque itération
+/// - `pheromone_deposit`: quantité déposée par une fourmi, divisée par la fitness de sa solution
+/// - `variant`: variante de l'algorithme (AS, MMAS, ACS)
+/// - `q0`: ACS, probabilité de choisir directement le meilleur candidat plutôt que par roulette
+/// - `local_evaporation_rate`: ACS, taux de la mise à jour locale vers tau_0
+/// - `p_best`: MMAS, probabilité de reconstruire la meilleure solution à la convergence, fixe le rapport tau_min / tau_max
+/// - `stagnation_limit`: MMAS, nombre d'itérations sans amélioration avant réinitialisation des phéromones (0 = jamais)
+#[derive(Clone, Copy, PartialEq)]
+pub struct ACOVariantConfig {
+    pub evaporation_rate: f32,
+    pub pheromone_deposit: f32,
+    pub variant: ACOVariant,
+    pub q0: f32,
+    pub local_evaporation_rate: f32,
+    pub p_best: f32,
+    pub stagnation_limit: usize,
+}
+
+impl Default for ACOVariantConfig {
+    fn default() -> Self {
+        ACOVariantConfig {
+            evaporation_rate: 0.5,
+            pheromone_deposit: 1.0,
+            variant: ACOVariant::AntSystem,
+            q0: 0.9,
+            local_evaporation_rate: 0.1,
+            p_best: 0.05,
+            stagnation_limit: 100,
+        }
+    }
+}
+
+/// Paramètres de l'information heuristique utilisée pendant la construction
+///
+/// La désirabilité d'un candidat j depuis i à l'instant t est 1 / (d_ij + `waiting_weight` * (attente_j +
+/// retard_j) + `urgency_weight` * marge_j), où la marge est le temps restant avant la fermeture de la fenêtre
+/// de j et le retard le temps écoulé depuis. Les candidats qui manqueraient leur fenêtre dure sont écartés
+/// tant qu'un autre peut encore être servi à temps.
+///
+/// - `alpha`, `beta`: exposants des phéromones et de la désirabilité dans la probabilité de choisir un candidat
+/// - `exclude_unreachable`: écarte les candidats qui rendraient un client non visité inatteignable à temps
+/// - `candidate_lists`: ne considère que les listes de candidats de l'instance, avec repli sur toutes les villes non visitées
+#[derive(Clone, Copy, PartialEq)]
+pub struct ACOHeuristicConfig {
+    pub alpha: f32,
+    pub beta: f32,
+    pub waiting_weight: f32,
+    pub urgency_weight: f32,
+    pub exclude_unreachable: bool,
+    pub candidate_lists: bool,
+}
+
+impl Default for ACOHeuristicConfig {
+    fn default() -> Self {
+        ACOHeuristicConfig {
+            alpha: 1.0,
+            beta: 2.0,
+            waiting_weight: 1.0,
+            urgency_weight: 1.0,
+            exclude_unreachable: true,
+            candidate_lists: true,
+        }
+    }
+}
+
 pub struct ACO<LS> {
     // Paramètres de l'algorithme
-    evaporation_rate: f32,
-    alpha: f32,
-    beta: f32,
     pheromone_matrix: Array2<f32>,
-    pheromone_deposit: f32,
+    variant_config: ACOVariantConfig,
+    heuristic_config: ACOHeuristicConfig,
+    // Phéromones non orientées sur une instance symétrique, orientées (de la ligne vers la colonne) sinon
+    symmetric: bool,
+
+    // Bornes MMAS et niveau initial ACS, calculés à partir de la meilleure fitness connue
+    pheromone_initialized: bool,
+    tau_min: f32,
+    tau_max: f32,
+    tau_0: f32,
+    stagnation: usize,
+    reinitializations: usize,
+    events: Vec<AlgoEvent>,
 
     max_iter: usize,
     iteration: usize,
@@ -24,21 +114,28 @@ pub struct ACO<LS> {
     solution_buffer: Solution,
     desirability_buffer: Vec<f32>,
     unvisited_nodes_buffer: Vec<u32>,
+    service_time_buffer: Vec<f32>,
+
+    // Villes triées par échéance croissante (fin des fenêtres dures) et plus grande distance au départ de chaque ville,
+    // pour arrêter tôt le test d'atteignabilité des clients non visités
+    deadline_order: Vec<Ville>,
+    max_distance_from: Vec<f32>,
 
     // Local search
     local_search: LS,
 
     // RNG réutilisé
     rng: StdRng,
+
+    // Meilleure solution jamais construite, les fourmis écrasant la population à chaque itération
+    elite: EliteTracker,
 }
 
 impl<LS> ACO<LS> {
     pub fn new(
         instance: &Instance,
-        evaporation_rate: f32,
-        alpha: f32,
-        beta: f32,
-        pheromone_deposit: f32,
+        variant_config: ACOVariantConfig,
+        heuristic_config: ACOHeuristicConfig,
         max_iter: usize,
         local_search: LS,
     ) -> Self {
@@ -46,26 +143,99 @@ impl<LS> ACO<LS> {
         let eps = 1e-6;
         let pheromone_matrix = 1.0 / (instance.distance_matrix.clone() + eps);
 
+        let mut deadline_order: Vec<Ville> = (0..num_nodes as Ville).collect();
+        deadline_order.sort_by(|&a, &b| instance.deadline(a as usize).total_cmp(&instance.deadline(b as usize)));
+        let max_distance_from = instance
+            .time_matrix
+            .rows()
+            .into_iter()
+            .map(|row| row.iter().copied().fold(0.0, f32::max))
+            .collect();
+
         ACO {
-            evaporation_rate,
-            alpha,
-            beta,
             pheromone_matrix,
-            pheromone_deposit,
+            variant_config,
+            heuristic_config,
+            symmetric: instance.symmetric,
+            pheromone_initialized: false,
+            tau_min: 0.0,
+            tau_max: f32::INFINITY,
+            tau_0: 0.0,
+            stagnation: 0,
+            reinitializations: 0,
+            events: Vec::new(),
             max_iter,
             iteration: 0,
             visited_buffer: vec![false; num_nodes],
             solution_buffer: Vec::with_capacity(num_nodes),
             desirability_buffer: Vec::with_capacity(num_nodes),
             unvisited_nodes_buffer: Vec::with_capacity(num_nodes),
+            service_time_buffer: Vec::with_capacity(num_nodes),
+            deadline_order,
+            max_distance_from,
             local_search,
-            rng: StdRng::from_os_rng(),
+            rng: new_rng(),
+            elite: EliteTracker::new(),
         }
     }
 }
 
 impl<LS> ACO<LS> {
-    fn construct_solution(&mut self, instance: &Instance) -> () {
+    /// Calcule les niveaux de référence des phéromones (tau_max, tau_min, tau_0) à partir de la meilleure fitness connue
+    fn update_pheromone_levels(&mut self, best_fitness: Fitness, num_nodes: usize) {
+        let best_fitness = best_fitness.max(1e-6);
+        match self.variant_config.variant {
+            ACOVariant::AntSystem => {}
+            ACOVariant::MaxMin => {
+                self.tau_max = self.variant_config.pheromone_deposit / (self.variant_config.evaporation_rate.max(1e-6) * best_fitness);
+                // Stützle & Hoos : tau_min = tau_max (1 - p^(1/n)) / ((n/2 - 1) p^(1/n))
+                let p_dec = self.variant_config.p_best.clamp(1e-6, 1.0).powf(1.0 / num_nodes.max(1) as f32);
+                let avg_choices = (num_nodes as f32 / 2.0 - 1.0).max(1.0);
+                self.tau_min = (self.tau_max * (1.0 - p_dec) / (avg_choices * p_dec)).min(self.tau_max);
+            }
+            ACOVariant::AntColonySystem => {
+                self.tau_0 = self.variant_config.pheromone_deposit / (num_nodes.max(1) as f32 * best_fitness);
+            }
+        }
+    }
+
+    /// Initialise la matrice de phéromones au premier appel de `step`, une fois une première fitness connue
+    fn initialize_pheromones(&mut self, best_fitness: Fitness, num_nodes: usize) {
+        self.update_pheromone_levels(best_fitness, num_nodes);
+        match self.variant_config.variant {
+            ACOVariant::AntSystem => {}
+            ACOVariant::MaxMin => self.pheromone_matrix.fill(self.tau_max),
+            ACOVariant::AntColonySystem => self.pheromone_matrix.fill(self.tau_0),
+        }
+        self.pheromone_initialized = true;
+    }
+
+    /// Dépose `amount` sur chaque arête de la tournée, après l'avoir atténué d'un facteur `keep`.
+    /// Sur une instance asymétrique, seul le sens parcouru est renforcé
+    fn reinforce_tour(pheromone_matrix: &mut Array2<f32>, tour: &[Ville], keep: f32, amount: f32, symmetric: bool) {
+        for j in 0..tour.len() {
+            let from = tour[j] as usize;
+            let to = tour[(j + 1) % tour.len()] as usize;
+            let value = pheromone_matrix[[from, to]] * keep + amount;
+            pheromone_matrix[[from, to]] = value;
+            if symmetric {
+                pheromone_matrix[[to, from]] = value;
+            }
+        }
+    }
+
+    /// Mise à jour locale ACS : l'arête empruntée se rapproche de tau_0, ce qui diversifie les fourmis suivantes
+    fn local_pheromone_update(&mut self, from: Ville, to: Ville) {
+        let xi = self.variant_config.local_evaporation_rate;
+        let (from, to) = (from as usize, to as usize);
+        let value = (1.0 - xi) * self.pheromone_matrix[[from, to]] + xi * self.tau_0;
+        self.pheromone_matrix[[from, to]] = value;
+        if self.symmetric {
+            self.pheromone_matrix[[to, from]] = value;
+        }
+    }
+
+    fn construct_solution(&mut self, instance: &Instance) {
         let num_nodes = instance.size();
 
         // Réinitialiser les buffers
@@ -76,73 +246,193 @@ impl<LS> ACO<LS> {
         self.solution_buffer.push(start_node);
         self.visited_buffer[start_node as usize] = true;
 
+        let q0 = match self.variant_config.variant {
+            ACOVariant::AntColonySystem => self.variant_config.q0,
+            _ => 0.0,
+        };
+
+        // Heure courante de la fourmi, comme dans `run_solution` : départ à 0, attente jusqu'à l'ouverture des fenêtres
+        let mut current_time = 0.0;
+
         while self.solution_buffer.len() < num_nodes {
             let current_node = *self.solution_buffer.last().unwrap();
 
-            let next_node = Self::compute_next_node_bfs(
-                current_node,
-                num_nodes,
-                &instance.distance_matrix,
-                &self.pheromone_matrix,
-                self.alpha,
-                self.beta,
-                &mut self.visited_buffer,
-                &mut self.desirability_buffer,
-                &mut self.unvisited_nodes_buffer,
-                &mut self.rng,
-            );
+            let next_node = self.compute_next_node_bfs(current_node, current_time, instance, q0);
 
             self.solution_buffer.push(next_node);
             self.visited_buffer[next_node as usize] = true;
+            let arrival = current_time + instance.time_matrix[[current_node as usize, next_node as usize]];
+            current_time = instance.windows[next_node as usize].service(arrival).0;
+
+            if self.variant_config.variant == ACOVariant::AntColonySystem {
+                self.local_pheromone_update(current_node, next_node);
+            }
+        }
+
+        // Arête de retour vers la ville de départ
+        if self.variant_config.variant == ACOVariant::AntColonySystem && num_nodes > 1 {
+            self.local_pheromone_update(*self.solution_buffer.last().unwrap(), start_node);
         }
     }
 
-    fn compute_next_node_bfs(
+    /// Vrai si, après avoir commencé le service de `candidate` à `service_time`, un client non visité
+    /// encore atteignable directement depuis `current_node` ne l'est plus avant la fin de sa fenêtre
+    fn makes_unreachable(
+        &self,
+        candidate: Ville,
+        service_time: f32,
         current_node: Ville,
-        num_nodes: usize,
-        distance_matrix: &Array2<f32>,
-        pheromone_matrix: &Array2<f32>,
-        alpha: f32,
-        beta: f32,
-        visited_buffer: &mut Vec<bool>,
-        desirability_buffer: &mut Vec<f32>,
-        unvisited_nodes_buffer: &mut Vec<Ville>,
-        rng: &mut StdRng,
-    ) -> Ville {
+        current_time: f32,
+        instance: &Instance,
+    ) -> bool {
+        let latest_arrival = service_time + self.max_distance_from[candidate as usize];
+        for &node in &self.deadline_order {
+            // Une fenêtre souple peut toujours être atteinte, moyennant pénalité
+            let window_end = instance.deadline(node as usize);
+            // Les fenêtres suivantes ferment trop tard pour être manquées
+            if window_end >= latest_arrival {
+                return false;
+            }
+            if node == candidate || self.visited_buffer[node as usize] {
+                continue;
+            }
+            let reachable_now = current_time + instance.time_matrix[[current_node as usize, node as usize]] <= window_end;
+            let reachable_after = service_time + instance.time_matrix[[candidate as usize, node as usize]] <= window_end;
+            if reachable_now && !reachable_after {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn push_candidate(&mut self, node: Ville, current_node: Ville, current_time: f32, instance: &Instance) {
+        let arrival = current_time + instance.time_matrix[[current_node as usize, node as usize]];
+        self.unvisited_nodes_buffer.push(node);
+        self.service_time_buffer.push(instance.windows[node as usize].service(arrival).0);
+    }
+
+    /// Ne garde que les candidats acceptés par `keep`, sauf s'ils sont tous refusés. Retourne vrai s'il en reste.
+    fn retain_candidates(&mut self, keep: impl Fn(&Self, Ville, f32) -> bool) -> bool {
+        let mut kept = 0;
+        for idx in 0..self.unvisited_nodes_buffer.len() {
+            let (node, service_time) = (self.unvisited_nodes_buffer[idx], self.service_time_buffer[idx]);
+            if keep(self, node, service_time) {
+                self.unvisited_nodes_buffer[kept] = node;
+                self.service_time_buffer[kept] = service_time;
+                kept += 1;
+            }
+        }
+        if kept > 0 {
+            self.unvisited_nodes_buffer.truncate(kept);
+            self.service_time_buffer.truncate(kept);
+        }
+        kept > 0
+    }
+
+    /// Retire les candidats qui condamnent un autre client, sauf s'ils le sont tous. Retourne vrai s'il en reste.
+    fn exclude_unreachable(&mut self, current_node: Ville, current_time: f32, instance: &Instance) -> bool {
+        self.retain_candidates(|aco, node, service_time| {
+            !aco.makes_unreachable(node, service_time, current_node, current_time, instance)
+        })
+    }
+
+    /// Retire les candidats servis après la fermeture de leur fenêtre dure, sauf s'ils le sont tous.
+    /// Retourne vrai s'il en reste.
+    fn exclude_late(&mut self, instance: &Instance) -> bool {
+        self.retain_candidates(|_, node, service_time| service_time <= instance.deadline(node as usize))
+    }
+
+    /// Coût heuristique d'un candidat dont le service commence à `service_time` : distance, attente,
+    /// marge restante dans sa fenêtre et, s'il est servi après la fermeture, retard pondéré comme l'attente
+    fn heuristic_cost(&self, current_node: Ville, current_time: f32, node: Ville, service_time: f32, instance: &Instance) -> f32 {
+        let heuristic = self.heuristic_config;
+        let (from, to) = (current_node as usize, node as usize);
+        let waiting = service_time - (current_time + instance.time_matrix[[from, to]]);
+        let window_end = instance.windows[to].service(service_time).1.wend;
+        let slack = (window_end - service_time).max(0.0);
+        let lateness = (service_time - window_end).max(0.0);
+        instance.distance_matrix[[from, to]]
+            + heuristic.waiting_weight * (waiting + lateness)
+            + heuristic.urgency_weight * slack
+    }
+
+    fn compute_next_node_bfs(&mut self, current_node: Ville, current_time: f32, instance: &Instance, q0: f32) -> Ville {
+        let num_nodes = instance.size();
+        let heuristic = self.heuristic_config;
+
         // Réinitialiser les buffers
-        desirability_buffer.clear();
-        unvisited_nodes_buffer.clear();
+        self.desirability_buffer.clear();
+        self.unvisited_nodes_buffer.clear();
+        self.service_time_buffer.clear();
 
-        // Calculer la désirabilité pour les nœuds non visités
-        for node in 0..num_nodes {
-            if !visited_buffer[node] {
-                let pheromone = pheromone_matrix[[current_node as usize, node]];
-                let distance = distance_matrix[[current_node as usize, node]];
-                let value = pheromone.powf(alpha) * (1.0 / (distance + 1e-6)).powf(beta);
-                desirability_buffer.push(value);
-                unvisited_nodes_buffer.push(node as Ville);
+        // Candidats restreints aux listes de l'instance lorsqu'il en reste de non visités
+        let mut found = false;
+        if heuristic.candidate_lists {
+            for &node in instance.candidates.combined(current_node) {
+                if !self.visited_buffer[node as usize] {
+                    self.push_candidate(node, current_node, current_time, instance);
+                }
             }
+            found = !self.unvisited_nodes_buffer.is_empty()
+                && self.exclude_late(instance)
+                && (!heuristic.exclude_unreachable || self.exclude_unreachable(current_node, current_time, instance));
         }
 
-        if unvisited_nodes_buffer.is_empty() {
-            return current_node;
+        // Repli sur l'ensemble des villes non visitées
+        if !found {
+            self.unvisited_nodes_buffer.clear();
+            self.service_time_buffer.clear();
+            for node in 0..num_nodes as Ville {
+                if !self.visited_buffer[node as usize] {
+                    self.push_candidate(node, current_node, current_time, instance);
+                }
+            }
+            if self.unvisited_nodes_buffer.is_empty() {
+                return current_node;
+            }
+            // Si tous les candidats sont en retard ou exclus, on les garde tous
+            self.exclude_late(instance);
+            if heuristic.exclude_unreachable {
+                self.exclude_unreachable(current_node, current_time, instance);
+            }
+        }
+
+        // Désirabilité : phéromone et heuristique combinant distance, attente, urgence de la marge restante et retard
+        for idx in 0..self.unvisited_nodes_buffer.len() {
+            let (node, service_time) = (self.unvisited_nodes_buffer[idx], self.service_time_buffer[idx]);
+            let cost = self.heuristic_cost(current_node, current_time, node, service_time, instance);
+            let pheromone = self.pheromone_matrix[[current_node as usize, node as usize]];
+            let value = pheromone.powf(heuristic.alpha) * (1.0 / (cost + 1e-6)).powf(heuristic.beta);
+            self.desirability_buffer.push(value);
+        }
+
+        let total: f32 = self.desirability_buffer.iter().sum();
+        if total <= 0.0 || !total.is_finite() {
+            let idx = self.rng.random_range(0..self.unvisited_nodes_buffer.len());
+            return self.unvisited_nodes_buffer[idx];
         }
 
-        let total: f32 = desirability_buffer.iter().sum();
-        if total <= 0.0 {
-            let idx = rng.random_range(0..unvisited_nodes_buffer.len());
-            return unvisited_nodes_buffer[idx];
+        // Règle pseudo-aléatoire proportionnelle (ACS) : exploitation directe du meilleur candidat
+        if q0 > 0.0 && self.rng.random::<f32>() < q0 {
+            let best = self
+                .desirability_buffer
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(idx, _)| idx)
+                .unwrap();
+            return self.unvisited_nodes_buffer[best];
         }
 
         // Sélection par roulette wheel
-        let mut pick = rng.random_range(0.0..total);
-        for (idx, &value) in desirability_buffer.iter().enumerate() {
+        let mut pick = self.rng.random_range(0.0..total);
+        for (idx, &value) in self.desirability_buffer.iter().enumerate() {
             pick -= value;
             if pick <= 0.0 {
-                return unvisited_nodes_buffer[idx];
+                return self.unvisited_nodes_buffer[idx];
             }
         }
-        *unvisited_nodes_buffer.last().unwrap()
+        *self.unvisited_nodes_buffer.last().unwrap()
     }
 }
 
@@ -157,7 +447,15 @@ where
         fitness: &mut [Fitness],
         instance: &Instance,
         metric_fn: &Eval,
-    ) -> () {
+    ) {
+        // Enregistre la population entrante avant qu'elle ne soit écrasée
+        self.elite.update(population, fitness, instance, self.iteration);
+
+        if !self.pheromone_initialized && let Some(best) = self.elite.best() {
+            let best_fitness = best.fitness;
+            self.initialize_pheromones(best_fitness, instance.size());
+        }
+
         // Construire les solutions pour chaque fourmi
         for i in 0..population.len() {
             self.construct_solution(instance);
@@ -167,30 +465,79 @@ where
             // Apply local search to improve solution
             self.local_search.search(&mut population[i], &mut fitness[i], instance, metric_fn);
 
-            // Dépôt de phéromones sur le chemin parcouru
-            let pheromone_amount = self.pheromone_deposit / (fitness[i] + 1e-6);
-            for j in 0..population[i].len() {
-                let from = population[i][j] as usize;
-                let to = population[i][(j + 1) % population[i].len()] as usize;
-                self.pheromone_matrix[[from, to]] += pheromone_amount;
-                self.pheromone_matrix[[to, from]] += pheromone_amount;
+            // Ant System : dépôt de phéromones sur le chemin parcouru par chaque fourmi
+            if self.variant_config.variant == ACOVariant::AntSystem {
+                let pheromone_amount = self.variant_config.pheromone_deposit / (fitness[i] + 1e-6);
+                Self::reinforce_tour(&mut self.pheromone_matrix, &population[i], 1.0, pheromone_amount, self.symmetric);
             }
         }
 
-        // Évaporation des phéromones
-        self.pheromone_matrix *= 1.0 - self.evaporation_rate;
         self.iteration += 1;
+        let improved = self.elite.update(population, fitness, instance, self.iteration);
+
+        match self.variant_config.variant {
+            ACOVariant::AntSystem => {
+                // Évaporation des phéromones
+                self.pheromone_matrix *= 1.0 - self.variant_config.evaporation_rate;
+            }
+            ACOVariant::MaxMin => {
+                // Seule la meilleure fourmi de l'itération dépose, puis les phéromones sont bornées
+                let iteration_best = fitness
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(idx, _)| idx);
+                if let Some(best) = self.elite.best() {
+                    let best_fitness = best.fitness;
+                    self.update_pheromone_levels(best_fitness, instance.size());
+                }
+                self.pheromone_matrix *= 1.0 - self.variant_config.evaporation_rate;
+                if let Some(idx) = iteration_best {
+                    let pheromone_amount = self.variant_config.pheromone_deposit / (fitness[idx] + 1e-6);
+                    Self::reinforce_tour(&mut self.pheromone_matrix, &population[idx], 1.0, pheromone_amount, self.symmetric);
+                }
+                let (tau_min, tau_max) = (self.tau_min, self.tau_max);
+                self.pheromone_matrix.mapv_inplace(|tau| tau.clamp(tau_min, tau_max));
+
+                // Réinitialisation sur stagnation de la meilleure solution
+                self.stagnation = if improved { 0 } else { self.stagnation + 1 };
+                let limit = self.variant_config.stagnation_limit;
+                if limit > 0 && self.stagnation >= limit {
+                    self.pheromone_matrix.fill(self.tau_max);
+                    self.stagnation = 0;
+                    self.reinitializations += 1;
+                    self.events.push(AlgoEvent {
+                        iteration: self.iteration,
+                        description: format!("Pheromone reinitialization #{}", self.reinitializations),
+                    });
+                }
+            }
+            ACOVariant::AntColonySystem => {
+                // Mise à jour globale sur les arêtes de la meilleure solution uniquement
+                if let Some(best) = self.elite.best() {
+                    let rho = self.variant_config.evaporation_rate;
+                    let pheromone_amount = rho * self.variant_config.pheromone_deposit / (best.fitness + 1e-6);
+                    Self::reinforce_tour(&mut self.pheromone_matrix, &best.solution, 1.0 - rho, pheromone_amount, self.symmetric);
+                }
+            }
+        }
     }
 
     fn get_metrics(&self) -> HashMap<String, f32> {
         let mut metrics = HashMap::new();
         let avg_pheromone: f32 = self.pheromone_matrix.sum() / (self.pheromone_matrix.len() as f32);
         metrics.insert("Average Pheromone".to_string(), avg_pheromone);
+        if self.variant_config.variant == ACOVariant::MaxMin {
+            metrics.insert("Reinitializations".to_string(), self.reinitializations as f32);
+        }
         metrics
     }
 
     fn get_metric_names(&self) -> Vec<String> {
-        vec!["Average Pheromone".to_string()]
+        match self.variant_config.variant {
+            ACOVariant::MaxMin => vec!["Average Pheromone".to_string(), "Reinitializations".to_string()],
+            _ => vec!["Average Pheromone".to_string()],
+        }
     }
 
     fn stop_condition_met(&self) -> bool {
@@ -199,4 +546,260 @@ where
     fn get_iteration(&self) -> usize {
         self.iteration
     }
+    fn get_best_solution(&self) -> Option<&Elite> {
+        self.elite.best()
+    }
+    fn take_events(&mut self) -> Vec<AlgoEvent> {
+        std::mem::take(&mut self.events)
+    }
+    fn get_pheromone_matrix(&self) -> Option<&Array2<f32>> {
+        Some(&self.pheromone_matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{Objective, Weighted};
+    use crate::shared::{TimeWindows, Window};
+
+    const SIZE: usize = 6;
+
+    // Recherche locale qui ne touche pas aux solutions
+    struct NoSearch;
+
+    impl<E: Evaluation> LocalSearch<E> for NoSearch {
+        fn search(&mut self, _: &mut Solution, _: &mut Fitness, _: &Instance, _: &E) {}
+        fn reset(&mut self) {}
+        fn change_neighborhood(&mut self, _: crate::neighborhood::Neighborhood) {}
+    }
+
+    // Villes sur une droite, fenêtres larges ; l'aller vers une ville d'indice plus élevé coûte le double
+    fn line_instance(symmetric: bool) -> Instance {
+        let distances = Array2::from_shape_fn((SIZE, SIZE), |(i, j)| {
+            let d = (i as f32 - j as f32).abs() * 10.0;
+            if symmetric || j < i { d } else { 2.0 * d }
+        });
+        let windows = (0..SIZE).map(|_| TimeWindows::single(Window { wstart: 0.0, wend: 1000.0 })).collect();
+        Instance::new(windows, distances)
+    }
+
+    fn distance() -> Weighted {
+        Weighted::new(vec![(Objective::Distance, 1.0)])
+    }
+
+    // Une population de départ quelconque, évaluée
+    fn population(instance: &Instance, ants: usize) -> (Vec<Solution>, Vec<Fitness>) {
+        let population: Vec<Solution> = (0..ants).map(|_| (0..SIZE as Ville).collect()).collect();
+        let fitness = population.iter().map(|s| distance().score(instance, s)).collect();
+        (population, fitness)
+    }
+
+    fn aco(instance: &Instance, variant_config: ACOVariantConfig) -> ACO<NoSearch> {
+        ACO::new(instance, variant_config, ACOHeuristicConfig::default(), 100, NoSearch)
+    }
+
+    #[test]
+    fn reinforces_only_the_travelled_direction() {
+        let tour: Solution = vec![0, 2, 1];
+        let mut directed = Array2::zeros((3, 3));
+        ACO::<NoSearch>::reinforce_tour(&mut directed, &tour, 1.0, 1.0, false);
+        let expected = ndarray::array![[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        assert_eq!(directed, expected);
+
+        let mut undirected = Array2::zeros((3, 3));
+        ACO::<NoSearch>::reinforce_tour(&mut undirected, &tour, 1.0, 1.0, true);
+        assert_eq!(undirected, &expected + &expected.t());
+
+        // keep atténue la valeur précédente avant le dépôt
+        ACO::<NoSearch>::reinforce_tour(&mut directed, &tour, 0.5, 2.0, false);
+        assert_eq!(directed[[0, 2]], 2.5);
+        assert_eq!(directed[[2, 0]], 0.0);
+    }
+
+    #[test]
+    fn ants_deposit_along_their_direction_on_asymmetric_instances() {
+        let instance = line_instance(false);
+        assert!(!instance.symmetric);
+        let config = ACOVariantConfig { evaporation_rate: 0.0, ..Default::default() };
+        let mut aco = aco(&instance, config);
+        let before = aco.pheromone_matrix.clone();
+
+        let (mut population, mut fitness) = population(&instance, 4);
+        aco.step(&mut population, &mut fitness, &instance, &distance());
+
+        let travelled = |a: usize, b: usize| {
+            population.iter().any(|tour| (0..SIZE).any(|k| tour[k] as usize == a && tour[(k + 1) % SIZE] as usize == b))
+        };
+        for ((a, b), &value) in aco.pheromone_matrix.indexed_iter() {
+            assert_eq!(value > before[[a, b]], travelled(a, b), "edge {} -> {}", a, b);
+        }
+    }
+
+    #[test]
+    fn ants_deposit_both_ways_on_symmetric_instances() {
+        let instance = line_instance(true);
+        assert!(instance.symmetric);
+        let config = ACOVariantConfig { evaporation_rate: 0.0, ..Default::default() };
+        let mut aco = aco(&instance, config);
+
+        let (mut population, mut fitness) = population(&instance, 4);
+        aco.step(&mut population, &mut fitness, &instance, &distance());
+        assert_eq!(aco.pheromone_matrix, aco.pheromone_matrix.t());
+    }
+
+    // Villes 0, 1, 2, 3 aux abscisses 0, 10, 20, 30, avec les fenêtres données
+    fn windows_instance(windows: &[(f32, f32)]) -> Instance {
+        let size = windows.len();
+        let distances = Array2::from_shape_fn((size, size), |(i, j)| (i as f32 - j as f32).abs() * 10.0);
+        let windows = windows.iter().map(|&(wstart, wend)| TimeWindows::single(Window { wstart, wend })).collect();
+        Instance::new(windows, distances)
+    }
+
+    // Fourmi partie du dépôt à l'instant 0
+    fn ant_at_depot(instance: &Instance, heuristic_config: ACOHeuristicConfig) -> ACO<NoSearch> {
+        let mut aco = ACO::new(instance, ACOVariantConfig::default(), heuristic_config, 100, NoSearch);
+        aco.visited_buffer[0] = true;
+        aco
+    }
+
+    #[test]
+    fn heuristic_cost_combines_distance_waiting_slack_and_lateness() {
+        let instance = windows_instance(&[(0.0, 1000.0), (20.0, 40.0), (0.0, 5.0)]);
+        let aco = ant_at_depot(&instance, ACOHeuristicConfig::default());
+        // Ville 1 : 10 de trajet, 10 d'attente, 20 de marge
+        assert_eq!(aco.heuristic_cost(0, 0.0, 1, 20.0, &instance), 40.0);
+        // Ville 2 : 20 de trajet, fenêtre fermée depuis 15
+        assert_eq!(aco.heuristic_cost(0, 0.0, 2, 20.0, &instance), 35.0);
+        assert!(aco.heuristic_cost(0, 10.0, 2, 30.0, &instance) > aco.heuristic_cost(0, 0.0, 2, 20.0, &instance));
+
+        let weighted = ACOHeuristicConfig { waiting_weight: 2.0, urgency_weight: 0.5, ..Default::default() };
+        let aco = ant_at_depot(&instance, weighted);
+        assert_eq!(aco.heuristic_cost(0, 0.0, 1, 20.0, &instance), 10.0 + 2.0 * 10.0 + 0.5 * 20.0);
+        assert_eq!(aco.heuristic_cost(0, 0.0, 2, 20.0, &instance), 20.0 + 2.0 * 15.0);
+    }
+
+    #[test]
+    fn ants_skip_closed_windows_while_others_are_open() {
+        // La ville 1, la plus proche, ferme avant qu'on puisse l'atteindre
+        let instance = windows_instance(&[(0.0, 1000.0), (0.0, 5.0), (0.0, 100.0), (0.0, 100.0)]);
+        for candidate_lists in [true, false] {
+            let heuristic = ACOHeuristicConfig { candidate_lists, ..Default::default() };
+            let mut aco = ant_at_depot(&instance, heuristic);
+            for _ in 0..200 {
+                assert_ne!(aco.compute_next_node_bfs(0, 0.0, &instance, 0.0), 1);
+            }
+        }
+
+        // Tous en retard : la fourmi continue quand même
+        let instance = windows_instance(&[(0.0, 1000.0), (0.0, 5.0), (0.0, 5.0)]);
+        let mut aco = ant_at_depot(&instance, ACOHeuristicConfig::default());
+        assert_ne!(aco.compute_next_node_bfs(0, 0.0, &instance, 0.0), 0);
+    }
+
+    #[test]
+    fn detects_candidates_that_strand_a_customer() {
+        // La ville 1 ferme à 15 : y aller d'abord est possible, pas après la ville 2
+        let instance = windows_instance(&[(0.0, 1000.0), (0.0, 15.0), (0.0, 100.0)]);
+        let mut aco = ant_at_depot(&instance, ACOHeuristicConfig::default());
+        assert!(aco.makes_unreachable(2, 20.0, 0, 0.0, &instance));
+        assert!(!aco.makes_unreachable(1, 10.0, 0, 0.0, &instance));
+        // Un client déjà inatteignable n'est pas protégé
+        assert!(!aco.makes_unreachable(2, 30.0, 0, 10.0, &instance));
+        // Un client déjà visité non plus
+        aco.visited_buffer[1] = true;
+        assert!(!aco.makes_unreachable(2, 20.0, 0, 0.0, &instance));
+
+        // Une fenêtre souple reste toujours atteignable
+        let mut soft = instance.clone();
+        soft.policies[1].kind = crate::shared::WindowKind::SoftLinear;
+        let aco = ant_at_depot(&soft, ACOHeuristicConfig::default());
+        assert!(!aco.makes_unreachable(2, 20.0, 0, 0.0, &soft));
+    }
+
+    #[test]
+    fn candidates_stranding_a_customer_are_excluded() {
+        let instance = windows_instance(&[(0.0, 1000.0), (0.0, 15.0), (0.0, 100.0)]);
+        let mut aco = ant_at_depot(&instance, ACOHeuristicConfig::default());
+        for _ in 0..200 {
+            assert_eq!(aco.compute_next_node_bfs(0, 0.0, &instance, 0.0), 1);
+        }
+    }
+
+    #[test]
+    fn max_min_clamps_pheromones() {
+        let instance = line_instance(true);
+        let config = ACOVariantConfig { variant: ACOVariant::MaxMin, stagnation_limit: 0, ..Default::default() };
+        let mut aco = aco(&instance, config);
+        let (mut population, mut fitness) = population(&instance, 4);
+        for _ in 0..20 {
+            aco.step(&mut population, &mut fitness, &instance, &distance());
+            let (tau_min, tau_max) = (aco.tau_min, aco.tau_max);
+            assert!(0.0 < tau_min && tau_min < tau_max && tau_max.is_finite());
+            assert!(aco.pheromone_matrix.iter().all(|&tau| tau_min <= tau && tau <= tau_max));
+        }
+
+        // tau_max = deposit / (rho * meilleure fitness)
+        let best = aco.elite.best().unwrap().fitness;
+        let expected = config.pheromone_deposit / (config.evaporation_rate * best);
+        assert!((aco.tau_max - expected).abs() <= 1e-6 * expected);
+    }
+
+    #[test]
+    fn max_min_reinitializes_on_stagnation() {
+        let instance = line_instance(true);
+        let config = ACOVariantConfig { variant: ACOVariant::MaxMin, stagnation_limit: 3, ..Default::default() };
+        let mut aco = aco(&instance, config);
+        // Population de départ optimale : les fourmis ne peuvent plus l'améliorer
+        let (mut population, mut fitness) = population(&instance, 4);
+        for _ in 0..config.stagnation_limit - 1 {
+            aco.step(&mut population, &mut fitness, &instance, &distance());
+        }
+        assert!(Metaheuristic::<Weighted>::take_events(&mut aco).is_empty());
+
+        aco.step(&mut population, &mut fitness, &instance, &distance());
+        let events = Metaheuristic::<Weighted>::take_events(&mut aco);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].iteration, config.stagnation_limit);
+        assert_eq!(aco.reinitializations, 1);
+        let tau_max = aco.tau_max;
+        assert!(aco.pheromone_matrix.iter().all(|&tau| tau == tau_max));
+    }
+
+    #[test]
+    fn colony_system_local_update_moves_towards_tau_0() {
+        for symmetric in [true, false] {
+            let instance = line_instance(symmetric);
+            let config = ACOVariantConfig { variant: ACOVariant::AntColonySystem, ..Default::default() };
+            let mut aco = aco(&instance, config);
+            aco.initialize_pheromones(100.0, SIZE);
+            let tau_0 = aco.tau_0;
+            assert_eq!(tau_0, config.pheromone_deposit / (SIZE as f32 * 100.0));
+            assert!(aco.pheromone_matrix.iter().all(|&tau| tau == tau_0));
+
+            aco.pheromone_matrix[[1, 2]] = 1.0;
+            aco.pheromone_matrix[[2, 1]] = 1.0;
+            aco.local_pheromone_update(1, 2);
+            let xi = config.local_evaporation_rate;
+            assert_eq!(aco.pheromone_matrix[[1, 2]], (1.0 - xi) + xi * tau_0);
+            // Le sens inverse ne suit que sur une instance symétrique
+            let reverse = if symmetric { (1.0 - xi) + xi * tau_0 } else { 1.0 };
+            assert_eq!(aco.pheromone_matrix[[2, 1]], reverse);
+        }
+    }
+
+    #[test]
+    fn colony_system_exploits_the_best_candidate_with_q0() {
+        let instance = windows_instance(&[(0.0, 1000.0), (0.0, 1000.0), (0.0, 1000.0), (0.0, 1000.0)]);
+        let heuristic = ACOHeuristicConfig { candidate_lists: false, urgency_weight: 0.0, ..Default::default() };
+        let mut aco = ant_at_depot(&instance, heuristic);
+        aco.pheromone_matrix.fill(1.0);
+        // q0 = 1 : toujours la ville la plus désirable, ici la plus proche
+        for _ in 0..100 {
+            assert_eq!(aco.compute_next_node_bfs(0, 0.0, &instance, 1.0), 1);
+        }
+        // q0 = 0 : roulette, les autres villes finissent par sortir
+        let picks: Vec<Ville> = (0..500).map(|_| aco.compute_next_node_bfs(0, 0.0, &instance, 0.0)).collect();
+        assert!(picks.contains(&2) && picks.contains(&3));
+    }
 }
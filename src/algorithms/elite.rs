@@ -0,0 +1,94 @@
+use crate::eval::utils::{Eval, run_solution};
+use crate::shared::{Fitness, Instance, Solution};
+
+use std::time::{Duration, Instant};
+
+/// Meilleure solution jamais rencontrée par un algorithme, avec le détail de son évaluation
+/// ainsi que l'itération et le temps écoulé au moment où elle a été trouvée pour la première fois.
+#[derive(Clone)]
+pub struct Elite {
+    pub solution: Solution,
+    pub fitness: Fitness,
+    pub eval: Eval,
+    pub iteration: usize,
+    pub elapsed: Duration,
+}
+
+/// Suit la meilleure solution jamais rencontrée, indépendamment du remplacement de la population.
+///
+/// `run_solution` n'est appelé que lorsqu'une amélioration stricte de la fitness est observée.
+#[derive(Clone)]
+pub struct EliteTracker {
+    best: Option<Elite>,
+    start: Instant,
+}
+
+impl Default for EliteTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EliteTracker {
+    pub fn new() -> Self {
+        EliteTracker {
+            best: None,
+            start: Instant::now(),
+        }
+    }
+
+    /// Propose une solution, retourne `true` si elle devient la nouvelle élite.
+    pub fn offer(
+        &mut self,
+        solution: &[u32],
+        fitness: Fitness,
+        instance: &Instance,
+        iteration: usize,
+    ) -> bool {
+        if self.best.as_ref().is_some_and(|best| fitness >= best.fitness) {
+            return false;
+        }
+
+        let solution = solution.to_vec();
+        let eval = run_solution(instance, &solution);
+        self.best = Some(Elite {
+            solution,
+            fitness,
+            eval,
+            iteration,
+            elapsed: self.start.elapsed(),
+        });
+        true
+    }
+
+    /// Propose le meilleur individu de la population, retourne `true` si l'élite a changé.
+    pub fn update(
+        &mut self,
+        population: &[Solution],
+        fitnesses: &[Fitness],
+        instance: &Instance,
+        iteration: usize,
+    ) -> bool {
+        let best_idx = fitnesses
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(idx, _)| idx);
+
+        match best_idx {
+            Some(idx) if idx < population.len() => {
+                self.offer(&population[idx], fitnesses[idx], instance, iteration)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn best(&self) -> Option<&Elite> {
+        self.best.as_ref()
+    }
+
+    pub fn reset(&mut self) {
+        self.best = None;
+        self.start = Instant::now();
+    }
+}
@@ -0,0 +1,739 @@
+use crate::shared::{Instance, Solution, Ville};
+
+use super::GeneticAlgorithm;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+
+impl<LS> GeneticAlgorithm<LS> {
+    pub(super) fn ox_crossover(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child_routes: &mut [Solution],
+        child1_visited_buffer: &mut [bool],
+        child2_visited_buffer: &mut [bool],
+        rng: &mut StdRng,
+    ) {
+        // Order Crossover (OX) logic
+        let size = parent1.len();
+
+        child1_visited_buffer.fill(false);
+        child2_visited_buffer.fill(false);
+
+        let start = rng.random_range(0..size);
+        let end = rng.random_range(start..size);
+
+        for i in start..end {
+            child_routes[0][i] = parent1[i];
+            child_routes[1][i] = parent2[i];
+
+            child1_visited_buffer[parent1[i] as usize] = true;
+            child2_visited_buffer[parent2[i] as usize] = true;
+        }
+
+        let mut current_pos1 = end % size;
+        let mut current_pos2 = end % size;
+
+        for i in 0..size {
+            let idx = (end + i) % size;
+
+            if !child1_visited_buffer[parent2[idx] as usize] {
+                child_routes[0][current_pos1] = parent2[idx];
+                current_pos1 = (current_pos1 + 1) % size;
+            }
+
+            if !child2_visited_buffer[parent1[idx] as usize] {
+                child_routes[1][current_pos2] = parent1[idx];
+                current_pos2 = (current_pos2 + 1) % size;
+            }
+        }
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    fn pmx_resolve_mapping(mapping: &[Ville], start: Ville) -> Ville {
+        let mut current = start;
+        let max_iter = mapping.len() + 1;
+
+        for _ in 0..max_iter {
+            let mapped = mapping[current as usize];
+            if mapped == u32::MAX {
+                return current;
+            }
+            current = mapped;
+        }
+        current
+    }
+
+    pub(super) fn pmx_crossover(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child_routes: &mut [Solution],
+        child1_mapping_buffer: &mut [Ville],
+        child2_mapping_buffer: &mut [Ville],
+        rng: &mut StdRng,
+    ) {
+        child1_mapping_buffer.fill(u32::MAX);
+        child2_mapping_buffer.fill(u32::MAX);
+
+        // PMX crossover logic
+        let size = parent1.len();
+
+        let start = rng.random_range(0..size);
+        let end = rng.random_range(start..size);
+
+        // Copier le segment et créer le mapping
+        for i in start..end {
+            let val1 = parent1[i];
+            let val2 = parent2[i];
+
+            child_routes[0][i] = val1;
+            child_routes[1][i] = val2;
+
+            // Créer le mapping: dans child1, val1 mappe vers val2 (et vice versa)
+            child1_mapping_buffer[val1 as usize] = val2;
+            child2_mapping_buffer[val2 as usize] = val1;
+        }
+
+        // Remplir le reste en utilisant le mapping PMX
+        for i in 0..size {
+            if i >= start && i < end {
+                continue; // Skip le segment déjà copié
+            }
+
+            // Pour child1, prendre de parent2
+            let mut val2 = parent2[i];
+            if child1_mapping_buffer[val2 as usize] != u32::MAX {
+                // Suivre le mapping jusqu'à trouver une valeur non utilisée
+                val2 = Self::pmx_resolve_mapping(child1_mapping_buffer, val2);
+            }
+            child_routes[0][i] = val2;
+            child1_mapping_buffer[val2 as usize] = val2;
+
+            // Pour child2, prendre de parent1
+            let mut val1 = parent1[i];
+            if child2_mapping_buffer[val1 as usize] != u32::MAX {
+                // Suivre le mapping jusqu'à trouver une valeur non utilisée
+                val1 = Self::pmx_resolve_mapping(child2_mapping_buffer, val1);
+            }
+            child_routes[1][i] = val1;
+            child2_mapping_buffer[val1 as usize] = val1;
+        }
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Construit la table des arêtes adjacentes à chaque ville dans l'un ou l'autre des parents (au plus 4 voisins)
+    fn erx_build_edge_map(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        edge_map_buffer: &mut [[Ville; 4]],
+        edge_count_buffer: &mut [u8],
+    ) {
+        edge_count_buffer.fill(0);
+
+        for parent in [parent1, parent2] {
+            let size = parent.len();
+            for i in 0..size {
+                let node = parent[i] as usize;
+                for neighbor in [parent[(i + size - 1) % size], parent[(i + 1) % size]] {
+                    let count = edge_count_buffer[node] as usize;
+                    if !edge_map_buffer[node][..count].contains(&neighbor) {
+                        edge_map_buffer[node][count] = neighbor;
+                        edge_count_buffer[node] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Construit un enfant ERX à partir de `start` : on suit de préférence le voisin ayant le moins
+    /// de voisins non visités, et on repart d'une ville aléatoire non visitée en cas d'impasse.
+    fn erx_child(
+        start: Ville,
+        child: &mut [Ville],
+        edge_map_buffer: &[[Ville; 4]],
+        edge_count_buffer: &[u8],
+        visited_buffer: &mut [bool],
+        rng: &mut StdRng,
+    ) {
+        let size = child.len();
+        visited_buffer.fill(false);
+
+        let mut current = start;
+        for (k, slot) in child.iter_mut().enumerate() {
+            *slot = current;
+            visited_buffer[current as usize] = true;
+
+            if k + 1 == size {
+                break;
+            }
+
+            let mut next = None;
+            let mut next_degree = usize::MAX;
+            let mut ties = 0;
+            let neighbors = &edge_map_buffer[current as usize][..edge_count_buffer[current as usize] as usize];
+            for &neighbor in neighbors {
+                if visited_buffer[neighbor as usize] {
+                    continue;
+                }
+                let degree = edge_map_buffer[neighbor as usize][..edge_count_buffer[neighbor as usize] as usize]
+                    .iter()
+                    .filter(|&&n| !visited_buffer[n as usize])
+                    .count();
+
+                if degree < next_degree {
+                    next = Some(neighbor);
+                    next_degree = degree;
+                    ties = 1;
+                } else if degree == next_degree {
+                    // Départage uniforme entre les voisins ex aequo
+                    ties += 1;
+                    if rng.random_range(0..ties) == 0 {
+                        next = Some(neighbor);
+                    }
+                }
+            }
+
+            current = match next {
+                Some(neighbor) => neighbor,
+                None => {
+                    let offset = rng.random_range(0..size);
+                    (0..size)
+                        .map(|d| ((offset + d) % size) as Ville)
+                        .find(|&node| !visited_buffer[node as usize])
+                        .unwrap()
+                }
+            };
+        }
+    }
+
+    /// Edge Recombination Crossover (ERX) : les enfants réutilisent en priorité les arêtes des deux parents
+    pub(super) fn erx_crossover(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child_routes: &mut [Solution],
+        edge_map_buffer: &mut [[Ville; 4]],
+        edge_count_buffer: &mut [u8],
+        visited_buffer: &mut [bool],
+        rng: &mut StdRng,
+    ) {
+        Self::erx_build_edge_map(parent1, parent2, edge_map_buffer, edge_count_buffer);
+
+        Self::erx_child(parent1[0], &mut child_routes[0], edge_map_buffer, edge_count_buffer, visited_buffer, rng);
+        Self::erx_child(parent2[0], &mut child_routes[1], edge_map_buffer, edge_count_buffer, visited_buffer, rng);
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Cycle Crossover (CX) : chaque position conserve la ville de l'un des parents, les cycles de positions
+    /// étant hérités alternativement de parent1 et de parent2.
+    pub(super) fn cx_crossover(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child_routes: &mut [Solution],
+        position_buffer: &mut [usize],
+        visited_buffer: &mut [bool],
+    ) {
+        let size = parent1.len();
+
+        for (i, &node) in parent1.iter().enumerate() {
+            position_buffer[node as usize] = i;
+        }
+        visited_buffer.fill(false);
+
+        let mut cycle = 0;
+        for start in 0..size {
+            if visited_buffer[start] {
+                continue;
+            }
+
+            let mut i = start;
+            loop {
+                visited_buffer[i] = true;
+                if cycle % 2 == 0 {
+                    child_routes[0][i] = parent1[i];
+                    child_routes[1][i] = parent2[i];
+                } else {
+                    child_routes[0][i] = parent2[i];
+                    child_routes[1][i] = parent1[i];
+                }
+
+                i = position_buffer[parent2[i] as usize];
+                if i == start {
+                    break;
+                }
+            }
+            cycle += 1;
+        }
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Calcule l'heure de début de service de chaque ville dans la tournée (indexée par ville)
+    fn service_times(instance: &Instance, route: &[Ville], times_buffer: &mut [f32]) {
+        let mut time = 0.0;
+        times_buffer[route[0] as usize] = 0.0;
+
+        for i in 1..route.len() {
+            let from = route[i - 1] as usize;
+            let to = route[i] as usize;
+            time += instance.time_matrix[[from, to]];
+            time = instance.windows[to].service(time).0;
+            times_buffer[to] = time;
+        }
+    }
+
+    /// Fusionne les deux parents comme deux listes triées par `key1` / `key2`, en sautant les villes déjà placées
+    fn merge_by_key(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child: &mut [Ville],
+        key1: impl Fn(Ville) -> f32,
+        key2: impl Fn(Ville) -> f32,
+        visited_buffer: &mut [bool],
+    ) {
+        let size = parent1.len();
+        visited_buffer.fill(false);
+
+        let (mut i, mut j) = (0, 0);
+        for slot in child.iter_mut().take(size) {
+            while i < size && visited_buffer[parent1[i] as usize] {
+                i += 1;
+            }
+            while j < size && visited_buffer[parent2[j] as usize] {
+                j += 1;
+            }
+
+            let node = if i >= size {
+                parent2[j]
+            } else if j >= size || key1(parent1[i]) <= key2(parent2[j]) {
+                parent1[i]
+            } else {
+                parent2[j]
+            };
+
+            *slot = node;
+            visited_buffer[node as usize] = true;
+        }
+    }
+
+    /// Merge crossover sensible aux fenêtres de temps : le premier enfant fusionne les parents selon
+    /// l'heure d'arrivée de chaque ville dans son parent, le second selon la fin de sa dernière fenêtre de temps.
+    /// Le second enfant ne dépend donc que de l'ordre des parents, pas de leurs horaires : c'est voulu,
+    /// deux enfants tirés de la même clé ne différeraient qu'aux égalités.
+    pub(super) fn tw_merge_crossover(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child_routes: &mut [Solution],
+        instance: &Instance,
+        arrival1_buffer: &mut [f32],
+        arrival2_buffer: &mut [f32],
+        visited_buffer: &mut [bool],
+    ) {
+        Self::service_times(instance, parent1, arrival1_buffer);
+        Self::service_times(instance, parent2, arrival2_buffer);
+
+        let arrival1: &[f32] = arrival1_buffer;
+        let arrival2: &[f32] = arrival2_buffer;
+        Self::merge_by_key(
+            parent1,
+            parent2,
+            &mut child_routes[0],
+            |node| arrival1[node as usize],
+            |node| arrival2[node as usize],
+            visited_buffer,
+        );
+
+        let deadline = |node: Ville| instance.windows[node as usize].last_end();
+        Self::merge_by_key(parent1, parent2, &mut child_routes[1], deadline, deadline, visited_buffer);
+    }
+}
+
+/// Buffers de l'Edge Assembly Crossover, alloués une seule fois par algorithme
+pub(super) struct EaxWorkspace {
+    adjacency_a: Vec<[Ville; 2]>,
+    adjacency_b: Vec<[Ville; 2]>,
+    remaining_a: Vec<[Ville; 2]>,
+    remaining_a_len: Vec<u8>,
+    remaining_b: Vec<[Ville; 2]>,
+    remaining_b_len: Vec<u8>,
+    adjacency_child: Vec<[Ville; 2]>,
+    ab_cycle: Vec<Ville>,
+    subtour: Vec<usize>,
+}
+
+impl EaxWorkspace {
+    pub(super) fn new(size: usize) -> Self {
+        EaxWorkspace {
+            adjacency_a: vec![[0; 2]; size],
+            adjacency_b: vec![[0; 2]; size],
+            remaining_a: vec![[0; 2]; size],
+            remaining_a_len: vec![0; size],
+            remaining_b: vec![[0; 2]; size],
+            remaining_b_len: vec![0; size],
+            adjacency_child: vec![[0; 2]; size],
+            ab_cycle: Vec::with_capacity(2 * size + 1),
+            subtour: vec![0; size],
+        }
+    }
+
+    fn fill_adjacency(route: &[Ville], adjacency: &mut [[Ville; 2]]) {
+        let size = route.len();
+        for i in 0..size {
+            adjacency[route[i] as usize] = [route[(i + size - 1) % size], route[(i + 1) % size]];
+        }
+    }
+
+    /// Arêtes de `own` absentes de `other` : les arêtes communes ne peuvent pas appartenir à un AB-cycle
+    fn fill_remaining(own: &[[Ville; 2]], other: &[[Ville; 2]], remaining: &mut [[Ville; 2]], remaining_len: &mut [u8]) {
+        for node in 0..own.len() {
+            remaining_len[node] = 0;
+            for neighbor in own[node] {
+                if !other[node].contains(&neighbor) {
+                    remaining[node][remaining_len[node] as usize] = neighbor;
+                    remaining_len[node] += 1;
+                }
+            }
+        }
+    }
+
+    fn remove_edge(remaining: &mut [[Ville; 2]], remaining_len: &mut [u8], u: Ville, v: Ville) {
+        for (from, to) in [(u, v), (v, u)] {
+            let len = remaining_len[from as usize] as usize;
+            let slots = &mut remaining[from as usize];
+            if let Some(pos) = slots[..len].iter().position(|&n| n == to) {
+                slots[pos] = slots[len - 1];
+                remaining_len[from as usize] -= 1;
+            }
+        }
+    }
+
+    fn pick_edge(remaining: &[[Ville; 2]], remaining_len: &[u8], node: Ville, rng: &mut StdRng) -> Ville {
+        let len = remaining_len[node as usize] as usize;
+        remaining[node as usize][rng.random_range(0..len)]
+    }
+
+    fn replace_neighbor(adjacency: &mut [[Ville; 2]], node: Ville, old: Ville, new: Ville) {
+        let slots = &mut adjacency[node as usize];
+        if slots[0] == old {
+            slots[0] = new;
+        } else {
+            slots[1] = new;
+        }
+    }
+
+    /// Numérote les sous-tours du graphe 2-régulier de l'enfant, retourne leur nombre
+    fn label_subtours(&mut self) -> usize {
+        let size = self.adjacency_child.len();
+        self.subtour.fill(usize::MAX);
+
+        let mut count = 0;
+        for start in 0..size {
+            if self.subtour[start] != usize::MAX {
+                continue;
+            }
+            let (mut prev, mut current) = (start, self.adjacency_child[start][0] as usize);
+            self.subtour[start] = count;
+            while current != start {
+                self.subtour[current] = count;
+                let [n0, n1] = self.adjacency_child[current];
+                let next = if n0 as usize != prev { n0 as usize } else { n1 as usize };
+                (prev, current) = (current, next);
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Fusionne les sous-tours en reconnectant le plus petit au reste par l'échange 2-opt le moins coûteux.
+    /// Le sens de parcours n'étant fixé qu'à la fin, une arête coûte sur une instance asymétrique
+    /// le moins cher de ses deux sens
+    fn merge_subtours(&mut self, instance: &Instance) {
+        let size = self.adjacency_child.len();
+        let directed = |a: Ville, b: Ville| instance.distance_matrix[[a as usize, b as usize]];
+        let dist = |a: Ville, b: Ville| {
+            if instance.symmetric { directed(a, b) } else { directed(a, b).min(directed(b, a)) }
+        };
+
+        while self.label_subtours() > 1 {
+            let mut subtour_sizes = vec![0usize; size];
+            for &label in &self.subtour {
+                subtour_sizes[label] += 1;
+            }
+            let smallest = (0..size)
+                .filter(|&label| subtour_sizes[label] > 0)
+                .min_by_key(|&label| subtour_sizes[label])
+                .unwrap();
+
+            // (coût, a, b, c, d, croisé)
+            let mut best: Option<(f32, Ville, Ville, Ville, Ville, bool)> = None;
+            for a in 0..size {
+                if self.subtour[a] != smallest {
+                    continue;
+                }
+                for b in self.adjacency_child[a] {
+                    for c in 0..size {
+                        if self.subtour[c] == smallest {
+                            continue;
+                        }
+                        for d in self.adjacency_child[c] {
+                            let (a, c) = (a as Ville, c as Ville);
+                            let removed = dist(a, b) + dist(c, d);
+                            let straight = dist(a, c) + dist(b, d) - removed;
+                            let crossed = dist(a, d) + dist(b, c) - removed;
+                            let (cost, is_crossed) = if crossed < straight { (crossed, true) } else { (straight, false) };
+                            if best.is_none_or(|(best_cost, ..)| cost < best_cost) {
+                                best = Some((cost, a, b, c, d, is_crossed));
+                            }
+                        }
+                    }
+                }
+            }
+
+            let (_, a, b, c, d, is_crossed) = best.unwrap();
+            let (new_a, new_b) = if is_crossed { (d, c) } else { (c, d) };
+            Self::replace_neighbor(&mut self.adjacency_child, a, b, new_a);
+            Self::replace_neighbor(&mut self.adjacency_child, b, a, new_b);
+            Self::replace_neighbor(&mut self.adjacency_child, c, d, if is_crossed { b } else { a });
+            Self::replace_neighbor(&mut self.adjacency_child, d, c, if is_crossed { a } else { b });
+        }
+    }
+
+    /// Produit un enfant EAX (stratégie « un seul AB-cycle ») : un AB-cycle alternant arêtes de A et de B
+    /// est appliqué à A, puis les sous-tours obtenus sont fusionnés de manière gloutonne.
+    fn build_child(&mut self, parent_a: &[Ville], parent_b: &[Ville], child: &mut [Ville], instance: &Instance, rng: &mut StdRng) {
+        let size = parent_a.len();
+        if size < 4 {
+            child.copy_from_slice(parent_a);
+            return;
+        }
+
+        Self::fill_adjacency(parent_a, &mut self.adjacency_a);
+        Self::fill_adjacency(parent_b, &mut self.adjacency_b);
+        Self::fill_remaining(&self.adjacency_a, &self.adjacency_b, &mut self.remaining_a, &mut self.remaining_a_len);
+        Self::fill_remaining(&self.adjacency_b, &self.adjacency_a, &mut self.remaining_b, &mut self.remaining_b_len);
+
+        let offset = rng.random_range(0..size);
+        let start = match (0..size)
+            .map(|d| (offset + d) % size)
+            .find(|&node| self.remaining_a_len[node] > 0)
+        {
+            Some(node) => node as Ville,
+            None => {
+                // Parents identiques à rotation/inversion près : aucun AB-cycle
+                child.copy_from_slice(parent_a);
+                return;
+            }
+        };
+
+        // Chaque sommet possède autant d'arêtes A que B restantes, la marche alternée ne peut donc
+        // se bloquer qu'en revenant au départ après une arête B.
+        self.ab_cycle.clear();
+        self.ab_cycle.push(start);
+        let mut current = start;
+        loop {
+            let next = Self::pick_edge(&self.remaining_a, &self.remaining_a_len, current, rng);
+            Self::remove_edge(&mut self.remaining_a, &mut self.remaining_a_len, current, next);
+            self.ab_cycle.push(next);
+            current = next;
+
+            let next = Self::pick_edge(&self.remaining_b, &self.remaining_b_len, current, rng);
+            Self::remove_edge(&mut self.remaining_b, &mut self.remaining_b_len, current, next);
+            self.ab_cycle.push(next);
+            current = next;
+
+            if current == start {
+                break;
+            }
+        }
+
+        // Retire les arêtes A du cycle, puis ajoute ses arêtes B dans les emplacements libérés
+        self.adjacency_child.clone_from_slice(&self.adjacency_a);
+        for k in (0..self.ab_cycle.len() - 1).step_by(2) {
+            let (u, v) = (self.ab_cycle[k], self.ab_cycle[k + 1]);
+            Self::replace_neighbor(&mut self.adjacency_child, u, v, Ville::MAX);
+            Self::replace_neighbor(&mut self.adjacency_child, v, u, Ville::MAX);
+        }
+        for k in (1..self.ab_cycle.len() - 1).step_by(2) {
+            let (u, v) = (self.ab_cycle[k], self.ab_cycle[k + 1]);
+            Self::replace_neighbor(&mut self.adjacency_child, u, Ville::MAX, v);
+            Self::replace_neighbor(&mut self.adjacency_child, v, Ville::MAX, u);
+        }
+
+        self.merge_subtours(instance);
+
+        // Parcours du tour en partant de la première ville de A, dans le sens de A si possible
+        let first = parent_a[0];
+        let [n0, n1] = self.adjacency_child[first as usize];
+        let mut next = if n1 == parent_a[1] { n1 } else { n0 };
+        let mut prev = first;
+        child[0] = first;
+        for slot in child.iter_mut().skip(1) {
+            *slot = next;
+            let [n0, n1] = self.adjacency_child[next as usize];
+            let following = if n0 != prev { n0 } else { n1 };
+            (prev, next) = (next, following);
+        }
+
+        // Les arêtes ne sont pas orientées : sur une instance asymétrique, on garde le sens le moins coûteux
+        if !instance.symmetric {
+            let dist = |a: Ville, b: Ville| instance.distance_matrix[[a as usize, b as usize]];
+            let forward: f32 = (0..size).map(|i| dist(child[i], child[(i + 1) % size])).sum();
+            let backward: f32 = (0..size).map(|i| dist(child[(i + 1) % size], child[i])).sum();
+            if backward < forward {
+                child[1..].reverse();
+            }
+        }
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Edge Assembly Crossover (EAX)
+    pub(super) fn eax_crossover(
+        parent1: &[Ville],
+        parent2: &[Ville],
+        child_routes: &mut [Solution],
+        instance: &Instance,
+        workspace: &mut EaxWorkspace,
+        rng: &mut StdRng,
+    ) {
+        workspace.build_child(parent1, parent2, &mut child_routes[0], instance, rng);
+        workspace.build_child(parent2, parent1, &mut child_routes[1], instance, rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{TimeWindows, Window};
+
+    use ndarray::Array2;
+    use rand::SeedableRng;
+    use rand::seq::SliceRandom;
+
+    const SIZE: usize = 12;
+    const TRIALS: u64 = 200;
+
+    type GA = GeneticAlgorithm<()>;
+
+    // Villes au hasard dans le carré [0, 100]², fenêtres assez larges pour que l'ordre compte
+    fn random_instance(rng: &mut StdRng, symmetric: bool) -> Instance {
+        let points: Vec<(f32, f32)> = (0..SIZE)
+            .map(|_| (rng.random_range(0.0..100.0), rng.random_range(0.0..100.0)))
+            .collect();
+        let distances = Array2::from_shape_fn((SIZE, SIZE), |(i, j)| {
+            let (a, b) = (points[i], points[j]);
+            let d = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+            if symmetric || i < j { d } else { 1.5 * d }
+        });
+        let windows = (0..SIZE)
+            .map(|_| {
+                let wstart = rng.random_range(0.0..500.0);
+                TimeWindows::single(Window { wstart, wend: wstart + 100.0 })
+            })
+            .collect();
+        Instance::new(windows, distances)
+    }
+
+    fn random_parents(rng: &mut StdRng) -> (Solution, Solution) {
+        let mut parent1: Solution = (0..SIZE as Ville).collect();
+        let mut parent2 = parent1.clone();
+        parent1.shuffle(rng);
+        parent2.shuffle(rng);
+        (parent1, parent2)
+    }
+
+    fn assert_permutations(children: &[Solution], crossover: &str) {
+        for child in children {
+            let mut sorted = child.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..SIZE as Ville).collect::<Vec<_>>(), "{} child {:?} is not a permutation", crossover, child);
+        }
+    }
+
+    #[test]
+    fn ox_and_pmx_children_are_permutations() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let (mut visited1, mut visited2) = (vec![false; SIZE], vec![false; SIZE]);
+        let (mut mapping1, mut mapping2) = (vec![u32::MAX; SIZE], vec![u32::MAX; SIZE]);
+        for _ in 0..TRIALS {
+            let (parent1, parent2) = random_parents(&mut rng);
+            let mut children = vec![vec![0; SIZE]; 2];
+            GA::ox_crossover(&parent1, &parent2, &mut children, &mut visited1, &mut visited2, &mut rng);
+            assert_permutations(&children, "OX");
+            GA::pmx_crossover(&parent1, &parent2, &mut children, &mut mapping1, &mut mapping2, &mut rng);
+            assert_permutations(&children, "PMX");
+        }
+    }
+
+    #[test]
+    fn erx_and_cx_children_are_permutations() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut edge_map = vec![[0; 4]; SIZE];
+        let mut edge_count = vec![0; SIZE];
+        let mut positions = vec![0; SIZE];
+        let mut visited = vec![false; SIZE];
+        for _ in 0..TRIALS {
+            let (parent1, parent2) = random_parents(&mut rng);
+            let mut children = vec![vec![0; SIZE]; 2];
+            GA::erx_crossover(&parent1, &parent2, &mut children, &mut edge_map, &mut edge_count, &mut visited, &mut rng);
+            assert_permutations(&children, "ERX");
+            GA::cx_crossover(&parent1, &parent2, &mut children, &mut positions, &mut visited);
+            assert_permutations(&children, "CX");
+        }
+    }
+
+    #[test]
+    fn cx_keeps_every_city_at_a_parent_position() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut positions = vec![0; SIZE];
+        let mut visited = vec![false; SIZE];
+        let (parent1, parent2) = random_parents(&mut rng);
+        let mut children = vec![vec![0; SIZE]; 2];
+        GA::cx_crossover(&parent1, &parent2, &mut children, &mut positions, &mut visited);
+        for child in &children {
+            assert!((0..SIZE).all(|i| child[i] == parent1[i] || child[i] == parent2[i]));
+        }
+    }
+
+    #[test]
+    fn eax_and_tw_merge_children_are_permutations() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut workspace = EaxWorkspace::new(SIZE);
+        let (mut arrival1, mut arrival2) = (vec![0.0; SIZE], vec![0.0; SIZE]);
+        let mut visited = vec![false; SIZE];
+        for trial in 0..TRIALS {
+            // Les instances asymétriques passent par l'orientation des sous-tours
+            let instance = random_instance(&mut rng, trial % 2 == 0);
+            let (parent1, parent2) = random_parents(&mut rng);
+            let mut children = vec![vec![0; SIZE]; 2];
+            GA::eax_crossover(&parent1, &parent2, &mut children, &instance, &mut workspace, &mut rng);
+            assert_permutations(&children, "EAX");
+            GA::tw_merge_crossover(&parent1, &parent2, &mut children, &instance, &mut arrival1, &mut arrival2, &mut visited);
+            assert_permutations(&children, "TWMerge");
+        }
+    }
+
+    #[test]
+    fn eax_of_identical_parents_is_the_parent_tour() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let instance = random_instance(&mut rng, true);
+        let mut workspace = EaxWorkspace::new(SIZE);
+        let (parent, _) = random_parents(&mut rng);
+        let mut children = vec![vec![0; SIZE]; 2];
+        GA::eax_crossover(&parent, &parent, &mut children, &instance, &mut workspace, &mut rng);
+        // Même cycle, à rotation et sens de parcours près
+        let edges = |tour: &[Ville]| {
+            let mut edges: Vec<(Ville, Ville)> = (0..SIZE)
+                .map(|i| {
+                    let (a, b) = (tour[i], tour[(i + 1) % SIZE]);
+                    (a.min(b), a.max(b))
+                })
+                .collect();
+            edges.sort_unstable();
+            edges
+        };
+        assert_eq!(edges(&children[0]), edges(&parent));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::shared::{Fitness, Solution, Ville};
+
+use rand::Rng;
+use rand::rngs::StdRng;
+
+/// Nombre maximal de paires évaluées pour estimer la distance moyenne entre individus
+const MAX_SAMPLED_PAIRS: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum ReplacementType {
+    /// La nouvelle génération remplace entièrement l'ancienne (hors élites)
+    #[default]
+    Generational,
+    /// Crowding déterministe : chaque enfant affronte le parent dont il est le plus proche
+    Crowding,
+    /// Partage de fitness : la sélection pénalise les individus situés dans une niche peuplée
+    FitnessSharing,
+}
+
+/// Paramètres de gestion de la diversité de l'algorithme génétique
+///
+/// - `replacement_type`: stratégie de remplacement (générationnel, crowding, partage de fitness)
+/// - `sharing_radius`: rayon des niches pour le partage de fitness, en proportion d'arêtes différentes
+/// - `duplicate_elimination`: remplace les doublons par des individus aléatoires
+/// - `restart_threshold`: redémarre la population (hors élites) lorsque la distance moyenne passe sous ce seuil (0 = jamais)
+#[derive(Clone, Copy, PartialEq)]
+pub struct DiversityConfig {
+    pub replacement_type: ReplacementType,
+    pub sharing_radius: f32,
+    pub duplicate_elimination: bool,
+    pub restart_threshold: f32,
+}
+
+impl Default for DiversityConfig {
+    fn default() -> Self {
+        DiversityConfig {
+            replacement_type: ReplacementType::Generational,
+            sharing_radius: 0.1,
+            duplicate_elimination: false,
+            restart_threshold: 0.0,
+        }
+    }
+}
+
+/// Mesures de diversité fondées sur les arêtes des tournées, avec buffers réutilisés.
+///
+/// La distance entre deux tournées est la proportion d'arêtes (non orientées) de l'une absentes de l'autre.
+pub(super) struct EdgeDiversity {
+    size: usize,
+    adjacency_buffer: Vec<[Ville; 2]>,
+    other_adjacency_buffer: Vec<[Ville; 2]>,
+    edge_count_buffer: Vec<u32>,
+}
+
+impl EdgeDiversity {
+    pub(super) fn new(size: usize, population_size: usize) -> Self {
+        EdgeDiversity {
+            size,
+            adjacency_buffer: vec![[0; 2]; size * population_size],
+            other_adjacency_buffer: vec![[0; 2]; size],
+            edge_count_buffer: vec![0; size * size],
+        }
+    }
+
+    fn fill_adjacency(route: &[Ville], adjacency: &mut [[Ville; 2]]) {
+        let size = route.len();
+        for i in 0..size {
+            adjacency[route[i] as usize] = [route[(i + size - 1) % size], route[(i + 1) % size]];
+        }
+    }
+
+    fn adjacency_distance(a: &[[Ville; 2]], b: &[[Ville; 2]]) -> f32 {
+        let size = a.len();
+        if size == 0 {
+            return 0.0;
+        }
+        // Chaque arête de A est comptée depuis sa plus petite extrémité
+        let missing = (0..size)
+            .flat_map(|node| a[node].iter().map(move |&neighbor| (node, neighbor)))
+            .filter(|&(node, neighbor)| node < neighbor as usize && !b[node].contains(&neighbor))
+            .count();
+        missing as f32 / size as f32
+    }
+
+    /// Calcule l'adjacence de chaque individu, à appeler avant les mesures portant sur la population
+    pub(super) fn refresh(&mut self, population: &[Solution]) {
+        let size = self.size;
+        for (i, route) in population.iter().enumerate() {
+            Self::fill_adjacency(route, &mut self.adjacency_buffer[i * size..(i + 1) * size]);
+        }
+    }
+
+    /// Met à jour l'adjacence d'un seul individu après sa modification
+    pub(super) fn refresh_one(&mut self, i: usize, route: &[Ville]) {
+        let size = self.size;
+        Self::fill_adjacency(route, &mut self.adjacency_buffer[i * size..(i + 1) * size]);
+    }
+
+    fn individual(&self, i: usize) -> &[[Ville; 2]] {
+        &self.adjacency_buffer[i * self.size..(i + 1) * self.size]
+    }
+
+    /// Distance entre deux individus de la population courante (après `refresh`)
+    pub(super) fn distance(&self, i: usize, j: usize) -> f32 {
+        Self::adjacency_distance(self.individual(i), self.individual(j))
+    }
+
+    /// Distance entre un individu de la population courante (après `refresh`) et une tournée quelconque
+    pub(super) fn distance_to(&mut self, i: usize, route: &[Ville]) -> f32 {
+        Self::fill_adjacency(route, &mut self.other_adjacency_buffer);
+        let size = self.size;
+        Self::adjacency_distance(&self.adjacency_buffer[i * size..(i + 1) * size], &self.other_adjacency_buffer)
+    }
+
+    /// Distance moyenne entre paires d'individus, exacte pour les petites populations et estimée
+    /// sur un échantillon de paires sinon.
+    pub(super) fn mean_pairwise_distance(&self, population_size: usize, rng: &mut StdRng) -> f32 {
+        if population_size < 2 {
+            return 0.0;
+        }
+
+        let pair_count = population_size * (population_size - 1) / 2;
+        if pair_count <= MAX_SAMPLED_PAIRS {
+            let mut total = 0.0;
+            for i in 0..population_size {
+                for j in i + 1..population_size {
+                    total += self.distance(i, j);
+                }
+            }
+            total / pair_count as f32
+        } else {
+            let mut total = 0.0;
+            for _ in 0..MAX_SAMPLED_PAIRS {
+                let i = rng.random_range(0..population_size - 1);
+                let j = rng.random_range(i + 1..population_size);
+                total += self.distance(i, j);
+            }
+            total / MAX_SAMPLED_PAIRS as f32
+        }
+    }
+
+    /// Entropie de l'utilisation des arêtes dans la population, normalisée entre 0 (population identique)
+    /// et 1 (aucune arête partagée).
+    pub(super) fn edge_entropy(&mut self, population: &[Solution]) -> f32 {
+        let size = self.size;
+        let population_size = population.len();
+        if population_size < 2 || size < 2 {
+            return 0.0;
+        }
+
+        for route in population {
+            for i in 0..size {
+                let (a, b) = (route[i] as usize, route[(i + 1) % size] as usize);
+                self.edge_count_buffer[a.min(b) * size + a.max(b)] += 1;
+            }
+        }
+
+        let total = (population_size * size) as f32;
+        let mut entropy = 0.0;
+        for route in population {
+            for i in 0..size {
+                let (a, b) = (route[i] as usize, route[(i + 1) % size] as usize);
+                let count = &mut self.edge_count_buffer[a.min(b) * size + a.max(b)];
+                if *count > 0 {
+                    let p = *count as f32 / total;
+                    entropy -= p * p.ln();
+                    // Remise à zéro au fil de l'eau, chaque arête n'est comptée qu'une fois
+                    *count = 0;
+                }
+            }
+        }
+
+        // Bornes : ln(n) lorsque tous les individus sont identiques, ln(P n) sans arête partagée
+        ((entropy - (size as f32).ln()) / (population_size as f32).ln()).clamp(0.0, 1.0)
+    }
+
+    /// Fitness partagée : la fitness (minimisée) est multipliée par le nombre de voisins dans un rayon `radius`
+    pub(super) fn shared_fitness(&self, fitness: &[Fitness], radius: f32, shared_buffer: &mut [Fitness]) {
+        let population_size = fitness.len();
+        if radius <= 0.0 {
+            shared_buffer.copy_from_slice(fitness);
+            return;
+        }
+        for i in 0..population_size {
+            let mut niche_count = 0.0;
+            for j in 0..population_size {
+                let d = if i == j { 0.0 } else { self.distance(i, j) };
+                if d < radius {
+                    niche_count += 1.0 - d / radius;
+                }
+            }
+            shared_buffer[i] = fitness[i] * niche_count;
+        }
+    }
+}
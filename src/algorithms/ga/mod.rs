@@ -0,0 +1,652 @@
+use crate::eval::Evaluation;
+use crate::shared::{Fitness, Instance, Solution, Ville};
+use crate::utils::rng::new_rng;
+
+use super::{Elite, EliteTracker, Metaheuristic, LocalSearch};
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+
+mod crossover;
+use crossover::EaxWorkspace;
+
+mod mutation;
+pub use mutation::{MutationRates, MutationType};
+
+mod diversity;
+use diversity::EdgeDiversity;
+pub use diversity::{DiversityConfig, ReplacementType};
+
+/// Taux de la recherche locale mémétique quand la configuration n'en donne pas, celui de l'ancien taux de mutation
+pub const DEFAULT_LOCAL_SEARCH_RATE: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CompetitionType {
+    Tournament,
+    Roulette,
+}
+
+impl Default for CompetitionType {
+    fn default() -> Self {
+        CompetitionType::Tournament
+    }
+}
+
+impl CompetitionType {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "Tournament" => Some(CompetitionType::Tournament),
+            "Roulette" => Some(CompetitionType::Roulette),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CrossoverType {
+    PMX,
+    OX,
+    /// Edge Recombination Crossover
+    ERX,
+    /// Edge Assembly Crossover
+    EAX,
+    /// Cycle Crossover
+    CX,
+    /// Merge crossover sensible aux fenêtres de temps, en deux variantes volontairement distinctes :
+    /// le premier enfant est ordonné par heure d'arrivée dans chaque parent, le second par fin de
+    /// dernière fenêtre, ce qui diversifie les enfants
+    TWMerge,
+}
+
+impl Default for CrossoverType {
+    fn default() -> Self {
+        CrossoverType::PMX
+    }
+}
+
+impl CrossoverType {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "PMX" => Some(CrossoverType::PMX),
+            "OX" => Some(CrossoverType::OX),
+            "ERX" => Some(CrossoverType::ERX),
+            "EAX" => Some(CrossoverType::EAX),
+            "CX" => Some(CrossoverType::CX),
+            "TWMerge" => Some(CrossoverType::TWMerge),
+            _ => None,
+        }
+    }
+}
+
+/// Paramètres de l'algorithme génétique
+///
+/// - `crossover_rate`: taux de crossover
+/// - `crossover_type`: type de crossover (PMX, OX, ERX, EAX, CX, TWMerge)
+/// - `mutation_rates`: taux d'application de chaque opérateur de mutation (inversion, scramble, insertion, displacement)
+/// - `local_search_rate`: taux d'application de la recherche locale (étape mémétique) après la mutation (0 pour la désactiver)
+/// - `elitism_rate`: taux d'élitisme
+/// - `competition_participation_rate`: taux de participation à chaque compétition pour l'étape de sélection
+/// - `competition_type`: type de compétition (tournoi, roulette)
+/// - `max_iter`: nombre maximal de générations
+/// - `population_size`: taille de la population
+/// - `diversity_config`: gestion de la diversité (remplacement, élimination des doublons, redémarrages)
+#[derive(Clone, Copy, PartialEq)]
+pub struct GAParameters {
+    pub crossover_rate: f32,
+    pub crossover_type: CrossoverType,
+    pub mutation_rates: MutationRates,
+    pub local_search_rate: f32,
+    pub elitism_rate: f32,
+    pub competition_participation_rate: f32,
+    pub competition_type: CompetitionType,
+    pub max_iter: usize,
+    pub population_size: usize,
+    pub diversity_config: DiversityConfig,
+}
+
+/// Algorithme génétique pour le TSP, avec divers types de crossover et de sélection, implémenté de manière efficace avec
+/// des buffers réutilisés pour minimiser les allocations mémoire. Les paramètres sont décrits dans `GAParameters`.
+pub struct GeneticAlgorithm<LS> {
+    /// taille de l'instance solution
+    population_size: usize,
+
+    crossover_rate: f32,
+    mutation_rates: MutationRates,
+    local_search_rate: f32,
+    competition_participation_count: usize,
+    elitism_count: usize,
+    crossover_type: CrossoverType,
+    competition_type: CompetitionType,
+
+    max_iter: usize,
+    iteration: usize,
+
+    rng: StdRng,
+
+    // Meilleure solution jamais rencontrée, la population étant entièrement remplacée à chaque génération
+    elite: EliteTracker,
+    
+    // Local search
+    local_search: LS,
+
+    // Gestion de la diversité
+    diversity_config: DiversityConfig,
+    diversity: EdgeDiversity,
+    mean_edge_distance: f32,
+    edge_entropy: f32,
+    restarts: usize,
+
+    // Buffers réutilisés, alloués UNE FOIS
+    participants_buffer: Vec<usize>,
+    population_idx_buffer: Vec<usize>,
+    new_population_buffer: Vec<Solution>,
+    new_fitness_buffer: Vec<Fitness>,
+    modified_buffer: Vec<bool>,
+    child1_visited_buffer: Vec<bool>,
+    child2_visited_buffer: Vec<bool>,
+    child1_mapping_buffer: Vec<u32>,
+    child2_mapping_buffer: Vec<u32>,
+    position_buffer: Vec<usize>,
+    edge_map_buffer: Vec<[Ville; 4]>,
+    edge_count_buffer: Vec<u8>,
+    arrival1_buffer: Vec<f32>,
+    arrival2_buffer: Vec<f32>,
+    eax_workspace: EaxWorkspace,
+    shared_fitness_buffer: Vec<Fitness>,
+    parents_buffer: Vec<(usize, usize)>,
+    parent_population_buffer: Vec<Solution>,
+    parent_fitness_buffer: Vec<Fitness>,
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Crée un nouvel algorithme génétique avec les paramètres donnés, pour l'instance `instance`.
+    pub fn new(instance: &Instance, parameters: GAParameters, local_search: LS) -> Self {
+        let GAParameters {
+            crossover_rate,
+            crossover_type,
+            mutation_rates,
+            local_search_rate,
+            elitism_rate,
+            competition_participation_rate,
+            competition_type,
+            max_iter,
+            population_size,
+            diversity_config,
+        } = parameters;
+        let solution_size = instance.size();
+        let elitism_count = (elitism_rate * population_size as f32) as usize;
+        let competition_participation_count =
+            (competition_participation_rate * population_size as f32) as usize;
+
+        GeneticAlgorithm {
+            population_size,
+            crossover_rate,
+            mutation_rates,
+            local_search_rate,
+            crossover_type,
+            elitism_count,
+            competition_participation_count,
+            max_iter,
+            iteration: 0,
+            rng: new_rng(),
+            elite: EliteTracker::new(),
+
+            competition_type,
+            local_search,
+
+            diversity_config,
+            diversity: EdgeDiversity::new(solution_size, population_size),
+            mean_edge_distance: 0.0,
+            edge_entropy: 0.0,
+            restarts: 0,
+
+            participants_buffer: vec![0; competition_participation_count],
+            population_idx_buffer: vec![0; population_size],
+            new_population_buffer: vec![vec![0; solution_size]; population_size],
+            new_fitness_buffer: vec![0.0; population_size],
+            modified_buffer: vec![false; population_size],
+            child1_visited_buffer: vec![false; solution_size],
+            child2_visited_buffer: vec![false; solution_size],
+            child1_mapping_buffer: vec![u32::MAX; solution_size],
+            child2_mapping_buffer: vec![u32::MAX; solution_size],
+            position_buffer: vec![0; solution_size],
+            edge_map_buffer: vec![[0; 4]; solution_size],
+            edge_count_buffer: vec![0; solution_size],
+            arrival1_buffer: vec![0.0; solution_size],
+            arrival2_buffer: vec![0.0; solution_size],
+            eax_workspace: EaxWorkspace::new(solution_size),
+            shared_fitness_buffer: vec![0.0; population_size],
+            parents_buffer: vec![(0, 0); population_size],
+            parent_population_buffer: Vec::new(),
+            parent_fitness_buffer: Vec::new(),
+        }
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Sélection par tournoi entre les participants sélectionnés
+    fn tournament_selection(&mut self, fitness: &[Fitness]) -> usize {
+        // Tournament selection logic
+        let best_idx = self
+            .rng
+            .random_range(0..self.competition_participation_count);
+        let mut participant_best_idx = self.participants_buffer[best_idx];
+
+        for _ in 0..self.competition_participation_count {
+            let ind = self
+                .rng
+                .random_range(0..self.competition_participation_count);
+            let participant_ind = self.participants_buffer[ind];
+
+            // In TSPTW, LOWER fitness is BETTER (minimization problem)
+            if fitness[participant_ind] < fitness[participant_best_idx] {
+                participant_best_idx = participant_ind;
+            }
+        }
+        participant_best_idx
+    }
+
+    /// Sélection proportionnelle à la fitness (roulette wheel selection)
+    fn roulette_selection(&mut self, fitness: &[Fitness]) -> usize {
+        // Roulette wheel selection logic
+        // For minimization: invert fitness (use 1/fitness) so better solutions have higher probability
+        // Find max fitness to avoid division issues
+        let max_fitness = self.participants_buffer.iter()
+            .map(|&idx| fitness[idx])
+            .fold(f32::NEG_INFINITY, |a, b| a.max(b));
+        
+        // Calculate inverted fitness sum (max - fitness for each)
+        let total_inverted: f32 = self
+            .participants_buffer
+            .iter()
+            .map(|&idx| max_fitness - fitness[idx] + 1.0) // +1 to avoid zero weights
+            .sum();
+
+        if total_inverted <= f32::EPSILON {
+            // Si toutes les fitness sont identiques, retourner un individu aléatoire parmi les participants
+            let rand_idx = self
+                .rng
+                .random_range(0..self.competition_participation_count);
+            return self.participants_buffer[rand_idx];
+        }
+
+        let mut pick = self.rng.random_range(0.0..total_inverted);
+
+        for idx in 0..self.competition_participation_count {
+            let inverted_fitness = max_fitness - fitness[self.participants_buffer[idx]] + 1.0;
+            pick -= inverted_fitness;
+            if pick <= 0.0 {
+                return self.participants_buffer[idx];
+            }
+        }
+        self.participants_buffer.last().cloned().unwrap()
+    }
+
+    /// Sélectionne les meilleurs individus de la population et stocke leurs indices dans population_idx_buffer
+    fn select_best(&mut self, fitness: &[Fitness]) -> () {
+        // Initialize indices
+        for i in 0..self.population_size {
+            self.population_idx_buffer[i] = i;
+        }
+
+        // Use select_nth_unstable to partition and find the best elitism_count indices
+        // We want the smallest fitness values (best solutions), so we partition at elitism_count - 1
+        self.population_idx_buffer
+            .select_nth_unstable_by(self.elitism_count - 1, |&a, &b| {
+                fitness[a]
+                    .partial_cmp(&fitness[b])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        // The first elitism_count elements are now the best indices
+        // No need for additional sorting since select_nth_unstable partitions correctly
+    }
+
+    /// Sélectionne les participants pour la compétition de manière aléatoire sans remise
+    fn select_particiants(&mut self) -> () {
+        let pop_size = self.population_size;
+
+        // Initialize the population indices
+        for i in 0..pop_size {
+            self.population_idx_buffer[i] = i;
+        }
+
+        // Shuffle the population indices
+        self.population_idx_buffer[..pop_size].shuffle(&mut self.rng);
+        
+        // Copy the first competition_participation_count indices to participants_buffer
+        for i in 0..self.competition_participation_count {
+            self.participants_buffer[i] = self.population_idx_buffer[i];
+        }
+    }
+
+    fn selection_round(&mut self, fitness: &[Fitness]) -> usize {
+        // Selection logic based on competition type
+        self.select_particiants();
+        match self.competition_type {
+            CompetitionType::Tournament => self.tournament_selection(fitness),
+            CompetitionType::Roulette => self.roulette_selection(fitness),
+        }
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    fn crossover(&mut self, parent1: &[Ville], parent2: &[Ville], cpt: usize, instance: &Instance) -> () {
+        match self.crossover_type {
+            CrossoverType::OX => Self::ox_crossover(
+                parent1,
+                parent2,
+                &mut self.new_population_buffer[cpt..cpt + 2],
+                &mut self.child1_visited_buffer,
+                &mut self.child2_visited_buffer,
+                &mut self.rng,
+            ),
+            CrossoverType::PMX => Self::pmx_crossover(
+                parent1,
+                parent2,
+                &mut self.new_population_buffer[cpt..cpt + 2],
+                &mut self.child1_mapping_buffer,
+                &mut self.child2_mapping_buffer,
+                &mut self.rng,
+            ),
+            CrossoverType::ERX => Self::erx_crossover(
+                parent1,
+                parent2,
+                &mut self.new_population_buffer[cpt..cpt + 2],
+                &mut self.edge_map_buffer,
+                &mut self.edge_count_buffer,
+                &mut self.child1_visited_buffer,
+                &mut self.rng,
+            ),
+            CrossoverType::EAX => Self::eax_crossover(
+                parent1,
+                parent2,
+                &mut self.new_population_buffer[cpt..cpt + 2],
+                instance,
+                &mut self.eax_workspace,
+                &mut self.rng,
+            ),
+            CrossoverType::CX => Self::cx_crossover(
+                parent1,
+                parent2,
+                &mut self.new_population_buffer[cpt..cpt + 2],
+                &mut self.position_buffer,
+                &mut self.child1_visited_buffer,
+            ),
+            CrossoverType::TWMerge => Self::tw_merge_crossover(
+                parent1,
+                parent2,
+                &mut self.new_population_buffer[cpt..cpt + 2],
+                instance,
+                &mut self.arrival1_buffer,
+                &mut self.arrival2_buffer,
+                &mut self.child1_visited_buffer,
+            ),
+        }
+    }
+
+    fn select_parents(&mut self, fitness: &[Fitness]) -> (usize, usize) {
+        let parent1 = self.selection_round(fitness);
+        let parent2 = self.selection_round(fitness);
+        (parent1, parent2)
+    }
+}
+
+impl<LS> GeneticAlgorithm<LS> {
+    /// Crowding déterministe : chaque enfant est apparié au parent le plus proche (en distance d'arêtes)
+    /// et le parent est conservé s'il est meilleur que l'enfant.
+    fn crowding_replacement(&mut self, population: &mut [Solution], fitness: &mut [Fitness]) {
+        let pop_size = population.len();
+        self.diversity.refresh(population);
+
+        let mut cpt = self.elitism_count;
+        while cpt < pop_size {
+            let (parent1_idx, parent2_idx) = self.parents_buffer[cpt];
+
+            if cpt + 1 < pop_size {
+                let parent1 = &self.parent_population_buffer[parent1_idx];
+                let parent2 = &self.parent_population_buffer[parent2_idx];
+                let straight = self.diversity.distance_to(cpt, parent1) + self.diversity.distance_to(cpt + 1, parent2);
+                let crossed = self.diversity.distance_to(cpt, parent2) + self.diversity.distance_to(cpt + 1, parent1);
+
+                let (match1, match2) = if straight <= crossed {
+                    (parent1_idx, parent2_idx)
+                } else {
+                    (parent2_idx, parent1_idx)
+                };
+                self.crowding_compete(population, fitness, cpt, match1);
+                self.crowding_compete(population, fitness, cpt + 1, match2);
+                cpt += 2;
+            } else {
+                self.crowding_compete(population, fitness, cpt, parent1_idx);
+                cpt += 1;
+            }
+        }
+    }
+
+    fn crowding_compete(&self, population: &mut [Solution], fitness: &mut [Fitness], child_idx: usize, parent_idx: usize) {
+        if self.parent_fitness_buffer[parent_idx] < fitness[child_idx] {
+            population[child_idx].clone_from_slice(&self.parent_population_buffer[parent_idx]);
+            fitness[child_idx] = self.parent_fitness_buffer[parent_idx];
+        }
+    }
+
+    /// Remplace par un individu aléatoire chaque individu non élite identique (aux arêtes près) à un précédent
+    fn eliminate_duplicates<Eval: Evaluation>(
+        &mut self,
+        population: &mut [Solution],
+        fitness: &mut [Fitness],
+        instance: &Instance,
+        evaluation: &Eval,
+    ) {
+        let pop_size = population.len();
+        self.diversity.refresh(population);
+
+        for i in self.elitism_count..pop_size {
+            let is_duplicate = (0..i).any(|j| fitness[j] == fitness[i] && self.diversity.distance(i, j) == 0.0);
+            if is_duplicate {
+                population[i].shuffle(&mut self.rng);
+                fitness[i] = evaluation.score(instance, &population[i]);
+                self.diversity.refresh_one(i, &population[i]);
+            }
+        }
+    }
+
+    /// Réinitialise aléatoirement toute la population hors élites
+    fn restart<Eval: Evaluation>(
+        &mut self,
+        population: &mut [Solution],
+        fitness: &mut [Fitness],
+        instance: &Instance,
+        evaluation: &Eval,
+    ) {
+        for i in self.elitism_count..population.len() {
+            population[i].shuffle(&mut self.rng);
+            fitness[i] = evaluation.score(instance, &population[i]);
+        }
+        self.restarts += 1;
+    }
+
+    fn update_diversity_metrics(&mut self, population: &[Solution]) {
+        self.diversity.refresh(population);
+        self.mean_edge_distance = self.diversity.mean_pairwise_distance(population.len(), &mut self.rng);
+        self.edge_entropy = self.diversity.edge_entropy(population);
+    }
+}
+
+impl<LS, Eval> Metaheuristic<Eval> for GeneticAlgorithm<LS>
+where
+    Eval: Evaluation,
+    LS: LocalSearch<Eval>,
+{
+    fn step(
+        &mut self,
+        population: &mut [Solution],
+        fitness: &mut [Fitness],
+        instance: &Instance,
+        evaluation: &Eval,
+    ) -> () {
+        let pop_size = population.len();
+
+        // Records the incoming population before it gets replaced
+        self.elite.update(population, fitness, instance, self.iteration);
+
+        // With fitness sharing, parents are selected on the shared fitness (elitism still uses the raw one)
+        let sharing = self.diversity_config.replacement_type == ReplacementType::FitnessSharing;
+        let shared_fitness = if sharing {
+            self.diversity.refresh(population);
+            self.diversity.shared_fitness(fitness, self.diversity_config.sharing_radius, &mut self.shared_fitness_buffer);
+            std::mem::take(&mut self.shared_fitness_buffer)
+        } else {
+            Vec::new()
+        };
+        let selection_fitness: &[Fitness] = if sharing { &shared_fitness } else { fitness };
+
+        // Keeps the parents around for deterministic crowding
+        if self.diversity_config.replacement_type == ReplacementType::Crowding {
+            self.parent_population_buffer.resize(pop_size, Vec::new());
+            for (parent, individual) in self.parent_population_buffer.iter_mut().zip(population.iter()) {
+                parent.clone_from(individual);
+            }
+            self.parent_fitness_buffer.clear();
+            self.parent_fitness_buffer.extend_from_slice(fitness);
+        }
+
+        // Selects the best individuals for elitism, puts their indices in population_idx_buffer to avoid
+        // reallocating memory each generation
+        self.select_best(fitness);
+
+        let best_idx = &self.population_idx_buffer[..self.elitism_count];
+        let mut cpt = 0;
+
+        // Adds the best individuals to the new population, their fitness is already known
+        for idx in best_idx.iter() {
+            self.new_population_buffer[cpt].clone_from_slice(&population[*idx][..]);
+            self.new_fitness_buffer[cpt] = fitness[*idx];
+            self.modified_buffer[cpt] = false;
+            cpt += 1;
+        }
+
+        // Generates new individuals through selection and crossover
+        while cpt + 1 < pop_size {
+            let (parent1_idx, parent2_idx) = self.select_parents(selection_fitness);
+            self.parents_buffer[cpt] = (parent1_idx, parent2_idx);
+            self.parents_buffer[cpt + 1] = (parent2_idx, parent1_idx);
+            let parent1 = &population[parent1_idx];
+            let parent2 = &population[parent2_idx];
+
+            let rand = self.rng.random_range(0.0..1.0);
+
+            if rand < self.crossover_rate {
+                // Crossover, produces two children that are added to the new population buffer in place
+                self.crossover(parent1, parent2, cpt, instance);
+                self.modified_buffer[cpt] = true;
+                self.modified_buffer[cpt + 1] = true;
+            } else {
+                // No crossover, simply clone the parents into the new population buffer
+                self.new_population_buffer[cpt].clone_from_slice(&parent1[..]);
+                self.new_population_buffer[cpt + 1].clone_from_slice(&parent2[..]);
+                self.new_fitness_buffer[cpt] = fitness[parent1_idx];
+                self.new_fitness_buffer[cpt + 1] = fitness[parent2_idx];
+                self.modified_buffer[cpt] = false;
+                self.modified_buffer[cpt + 1] = false;
+            };
+
+            cpt += 2;
+        }
+
+        // Odd number of free slots: the last one gets a selected individual
+        if cpt < pop_size {
+            let parent_idx = self.selection_round(selection_fitness);
+            self.parents_buffer[cpt] = (parent_idx, parent_idx);
+            self.new_population_buffer[cpt].clone_from_slice(&population[parent_idx][..]);
+            self.new_fitness_buffer[cpt] = fitness[parent_idx];
+            self.modified_buffer[cpt] = false;
+        }
+
+        if sharing {
+            self.shared_fitness_buffer = shared_fitness;
+        }
+
+        // Replace old population with new population
+        population.clone_from_slice(&self.new_population_buffer[..]);
+        fitness.copy_from_slice(&self.new_fitness_buffer[..]);
+
+        // Mutation of non-elite individuals, each operator is drawn independently
+        for (individual, modified) in population
+            .iter_mut()
+            .zip(self.modified_buffer.iter_mut())
+            .skip(self.elitism_count)
+        {
+            for mutation_type in MutationType::ALL {
+                let rand = self.rng.random_range(0.0..1.0);
+                if rand < self.mutation_rates.rate(mutation_type) {
+                    mutation_type.apply(individual, &mut self.rng);
+                    *modified = true;
+                }
+            }
+        }
+
+        // Evaluates only the individuals that were modified by crossover or mutation
+        for i in self.elitism_count..pop_size {
+            if self.modified_buffer[i] {
+                fitness[i] = evaluation.score(instance, &population[i]);
+            }
+        }
+
+        // Optional memetic stage, the local search keeps the fitness up to date
+        if self.local_search_rate > 0.0 {
+            for i in self.elitism_count..pop_size {
+                let rand = self.rng.random_range(0.0..1.0);
+                if rand < self.local_search_rate {
+                    self.local_search.search(&mut population[i], &mut fitness[i], instance, evaluation);
+                }
+            }
+        }
+        
+        if self.diversity_config.replacement_type == ReplacementType::Crowding {
+            self.crowding_replacement(population, fitness);
+        }
+        if self.diversity_config.duplicate_elimination {
+            self.eliminate_duplicates(population, fitness, instance, evaluation);
+        }
+
+        self.update_diversity_metrics(population);
+        if self.mean_edge_distance < self.diversity_config.restart_threshold {
+            self.restart(population, fitness, instance, evaluation);
+            self.update_diversity_metrics(population);
+        }
+
+        // Increment iteration counter
+        self.iteration += 1;
+        self.elite.update(population, fitness, instance, self.iteration);
+    }
+
+    fn get_metrics(&self) -> HashMap<String, f32> {
+        let mut metrics = HashMap::new();
+        metrics.insert("mean_edge_distance".to_string(), self.mean_edge_distance);
+        metrics.insert("edge_entropy".to_string(), self.edge_entropy);
+        metrics.insert("restarts".to_string(), self.restarts as f32);
+        metrics
+    }
+
+    fn get_metric_names(&self) -> Vec<String> {
+        vec![
+            "mean_edge_distance".to_string(),
+            "edge_entropy".to_string(),
+            "restarts".to_string(),
+        ]
+    }
+
+    fn stop_condition_met(&self) -> bool {
+        self.iteration >= self.max_iter
+    }
+    fn get_iteration(&self) -> usize {
+        self.iteration
+    }
+    fn get_best_solution(&self) -> Option<&Elite> {
+        self.elite.best()
+    }
+}
@@ -0,0 +1,168 @@
+use crate::shared::Ville;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MutationType {
+    /// Inverse un segment aléatoire
+    Inversion,
+    /// Mélange un segment aléatoire
+    Scramble,
+    /// Déplace une ville à une autre position
+    Insertion,
+    /// Déplace un segment à une autre position, sans l'inverser
+    Displacement,
+}
+
+impl MutationType {
+    pub const ALL: [MutationType; 4] = [
+        MutationType::Inversion,
+        MutationType::Scramble,
+        MutationType::Insertion,
+        MutationType::Displacement,
+    ];
+
+    /// Applique la mutation en place
+    pub fn apply(&self, route: &mut [Ville], rng: &mut StdRng) {
+        let size = route.len();
+        if size < 2 {
+            return;
+        }
+
+        match self {
+            MutationType::Inversion => {
+                let (i, j) = Self::random_pair(size, rng);
+                route[i..=j].reverse();
+            }
+            MutationType::Scramble => {
+                let (i, j) = Self::random_pair(size, rng);
+                route[i..=j].shuffle(rng);
+            }
+            MutationType::Insertion => {
+                let from = rng.random_range(0..size);
+                let to = rng.random_range(0..size);
+                if from < to {
+                    route[from..=to].rotate_left(1);
+                } else {
+                    route[to..=from].rotate_right(1);
+                }
+            }
+            MutationType::Displacement => {
+                // Trois points de coupe a < b <= c : le segment [a, b) est déplacé après [b, c)
+                let (a, c) = Self::random_pair(size, rng);
+                let b = rng.random_range(a + 1..=c);
+                route[a..=c].rotate_left(b - a);
+            }
+        }
+    }
+
+    /// Tire deux positions distinctes i < j
+    fn random_pair(size: usize, rng: &mut StdRng) -> (usize, usize) {
+        let i = rng.random_range(0..size - 1);
+        let j = rng.random_range(i + 1..size);
+        (i, j)
+    }
+}
+
+/// Probabilité d'application de chaque opérateur de mutation, tirée indépendamment pour chaque enfant
+#[derive(Clone, Copy, PartialEq)]
+pub struct MutationRates {
+    pub inversion: f32,
+    pub scramble: f32,
+    pub insertion: f32,
+    pub displacement: f32,
+}
+
+// Taux utilisés quand la configuration n'en donne aucun
+impl Default for MutationRates {
+    fn default() -> Self {
+        MutationRates {
+            inversion: 0.1,
+            scramble: 0.0,
+            insertion: 0.05,
+            displacement: 0.05,
+        }
+    }
+}
+
+impl MutationRates {
+    pub fn rate(&self, mutation_type: MutationType) -> f32 {
+        match mutation_type {
+            MutationType::Inversion => self.inversion,
+            MutationType::Scramble => self.scramble,
+            MutationType::Insertion => self.insertion,
+            MutationType::Displacement => self.displacement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    const SIZE: usize = 10;
+
+    #[test]
+    fn mutations_keep_a_permutation() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for mutation_type in MutationType::ALL {
+            let mut route: Vec<Ville> = (0..SIZE as Ville).collect();
+            for _ in 0..500 {
+                mutation_type.apply(&mut route, &mut rng);
+                let mut sorted = route.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..SIZE as Ville).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn mutations_change_the_route() {
+        let mut rng = StdRng::seed_from_u64(2);
+        // Le scramble peut redonner le même ordre, les autres opérateurs jamais
+        for mutation_type in [MutationType::Inversion, MutationType::Displacement] {
+            for _ in 0..100 {
+                let original: Vec<Ville> = (0..SIZE as Ville).collect();
+                let mut route = original.clone();
+                mutation_type.apply(&mut route, &mut rng);
+                assert_ne!(route, original);
+            }
+        }
+    }
+
+    #[test]
+    fn insertion_moves_a_single_city() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..100 {
+            let original: Vec<Ville> = (0..SIZE as Ville).collect();
+            let mut route = original.clone();
+            MutationType::Insertion.apply(&mut route, &mut rng);
+            // Retirer une ville bien choisie de chaque tournée redonne la même suite
+            let restored = (0..SIZE as Ville).any(|city| {
+                let without = |r: &[Ville]| r.iter().copied().filter(|&c| c != city).collect::<Vec<_>>();
+                without(&route) == without(&original)
+            });
+            assert!(restored, "{:?}", route);
+        }
+    }
+
+    #[test]
+    fn tiny_routes_are_left_unchanged() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for mutation_type in MutationType::ALL {
+            let mut route: Vec<Ville> = vec![0];
+            mutation_type.apply(&mut route, &mut rng);
+            assert_eq!(route, vec![0]);
+        }
+    }
+
+    #[test]
+    fn rates_are_read_per_operator() {
+        let rates = MutationRates { inversion: 0.1, scramble: 0.2, insertion: 0.3, displacement: 0.4 };
+        let read: Vec<f32> = MutationType::ALL.iter().map(|&m| rates.rate(m)).collect();
+        assert_eq!(read, vec![0.1, 0.2, 0.3, 0.4]);
+    }
+}
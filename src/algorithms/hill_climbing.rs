@@ -1,5 +1,6 @@
 use super::LocalSearch;
 use super::Metaheuristic;
+use super::{Elite, EliteTracker};
 
 use crate::eval::Evaluation;
 use crate::neighborhood::{NeighborFn, Neighborhood};
@@ -13,6 +14,7 @@ pub struct HillClimbing {
     neighborhood: Neighborhood,
     // rng: StdRng,
     iteration: usize,
+    elite: EliteTracker,
 }
 
 impl HillClimbing {
@@ -22,6 +24,7 @@ impl HillClimbing {
             max_steps,
             neighborhood,
             iteration: 0,
+            elite: EliteTracker::new(),
         }
     }
 }
@@ -64,6 +67,7 @@ impl<Eval: Evaluation> Metaheuristic<Eval> for HillClimbing {
             self.single_step(&mut population[i], &mut fitness[i], instance, evaluation);
         }
         self.iteration += 1;
+        self.elite.update(population, fitness, instance, self.iteration);
     }
 
     fn get_metrics(&self) -> std::collections::HashMap<String, f32> {
@@ -79,6 +83,9 @@ impl<Eval: Evaluation> Metaheuristic<Eval> for HillClimbing {
     fn get_iteration(&self) -> usize {
         self.iteration
     }
+    fn get_best_solution(&self) -> Option<&Elite> {
+        self.elite.best()
+    }
 }
 
 impl<Eval: Evaluation> LocalSearch<Eval> for HillClimbing {
@@ -0,0 +1,402 @@
+use crate::eval::Evaluation;
+use crate::shared::{Fitness, Instance, Solution};
+use crate::utils::rng::new_rng;
+
+use super::{AlgoEvent, Elite, GeneticAlgorithm, LocalSearch, Metaheuristic};
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum MigrationTopology {
+    /// Chaque île envoie ses migrants à l'île suivante
+    #[default]
+    Ring,
+    /// Chaque île envoie ses migrants à toutes les autres
+    FullyConnected,
+    /// Chaque île envoie ses migrants à une autre île tirée au hasard
+    Random,
+}
+
+impl MigrationTopology {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MigrationTopology::Ring => "ring",
+            MigrationTopology::FullyConnected => "fully connected",
+            MigrationTopology::Random => "random",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum MigrationPolicy {
+    /// Les meilleurs individus de l'île source remplacent les pires de l'île destination
+    #[default]
+    BestReplaceWorst,
+    /// Des individus tirés au hasard remplacent des individus tirés au hasard
+    Random,
+}
+
+impl MigrationPolicy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MigrationPolicy::BestReplaceWorst => "best-replace-worst",
+            MigrationPolicy::Random => "random",
+        }
+    }
+}
+
+/// Modèle en îles : plusieurs algorithmes génétiques évoluent en parallèle (threads rayon) sur des
+/// sous-populations contiguës de la population, et échangent des migrants toutes les `migration_interval` générations.
+///
+/// Paramètres :
+/// - `islands`: les algorithmes génétiques, un par île, de même taille de population
+/// - `migration_interval`: nombre de générations entre deux migrations (0 = pas de migration)
+/// - `migration_count`: nombre de migrants envoyés par île et par destination
+/// - `topology`: topologie de migration (anneau, complète, aléatoire)
+/// - `policy`: politique de migration (meilleurs remplacent les pires, aléatoire)
+pub struct IslandModel<LS> {
+    islands: Vec<GeneticAlgorithm<LS>>,
+    island_size: usize,
+    migration_interval: usize,
+    migration_count: usize,
+    topology: MigrationTopology,
+    policy: MigrationPolicy,
+
+    max_iter: usize,
+    iteration: usize,
+    migrations: usize,
+    events: Vec<AlgoEvent>,
+
+    rng: StdRng,
+
+    // Buffers réutilisés
+    order_buffer: Vec<usize>,
+    migrants_buffer: Vec<(usize, Solution, Fitness)>,
+}
+
+impl<LS> IslandModel<LS> {
+    pub fn new(
+        islands: Vec<GeneticAlgorithm<LS>>,
+        island_size: usize,
+        migration_interval: usize,
+        migration_count: usize,
+        topology: MigrationTopology,
+        policy: MigrationPolicy,
+        max_iter: usize,
+    ) -> Self {
+        IslandModel {
+            islands,
+            island_size,
+            migration_interval,
+            migration_count: migration_count.min(island_size),
+            topology,
+            policy,
+            max_iter,
+            iteration: 0,
+            migrations: 0,
+            events: Vec::new(),
+            rng: new_rng(),
+            order_buffer: vec![0; island_size],
+            migrants_buffer: Vec::new(),
+        }
+    }
+
+    fn destinations(&mut self, source: usize, destinations: &mut Vec<usize>) {
+        let island_count = self.islands.len();
+        destinations.clear();
+        match self.topology {
+            MigrationTopology::Ring => destinations.push((source + 1) % island_count),
+            MigrationTopology::FullyConnected => {
+                destinations.extend((0..island_count).filter(|&island| island != source))
+            }
+            MigrationTopology::Random => {
+                let offset = self.rng.random_range(1..island_count);
+                destinations.push((source + offset) % island_count);
+            }
+        }
+    }
+
+    /// Ordonne les indices de l'île par fitness croissante (politique best-replace-worst) ou aléatoirement
+    fn order_island(&mut self, fitness: &[Fitness]) {
+        for (i, idx) in self.order_buffer.iter_mut().enumerate() {
+            *idx = i;
+        }
+        match self.policy {
+            MigrationPolicy::BestReplaceWorst => {
+                self.order_buffer.sort_by(|&a, &b| fitness[a].total_cmp(&fitness[b]))
+            }
+            MigrationPolicy::Random => self.order_buffer.shuffle(&mut self.rng),
+        }
+    }
+
+    /// Copie les migrants de chaque île avant de les insérer, pour qu'un migrant ne voyage qu'une fois par migration
+    fn migrate(&mut self, population: &mut [Solution], fitness: &mut [Fitness]) {
+        let island_count = self.islands.len();
+        let size = self.island_size;
+        if island_count < 2 || self.migration_count == 0 {
+            return;
+        }
+
+        self.migrants_buffer.clear();
+        let mut destinations = Vec::with_capacity(island_count);
+        for source in 0..island_count {
+            let offset = source * size;
+            self.order_island(&fitness[offset..offset + size]);
+            self.destinations(source, &mut destinations);
+
+            for &destination in &destinations {
+                for &idx in &self.order_buffer[..self.migration_count] {
+                    self.migrants_buffer.push((destination, population[offset + idx].clone(), fitness[offset + idx]));
+                }
+            }
+        }
+
+        // Les migrants remplacent les pires individus (ou des individus aléatoires) de l'île destination
+        let migrants = std::mem::take(&mut self.migrants_buffer);
+        for destination in 0..island_count {
+            let offset = destination * size;
+            self.order_island(&fitness[offset..offset + size]);
+
+            let incoming = migrants.iter().filter(|(dest, _, _)| *dest == destination);
+            for ((_, migrant, migrant_fitness), &idx) in incoming.zip(self.order_buffer.iter().rev()) {
+                population[offset + idx].clone_from(migrant);
+                fitness[offset + idx] = *migrant_fitness;
+            }
+        }
+        self.migrants_buffer = migrants;
+
+        self.migrations += 1;
+        self.events.push(AlgoEvent {
+            iteration: self.iteration,
+            description: format!(
+                "Migration #{} ({} topology, {} policy, {} migrants per island)",
+                self.migrations,
+                self.topology.name(),
+                self.policy.name(),
+                self.migration_count
+            ),
+        });
+    }
+}
+
+impl<LS, Eval> Metaheuristic<Eval> for IslandModel<LS>
+where
+    Eval: Evaluation,
+    LS: LocalSearch<Eval>,
+{
+    fn step(
+        &mut self,
+        population: &mut [Solution],
+        fitness: &mut [Fitness],
+        instance: &Instance,
+        evaluation: &Eval,
+    ) {
+        let size = self.island_size;
+
+        // Chaque île évolue sur sa propre sous-population, en parallèle
+        self.islands
+            .par_iter_mut()
+            .zip(population.par_chunks_mut(size))
+            .zip(fitness.par_chunks_mut(size))
+            .for_each(|((island, island_population), island_fitness)| {
+                island.step(island_population, island_fitness, instance, evaluation);
+            });
+
+        self.iteration += 1;
+
+        if self.migration_interval > 0 && self.iteration.is_multiple_of(self.migration_interval) {
+            self.migrate(population, fitness);
+        }
+    }
+
+    fn get_metrics(&self) -> HashMap<String, f32> {
+        // Moyenne des métriques des îles
+        let mut metrics: HashMap<String, f32> = HashMap::new();
+        for island in &self.islands {
+            for (name, value) in Metaheuristic::<Eval>::get_metrics(island) {
+                *metrics.entry(name).or_insert(0.0) += value / self.islands.len() as f32;
+            }
+        }
+        metrics.insert("migrations".to_string(), self.migrations as f32);
+        metrics
+    }
+
+    fn get_metric_names(&self) -> Vec<String> {
+        let mut names = self
+            .islands
+            .first()
+            .map(Metaheuristic::<Eval>::get_metric_names)
+            .unwrap_or_default();
+        names.push("migrations".to_string());
+        names
+    }
+
+    fn stop_condition_met(&self) -> bool {
+        self.iteration >= self.max_iter
+    }
+
+    fn get_iteration(&self) -> usize {
+        self.iteration
+    }
+
+    fn get_best_solution(&self) -> Option<&Elite> {
+        self.islands
+            .iter()
+            .filter_map(Metaheuristic::<Eval>::get_best_solution)
+            .min_by(|a, b| a.fitness.total_cmp(&b.fitness))
+    }
+
+    fn take_events(&mut self) -> Vec<AlgoEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{CompetitionType, CrossoverType, DiversityConfig, GAParameters, MutationRates};
+    use crate::eval::{Objective, Weighted};
+    use crate::neighborhood::Swap;
+    use crate::shared::{TimeWindows, Ville, Window};
+
+    use ndarray::Array2;
+
+    const SIZE: usize = 6;
+    const ISLAND_SIZE: usize = 4;
+    // Ordre des individus d'une île : la fitness ne suit pas la position
+    const RANKS: [usize; ISLAND_SIZE] = [2, 0, 3, 1];
+
+    fn instance() -> Instance {
+        let distances = Array2::from_shape_fn((SIZE, SIZE), |(i, j)| (i as f32 - j as f32).abs());
+        let windows = (0..SIZE).map(|_| TimeWindows::single(Window { wstart: 0.0, wend: 1000.0 })).collect();
+        Instance::new(windows, distances)
+    }
+
+    fn model(
+        islands: usize,
+        migration_interval: usize,
+        migration_count: usize,
+        topology: MigrationTopology,
+        policy: MigrationPolicy,
+    ) -> IslandModel<Swap> {
+        let instance = instance();
+        let parameters = GAParameters {
+            crossover_rate: 0.8,
+            crossover_type: CrossoverType::OX,
+            mutation_rates: MutationRates::default(),
+            local_search_rate: 0.0,
+            elitism_rate: 0.25,
+            competition_participation_rate: 0.5,
+            competition_type: CompetitionType::Tournament,
+            max_iter: 100,
+            population_size: ISLAND_SIZE,
+            diversity_config: DiversityConfig::default(),
+        };
+        let islands = (0..islands).map(|_| GeneticAlgorithm::new(&instance, parameters, Swap::new(&instance))).collect();
+        IslandModel::new(islands, ISLAND_SIZE, migration_interval, migration_count, topology, policy, 100)
+    }
+
+    // L'individu de rang k de l'île i a la fitness 100 i + k, et sa solution le rappelle
+    fn tagged_population(islands: usize) -> (Vec<Solution>, Vec<Fitness>) {
+        let tags: Vec<(usize, usize)> = (0..islands).flat_map(|i| RANKS.iter().map(move |&k| (i, k))).collect();
+        let population = tags.iter().map(|&(i, k)| vec![i as Ville, k as Ville]).collect();
+        let fitness = tags.iter().map(|&(i, k)| (100 * i + k) as Fitness).collect();
+        (population, fitness)
+    }
+
+    // (île d'origine, rang) des individus de l'île `island`, triés
+    fn origins(population: &[Solution], fitness: &[Fitness], island: usize) -> Vec<(usize, usize)> {
+        let range = island * ISLAND_SIZE..(island + 1) * ISLAND_SIZE;
+        let mut origins: Vec<(usize, usize)> = population[range.clone()].iter()
+            .zip(&fitness[range])
+            .map(|(solution, &f)| {
+                assert_eq!(f, (100 * solution[0] + solution[1]) as Fitness);
+                (solution[0] as usize, solution[1] as usize)
+            })
+            .collect();
+        origins.sort_unstable();
+        origins
+    }
+
+    #[test]
+    fn ring_sends_the_best_to_the_next_island() {
+        let mut model = model(3, 1, 2, MigrationTopology::Ring, MigrationPolicy::BestReplaceWorst);
+        let (mut population, mut fitness) = tagged_population(3);
+        model.migrate(&mut population, &mut fitness);
+        for island in 0..3 {
+            let source = (island + 2) % 3;
+            let mut expected = vec![(island, 0), (island, 1), (source, 0), (source, 1)];
+            expected.sort_unstable();
+            assert_eq!(origins(&population, &fitness, island), expected);
+        }
+    }
+
+    #[test]
+    fn fully_connected_sends_to_every_other_island() {
+        let mut model = model(3, 1, 1, MigrationTopology::FullyConnected, MigrationPolicy::BestReplaceWorst);
+        let (mut population, mut fitness) = tagged_population(3);
+        model.migrate(&mut population, &mut fitness);
+        for island in 0..3 {
+            // Le meilleur de chacune des deux autres îles remplace les deux pires
+            let mut expected: Vec<(usize, usize)> = (0..3).filter(|&i| i != island).map(|i| (i, 0)).collect();
+            expected.extend([(island, 0), (island, 1)]);
+            expected.sort_unstable();
+            assert_eq!(origins(&population, &fitness, island), expected);
+        }
+    }
+
+    #[test]
+    fn random_topology_sends_to_one_other_island() {
+        for _ in 0..20 {
+            let mut model = model(3, 1, 1, MigrationTopology::Random, MigrationPolicy::BestReplaceWorst);
+            let (mut population, mut fitness) = tagged_population(3);
+            model.migrate(&mut population, &mut fitness);
+
+            let mut senders = Vec::new();
+            for island in 0..3 {
+                let origins = origins(&population, &fitness, island);
+                let foreign: Vec<(usize, usize)> = origins.iter().copied().filter(|&(i, _)| i != island).collect();
+                assert!(foreign.iter().all(|&(_, rank)| rank == 0));
+                // Les migrants remplacent les pires de l'île
+                let natives = ISLAND_SIZE - foreign.len();
+                assert_eq!(origins.iter().filter(|&&(i, _)| i == island).map(|&(_, k)| k).max(), Some(natives - 1));
+                senders.extend(foreign.iter().map(|&(i, _)| i));
+            }
+            // Une île, un migrant, une seule destination
+            senders.sort_unstable();
+            assert_eq!(senders, vec![0, 1, 2]);
+        }
+    }
+
+    #[test]
+    fn random_policy_moves_the_configured_number_of_migrants() {
+        let mut model = model(2, 1, 3, MigrationTopology::Ring, MigrationPolicy::Random);
+        let (mut population, mut fitness) = tagged_population(2);
+        model.migrate(&mut population, &mut fitness);
+        for island in 0..2 {
+            let origins = origins(&population, &fitness, island);
+            assert_eq!(origins.iter().filter(|&&(i, _)| i != island).count(), 3);
+        }
+    }
+
+    #[test]
+    fn migrations_are_reported_every_interval() {
+        let instance = instance();
+        let evaluation = Weighted::new(vec![(Objective::Distance, 1.0)]);
+        let mut model = model(2, 3, 1, MigrationTopology::Ring, MigrationPolicy::BestReplaceWorst);
+        let mut population: Vec<Solution> = (0..2 * ISLAND_SIZE).map(|_| (0..SIZE as Ville).collect()).collect();
+        let mut fitness: Vec<Fitness> = population.iter().map(|s| evaluation.score(&instance, s)).collect();
+
+        let mut iterations = Vec::new();
+        for _ in 0..7 {
+            model.step(&mut population, &mut fitness, &instance, &evaluation);
+            iterations.extend(Metaheuristic::<Weighted>::take_events(&mut model).iter().map(|event| event.iteration));
+        }
+        assert_eq!(iterations, vec![3, 6]);
+        assert_eq!(Metaheuristic::<Weighted>::get_metrics(&model)["migrations"], 2.0);
+    }
+}
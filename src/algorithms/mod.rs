@@ -2,6 +2,7 @@ use crate::eval::Evaluation;
 use crate::neighborhood::Neighborhood;
 use crate::shared::{Fitness, Instance, Solution};
 
+use ndarray::Array2;
 use std::collections::HashMap;
 
 mod hill_climbing;
@@ -14,10 +15,23 @@ pub use vns::VNS; // Added VNS public export
 
 mod ga;
 pub use ga::GeneticAlgorithm;
-pub use ga::{CompetitionType, CrossoverType};
+pub use ga::{CompetitionType, CrossoverType, DiversityConfig, DEFAULT_LOCAL_SEARCH_RATE, GAParameters, MutationRates, MutationType, ReplacementType};
 
 mod aco;
-pub use aco::ACO;
+pub use aco::{ACO, ACOHeuristicConfig, ACOVariant, ACOVariantConfig};
+
+mod elite;
+pub use elite::{Elite, EliteTracker};
+
+mod island;
+pub use island::{IslandModel, MigrationPolicy, MigrationTopology};
+
+/// Notable event of a run (e.g. a migration between islands), recorded in the run history
+#[derive(Clone)]
+pub struct AlgoEvent {
+    pub iteration: usize,
+    pub description: String,
+}
 
 
 
@@ -43,28 +57,17 @@ pub trait Metaheuristic<Eval: Evaluation>: Send + Sync {
     
     fn get_iteration(&self) -> usize;
 
-    fn get_best_solution(
-        &self,
-        population: &[Solution],
-        fitnesses: &[Fitness],
-    ) -> Option<(Vec<u32>, f32, f32)> {
-        if population.is_empty() || fitnesses.is_empty() {
-            return None;
-        }
+    // best solution ever found by the algorithm, with its full evaluation and when it was found
+    fn get_best_solution(&self) -> Option<&Elite>;
 
-        let (best_index, _) = fitnesses
-            .iter()
-            .enumerate()
-            .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())
-            .unwrap();
-
-        let best_solution = population[best_index].clone();
-        let best_fitness = fitnesses[best_index];
-
-        // Placeholder for violation calculation
-        let violation = 0.0;
+    // events that happened since the last call, drained by the caller
+    fn take_events(&mut self) -> Vec<AlgoEvent> {
+        Vec::new()
+    }
 
-        Some((best_solution, best_fitness, violation))
+    // pheromone levels on each edge, for algorithms that maintain them
+    fn get_pheromone_matrix(&self) -> Option<&Array2<f32>> {
+        None
     }
 }
 
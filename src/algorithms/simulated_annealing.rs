@@ -1,12 +1,13 @@
 use super::LocalSearch;
 use super::Metaheuristic;
+use super::{Elite, EliteTracker};
 
 use crate::eval::Evaluation;
 use crate::neighborhood::{NeighborFn, Neighborhood};
 use crate::shared::{Fitness, Instance, Solution};
+use crate::utils::rng::new_rng;
 
 use rand::Rng;
-use rand::SeedableRng;
 use rand::rngs::StdRng;
 use std::collections::HashMap;
 
@@ -40,6 +41,9 @@ pub struct SimulatedAnnealing {
     pub best_fitness: Option<Fitness>,
     pub backtracking_interval: usize,
 
+    // Élite exposée via Metaheuristic, alimentée par best_solution à la fin de chaque étape
+    elite: EliteTracker,
+
     iteration: usize,
 }
 
@@ -60,7 +64,7 @@ impl SimulatedAnnealing {
             initial_temperature,
             cooling_rate,
             stopping_temperature,
-            rng: StdRng::from_os_rng(),
+            rng: new_rng(),
             
             avg_acceptance_rate: None,
             acceptance_smoothing_factor,
@@ -74,6 +78,8 @@ impl SimulatedAnnealing {
             best_fitness: None,
             backtracking_interval,
 
+            elite: EliteTracker::new(),
+
             iteration: 0,
         }
     }
@@ -210,6 +216,10 @@ impl<Eval: Evaluation> Metaheuristic<Eval> for SimulatedAnnealing {
 
         self.iteration += 1;
 
+        if let (Some(best_sol), Some(best_fit)) = (&self.best_solution, self.best_fitness) {
+            self.elite.offer(best_sol, best_fit, instance, self.iteration);
+        }
+
         if self.backtracking_interval > 0 && self.iteration % self.backtracking_interval == 0 {
             if let (Some(best_sol), Some(best_fit)) = (&self.best_solution, self.best_fitness) {
                 for i in 0..population.len() {
@@ -250,6 +260,10 @@ impl<Eval: Evaluation> Metaheuristic<Eval> for SimulatedAnnealing {
         // Placeholder implementation, replace with actual iteration tracking if needed
         self.iteration
     }
+
+    fn get_best_solution(&self) -> Option<&Elite> {
+        self.elite.best()
+    }
 }
 
 impl<Eval: Evaluation> LocalSearch<Eval> for SimulatedAnnealing {
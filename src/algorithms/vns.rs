@@ -1,5 +1,6 @@
 use super::LocalSearch;
 use super::Metaheuristic;
+use super::{Elite, EliteTracker};
 use crate::eval::Evaluation;
 use crate::neighborhood::Neighborhood; // Use Enum
 use crate::shared::{Fitness, Instance, Solution};
@@ -9,6 +10,7 @@ pub struct VNS<LS> {
     local_search: LS,
     indexes: Vec<usize>,
     iteration: usize,
+    elite: EliteTracker,
 }
 
 impl<LS> VNS<LS> {
@@ -22,6 +24,7 @@ impl<LS> VNS<LS> {
             local_search,
             indexes: (0..neighborhoods_len).collect(),
             iteration: 0,
+            elite: EliteTracker::new(),
         }
     }
 }
@@ -58,6 +61,7 @@ impl<Eval: Evaluation,  LS: LocalSearch<Eval>> Metaheuristic<Eval> for VNS<LS> {
                 }
             }
         } self.iteration += 1;
+        self.elite.update(population, fitness, instance, self.iteration);
     }
     fn stop_condition_met(&self) -> bool {
         // VNS step runs completely. We rely on calling code or SA metrics.
@@ -70,4 +74,7 @@ impl<Eval: Evaluation,  LS: LocalSearch<Eval>> Metaheuristic<Eval> for VNS<LS> {
     fn get_iteration(&self) -> usize {
         self.iteration
     }
+    fn get_best_solution(&self) -> Option<&Elite> {
+        self.elite.best()
+    }
 }
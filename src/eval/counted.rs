@@ -0,0 +1,36 @@
+use std::cmp::Ordering;
+use std::sync::atomic::{self, AtomicU64};
+
+use super::{Evaluation, Fitness};
+
+use crate::shared::{Instance, Solution};
+
+/// Wraps an evaluation and counts the solutions it evaluates, so runs of different
+/// algorithms can be compared at equal evaluation budget.
+pub struct Counted<E> {
+    inner: E,
+    count: AtomicU64,
+}
+
+impl<E: Evaluation> Counted<E> {
+    pub fn new(inner: E) -> Self {
+        Self { inner, count: AtomicU64::new(0) }
+    }
+
+    /// Number of solutions evaluated so far (a comparison counts for two)
+    pub fn count(&self) -> u64 {
+        self.count.load(atomic::Ordering::Relaxed)
+    }
+}
+
+impl<E: Evaluation> Evaluation for Counted<E> {
+    fn score(&self, instance: &Instance, solution: &Solution) -> Fitness {
+        self.count.fetch_add(1, atomic::Ordering::Relaxed);
+        self.inner.score(instance, solution)
+    }
+
+    fn compare(&self, instance: &Instance, s1: &Solution, s2: &Solution) -> Ordering {
+        self.count.fetch_add(2, atomic::Ordering::Relaxed);
+        self.inner.compare(instance, s1, s2)
+    }
+}
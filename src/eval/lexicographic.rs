@@ -1,58 +1,132 @@
 use std::cmp::Ordering;
 
-use super::Evaluation;
-use super::utils::run_solution;
+use super::{Evaluation, Objective};
+use super::utils::{Eval, run_solution};
 
 use crate::shared::{Instance, Solution};
 
+// Facteur entre deux niveaux consécutifs du score : un niveau domine le suivant tant que
+// celui-ci reste sous ce facteur
+const LEVEL_SCALE: f32 = 1e4;
+// Au-delà, les niveaux suivants sont sous la précision d'un f32 et n'entrent plus dans le score
+const SCORED_LEVELS: usize = 3;
+
+/// Comparaison objectif par objectif, dans l'ordre de priorité donné.
+/// Sans mesure de retard dans l'ordre, le retard total passe en premier : une solution réalisable
+/// est toujours meilleure qu'une solution en retard.
 #[derive(Clone)]
 pub struct Lexicographic {
-    distance_first: bool,
+    objectives: Vec<Objective>,
 }
 
 impl Lexicographic {
+    /// Distance puis retard, ou retard puis distance
     pub fn new(distance_first: bool) -> Self {
-        Self { distance_first }
+        if distance_first {
+            Self::with_objectives(vec![Objective::Distance, Objective::Tardiness])
+        } else {
+            Self::with_objectives(vec![Objective::Tardiness, Objective::Distance])
+        }
+    }
+
+    pub fn with_objectives(objectives: Vec<Objective>) -> Self {
+        Self { objectives }
+    }
+
+    // Niveaux effectivement comparés, du plus prioritaire au moins prioritaire
+    fn levels(&self) -> impl Iterator<Item = Objective> + '_ {
+        let feasibility_first = !self.objectives.iter().any(Objective::is_lateness);
+        feasibility_first.then_some(Objective::Tardiness).into_iter()
+            .chain(self.objectives.iter().copied())
+    }
+
+    fn level_values(&self, eval: &Eval) -> Vec<f32> {
+        self.levels().map(|objective| objective.value(eval)).collect()
     }
 }
 
 impl Evaluation for Lexicographic {
     fn compare(&self, problem: &Instance, a: &Solution, b: &Solution) -> Ordering {
-        let eval_a = run_solution(problem, a);
-        let eval_b = run_solution(problem, b);
-
-        let ((a_primary, a_secondary), (b_primary, b_secondary)) = if self.distance_first {
-            (
-                (eval_a.total_distance, eval_a.violation_time),
-                (eval_b.total_distance, eval_b.violation_time),
-            )
-        } else {
-            (
-                (eval_a.violation_time, eval_a.total_distance),
-                (eval_b.violation_time, eval_b.total_distance),
-            )
-        };
+        let values_a = self.level_values(&run_solution(problem, a));
+        let values_b = self.level_values(&run_solution(problem, b));
 
-        if a_primary < b_primary {
-            Ordering::Less
-        } else if a_primary > b_primary {
-            Ordering::Greater
-        } else if a_secondary < b_secondary {
-            Ordering::Less
-        } else if a_secondary > b_secondary {
-            Ordering::Greater
-        } else {
-            Ordering::Equal
+        for (a_value, b_value) in values_a.iter().zip(&values_b) {
+            if a_value < b_value {
+                return Ordering::Less;
+            } else if a_value > b_value {
+                return Ordering::Greater;
+            }
         }
+        Ordering::Equal
     }
 
+    // Les niveaux sont combinés en base LEVEL_SCALE. Si le premier niveau mesure un retard, une
+    // solution en retard y vaut au moins 1 et passe au-dessus de toute solution réalisable,
+    // tant que les niveaux suivants restent sous LEVEL_SCALE.
     fn score(&self, problem: &Instance, solution: &Solution) -> f32 {
         let eval = run_solution(problem, solution);
-        let (distance, violation) = (eval.total_distance, eval.violation_time);
-        if violation > 0.0f32 {
-            violation
-        } else {
-            distance
+        let mut levels = self.levels().take(SCORED_LEVELS);
+        let Some(first) = levels.next() else {
+            return 0.0;
+        };
+        let mut score = first.value(&eval);
+        if first.is_lateness() && score > 0.0 {
+            score += 1.0;
+        }
+        for objective in levels {
+            score = score * LEVEL_SCALE + objective.value(&eval);
         }
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{TimeWindows, Window};
+
+    use ndarray::{Array2, array};
+
+    // [0, 1, 2] est réalisable mais plus long que [0, 2, 1], qui arrive en retard chez le client 1
+    fn instance() -> Instance {
+        let distances: Array2<f32> = array![[0.0, 10.0, 20.0], [10.0, 0.0, 10.0], [20.0, 5.0, 0.0]];
+        let windows = [(0.0, 1000.0), (0.0, 15.0), (0.0, 100.0)]
+            .map(|(wstart, wend)| TimeWindows::single(Window { wstart, wend }))
+            .to_vec();
+        Instance::new(windows, distances)
+    }
+
+    fn assert_ranks(evaluation: &Lexicographic, better: &Solution, worse: &Solution) {
+        let instance = instance();
+        assert_eq!(evaluation.compare(&instance, better, worse), Ordering::Less);
+        assert_eq!(evaluation.compare(&instance, worse, better), Ordering::Greater);
+        assert!(evaluation.score(&instance, better) < evaluation.score(&instance, worse));
+    }
+
+    #[test]
+    fn follows_the_priority_order() {
+        let (feasible, short): (Solution, Solution) = (vec![0, 1, 2], vec![0, 2, 1]);
+        assert_ranks(&Lexicographic::new(false), &feasible, &short);
+        assert_ranks(&Lexicographic::new(true), &short, &feasible);
+    }
+
+    #[test]
+    fn feasibility_comes_first_without_lateness_objective() {
+        let (feasible, short): (Solution, Solution) = (vec![0, 1, 2], vec![0, 2, 1]);
+        assert_ranks(&Lexicographic::with_objectives(vec![Objective::Distance]), &feasible, &short);
+        assert_ranks(&Lexicographic::with_objectives(vec![Objective::Makespan, Objective::Distance]), &feasible, &short);
+    }
+
+    #[test]
+    fn small_lateness_outweighs_any_feasible_score() {
+        let instance = instance();
+        let evaluation = Lexicographic::new(false);
+        let feasible = evaluation.score(&instance, &vec![0, 1, 2]);
+        assert_eq!(feasible, 40.0);
+
+        // Un retard infime place quand même la solution derrière toute solution réalisable
+        let mut barely_late = instance.clone();
+        barely_late.windows[1] = TimeWindows::single(Window { wstart: 0.0, wend: 24.9 });
+        assert!(evaluation.score(&barely_late, &vec![0, 2, 1]) >= LEVEL_SCALE);
     }
 }
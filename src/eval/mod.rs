@@ -1,11 +1,15 @@
+mod counted;
 mod lexicographic;
+mod objective;
 mod random;
 pub mod utils;
 mod weighted;
 
 use std::cmp::Ordering;
 
+pub use counted::Counted;
 pub use lexicographic::Lexicographic;
+pub use objective::{Objective, parse_order, parse_weights};
 pub use weighted::Weighted;
 
 pub type Fitness = f32;
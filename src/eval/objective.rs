@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use super::utils::Eval;
+
+/// Critère mesuré sur le résultat de `run_solution`, combiné par `Weighted` ou ordonné par `Lexicographic`
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Objective {
+    /// Distance totale de la tournée
+    Distance,
+    /// Date de retour au point de départ
+    Makespan,
+    /// Somme des dates de début de service
+    CompletionTime,
+    /// Attente totale avant l'ouverture des fenêtres
+    Waiting,
+    /// Somme des retards sur les fenêtres dures (`violation_time`)
+    Tardiness,
+    /// Nombre de villes servies après la fermeture d'une fenêtre dure (`nb_violations`)
+    LateCustomers,
+    /// Somme des retards sur les fenêtres dures, pondérés par le poids de priorité des villes
+    WeightedLateness,
+    /// Pénalités des fenêtres souples et des arrivées en avance
+    Penalty,
+}
+
+impl Objective {
+    pub const ALL: [Objective; 8] = [
+        Objective::Distance,
+        Objective::Makespan,
+        Objective::CompletionTime,
+        Objective::Waiting,
+        Objective::Tardiness,
+        Objective::LateCustomers,
+        Objective::WeightedLateness,
+        Objective::Penalty,
+    ];
+
+    pub fn value(&self, eval: &Eval) -> f32 {
+        match self {
+            Objective::Distance => eval.total_distance,
+            Objective::Makespan => eval.total_time,
+            Objective::CompletionTime => eval.completion_time,
+            Objective::Waiting => eval.delay,
+            Objective::Tardiness => eval.violation_time,
+            Objective::LateCustomers => eval.nb_violations as f32,
+            Objective::WeightedLateness => eval.weighted_lateness,
+            Objective::Penalty => eval.penalty,
+        }
+    }
+
+    /// Vrai pour les objectifs qui mesurent le non-respect des fenêtres, nuls pour une solution réalisable
+    pub fn is_lateness(&self) -> bool {
+        matches!(self, Objective::Tardiness | Objective::LateCustomers | Objective::WeightedLateness)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Objective::Distance => "Distance",
+            Objective::Makespan => "Makespan",
+            Objective::CompletionTime => "Completion Time",
+            Objective::Waiting => "Waiting",
+            Objective::Tardiness => "Tardiness",
+            Objective::LateCustomers => "Late Customers",
+            Objective::WeightedLateness => "Weighted Lateness",
+            Objective::Penalty => "Penalty",
+        }
+    }
+
+    /// Forme texte acceptée par `FromStr`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Objective::Distance => "distance",
+            Objective::Makespan => "makespan",
+            Objective::CompletionTime => "completion",
+            Objective::Waiting => "waiting",
+            Objective::Tardiness => "tardiness",
+            Objective::LateCustomers => "late",
+            Objective::WeightedLateness => "weighted-lateness",
+            Objective::Penalty => "penalty",
+        }
+    }
+}
+
+// Formes texte : "distance", "makespan", "completion", "waiting", "tardiness", "late", "weighted-lateness", "penalty"
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        Objective::ALL.into_iter()
+            .find(|objective| objective.key() == s)
+            .ok_or_else(|| {
+                let keys: Vec<&str> = Objective::ALL.iter().map(Objective::key).collect();
+                format!("Unknown objective '{}' (expected one of {})", s, keys.join(", "))
+            })
+    }
+}
+
+/// Lit une liste `objectif=poids` séparée par des virgules, par exemple "distance=1,tardiness=10000"
+pub fn parse_weights(s: &str) -> Result<Vec<(Objective, f32)>, String> {
+    s.split(',')
+        .filter(|term| !term.trim().is_empty())
+        .map(|term| {
+            let (objective, weight) = term.split_once('=')
+                .ok_or_else(|| format!("Expected objective=weight, got '{}'", term.trim()))?;
+            let weight: f32 = weight.trim().parse().map_err(|_| format!("Invalid weight '{}'", weight.trim()))?;
+            Ok((objective.parse()?, weight))
+        })
+        .collect()
+}
+
+/// Lit une liste d'objectifs séparée par des virgules, du plus prioritaire au moins prioritaire
+pub fn parse_order(s: &str) -> Result<Vec<Objective>, String> {
+    s.split(',')
+        .filter(|term| !term.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
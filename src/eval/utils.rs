@@ -1,12 +1,82 @@
-use crate::shared::{Instance, Solution};
-
+use crate::shared::{Instance, Solution, Window, WindowKind};
 
+#[derive(Clone, Debug)]
 pub struct Eval {
     pub total_distance: f32,
     pub violation_time: f32,
     pub total_time: f32,
     pub nb_violations: u32,
     pub delay: f32,
+    // Somme des dates de début de service des villes visitées, retour au départ exclu
+    pub completion_time: f32,
+    // Somme des retards sur fenêtres dures pondérés par le poids de priorité de chaque ville
+    pub weighted_lateness: f32,
+    // Pénalités des fenêtres souples et des arrivées en avance (voir `WindowPolicy`)
+    pub penalty: f32,
+}
+
+/// Arrivée dans une ville le long d'une tournée
+#[derive(Clone, Debug)]
+pub struct Visit {
+    pub node: usize,
+    // Distance de l'arête empruntée pour arriver
+    pub distance: f32,
+    pub arrival: f32,
+    // Attente jusqu'à la prochaine fenêtre ouverte
+    pub wait: f32,
+    pub service_start: f32,
+    // Fenêtre dans laquelle la ville est servie
+    pub window: Window,
+    // Retard sur une fenêtre dure, seul compté comme violation
+    pub violation: f32,
+    pub penalty: f32,
+    pub kind: WindowKind,
+}
+
+/// Parcourt une tournée depuis sa première ville, partie à l'instant 0, jusqu'au retour à celle-ci
+pub struct Visits<'a> {
+    instance: &'a Instance,
+    solution: &'a Solution,
+    idx: usize,
+    time: f32,
+}
+
+pub fn visits<'a>(instance: &'a Instance, solution: &'a Solution) -> Visits<'a> {
+    Visits { instance, solution, idx: 0, time: 0.0 }
+}
+
+impl Iterator for Visits<'_> {
+    type Item = Visit;
+
+    fn next(&mut self) -> Option<Visit> {
+        let (instance, solution) = (self.instance, self.solution);
+        if self.idx >= solution.len() {
+            return None;
+        }
+        let from = solution[self.idx] as usize;
+        let to = solution[(self.idx + 1) % solution.len()] as usize;
+
+        let arrival = self.time + instance.time_matrix[[from, to]];
+        let (service_start, window) = instance.windows[to].service(arrival);
+        let wait = service_start - arrival;
+        let lateness = (service_start - window.wend).max(0.0);
+        let policy = &instance.policies[to];
+        let violation = if policy.kind == WindowKind::Hard { lateness } else { 0.0 };
+
+        self.time = service_start;
+        self.idx += 1;
+        Some(Visit {
+            node: to,
+            distance: instance.distance_matrix[[from, to]],
+            arrival,
+            wait,
+            service_start,
+            window: *window,
+            violation,
+            penalty: policy.penalty(instance.weights[to], wait, lateness),
+            kind: policy.kind,
+        })
+    }
 }
 
 pub fn run_solution(instance: &Instance, solution: &Solution) -> Eval {
@@ -16,26 +86,24 @@ pub fn run_solution(instance: &Instance, solution: &Solution) -> Eval {
     let mut violation_time: f32 = 0.0;
     let mut nb_violations: u32 = 0;
     let mut delay: f32 = 0.0;
+    let mut completion_time: f32 = 0.0;
+    let mut weighted_lateness: f32 = 0.0;
+    let mut penalty: f32 = 0.0;
 
 
-    for idx in 0..(solution.len()) {
-
-        let from = solution[idx] as usize;
-        let to = solution[(idx + 1) % solution.len()] as usize;
-
-        total_time += instance.distance_matrix[[from, to]];
-        total_distance += instance.distance_matrix[[from, to]];
-        let next_start = instance.windows[to].wstart;
-        let next_end = instance.windows[to].wend;
-
-        if total_time<next_start {
-            delay += next_start - total_time;
-            total_time = next_start;
-        }
-        if total_time>next_end {
-            violation_time += total_time-next_end;
+    for (idx, visit) in visits(instance, solution).enumerate() {
+        total_distance += visit.distance;
+        delay += visit.wait;
+        total_time = visit.service_start;
+        if visit.violation > 0.0 {
+            violation_time += visit.violation;
+            weighted_lateness += instance.weights[visit.node] * visit.violation;
             nb_violations += 1;
         }
+        penalty += visit.penalty;
+        if idx + 1 < solution.len() {
+            completion_time += total_time;
+        }
     }
     Eval {
         total_distance, 
@@ -43,5 +111,8 @@ pub fn run_solution(instance: &Instance, solution: &Solution) -> Eval {
         total_time,
         nb_violations,
         delay,
+        completion_time,
+        weighted_lateness,
+        penalty,
     }
-}
\ No newline at end of file
+}
@@ -1,25 +1,28 @@
 use std::cmp::Ordering;
 
-use super::Evaluation;
+use super::{Evaluation, Objective};
 use super::utils::run_solution;
 
 use crate::shared::{Instance, Solution};
 
+/// Somme pondérée d'objectifs
 pub struct Weighted {
-    pub total_distance_weight: f32,
-    pub violation_time_weight: f32,
-    pub total_time_weight: f32,
-    pub delay_weight: f32,
+    pub terms: Vec<(Objective, f32)>,
+}
+
+impl Weighted {
+    pub fn new(terms: Vec<(Objective, f32)>) -> Self {
+        Self { terms }
+    }
 }
 
 impl Evaluation for Weighted {
     fn score(&self, problem: &Instance, solution: &Solution) -> f32 {
         let eval = run_solution(problem, solution);
 
-        self.total_distance_weight * eval.total_distance
-            + self.violation_time_weight * eval.violation_time
-            + self.total_time_weight * eval.total_time
-            + self.delay_weight * eval.delay
+        self.terms.iter()
+            .map(|(objective, weight)| weight * objective.value(&eval))
+            .sum()
     }
 
     fn compare(&self, problem: &Instance, a: &Solution, b: &Solution) -> Ordering {
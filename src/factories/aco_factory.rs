@@ -1,14 +1,12 @@
-use crate::algorithms::{ACO, Metaheuristic};
+use crate::algorithms::{ACO, ACOHeuristicConfig, ACOVariantConfig, Metaheuristic};
 use crate::shared::Instance;
 use crate::eval::Evaluation;
 use crate::neighborhood::{LocalSearchType, LocalSearchImpl};
 use super::Factory;
 
 pub struct ACOConfig {
-    pub evaporation_rate: f32,
-    pub alpha: f32,
-    pub beta: f32,
-    pub pheromone_deposit: f32,
+    pub variant_config: ACOVariantConfig,
+    pub heuristic_config: ACOHeuristicConfig,
     pub max_iter: usize,
     pub local_search_type: LocalSearchType,
 }
@@ -22,10 +20,8 @@ impl<Eval: Evaluation> Factory<Eval> for ACOFactory {
         let local_search = LocalSearchImpl::from_type(self.config.local_search_type, instance);
         let aco = ACO::new(
             instance,
-            self.config.evaporation_rate,
-            self.config.alpha,
-            self.config.beta,
-            self.config.pheromone_deposit,
+            self.config.variant_config,
+            self.config.heuristic_config,
             self.config.max_iter,
             local_search,
         );
@@ -0,0 +1,41 @@
+use crate::algorithms::{GeneticAlgorithm, IslandModel, Metaheuristic, MigrationPolicy, MigrationTopology};
+use crate::shared::Instance;
+use crate::eval::Evaluation;
+use crate::neighborhood::LocalSearchImpl;
+use super::{Factory, GAConfig};
+
+pub struct IslandGAConfig {
+    pub ga_config: GAConfig,
+    pub island_count: usize,
+    pub migration_interval: usize,
+    pub migration_count: usize,
+    pub migration_topology: MigrationTopology,
+    pub migration_policy: MigrationPolicy,
+}
+
+pub struct IslandGAFactory {
+    pub config: IslandGAConfig,
+}
+
+impl<Eval: Evaluation> Factory<Eval> for IslandGAFactory {
+    fn build(&self, instance: &Instance) -> Box<dyn Metaheuristic<Eval>> {
+        let ga_config = &self.config.ga_config;
+        let islands = (0..self.config.island_count)
+            .map(|_| {
+                let local_search = LocalSearchImpl::from_type(ga_config.local_search_type, instance);
+                GeneticAlgorithm::new(instance, ga_config.parameters, local_search)
+            })
+            .collect();
+
+        let island_model = IslandModel::new(
+            islands,
+            ga_config.parameters.population_size,
+            self.config.migration_interval,
+            self.config.migration_count,
+            self.config.migration_topology,
+            self.config.migration_policy,
+            ga_config.parameters.max_iter,
+        );
+        Box::new(island_model)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::algorithms::{Metaheuristic, CompetitionType, CrossoverType};
+use crate::algorithms::{Metaheuristic, ACOHeuristicConfig, ACOVariant, ACOVariantConfig, CompetitionType, CrossoverType, DEFAULT_LOCAL_SEARCH_RATE, DiversityConfig, GAParameters, MigrationPolicy, MigrationTopology, MutationRates, ReplacementType};
 use crate::shared::Instance;
 use crate::eval::Evaluation;
 use crate::neighborhood::{NeighborhoodType, LocalSearchType};
@@ -23,12 +23,17 @@ mod hc_factory;
 pub use hc_factory::HCFactory;
 pub use hc_factory::HCConfig;
 
+mod island_factory;
+pub use island_factory::IslandGAFactory;
+pub use island_factory::IslandGAConfig;
+
 pub enum AlgoConfig {
     SimulatedAnnealing(SAConfig),
     GeneticAlgorithm(GAConfig),
     AntColonyOptimization(ACOConfig),
     VariableNeighborhoodSearch(VNSConfig),
     HillClimbing(HCConfig),
+    IslandGeneticAlgorithm(IslandGAConfig),
 }
 
 impl AlgoConfig {
@@ -39,6 +44,7 @@ impl AlgoConfig {
             AlgoConfig::AntColonyOptimization(config) => AlgoFactories::ACOFactory(ACOFactory { config }),
             AlgoConfig::VariableNeighborhoodSearch(config) => AlgoFactories::VNSFactory(VNSFactory { config }),
             AlgoConfig::HillClimbing(config) => AlgoFactories::HCFactory(HCFactory { config }),
+            AlgoConfig::IslandGeneticAlgorithm(config) => AlgoFactories::IslandGAFactory(IslandGAFactory { config }),
         }
     }
 }
@@ -55,6 +61,7 @@ pub enum AlgoFactories {
     ACOFactory(ACOFactory),
     VNSFactory(VNSFactory),
     HCFactory(HCFactory),
+    IslandGAFactory(IslandGAFactory),
 }
 
 impl AlgoFactories {
@@ -65,6 +72,7 @@ impl AlgoFactories {
             AlgoFactories::ACOFactory(_) => "Ant Colony Optimization",
             AlgoFactories::VNSFactory(_) => "Variable Neighborhood Search",
             AlgoFactories::HCFactory(_) => "Hill Climbing",
+            AlgoFactories::IslandGAFactory(_) => "Island Genetic Algorithm",
         }
     }
     pub fn build<Eval: Evaluation>(&self, instance: &Instance) -> Box<dyn Metaheuristic<Eval>> {
@@ -74,6 +82,7 @@ impl AlgoFactories {
             AlgoFactories::ACOFactory(factory) => factory.build(instance),
             AlgoFactories::VNSFactory(factory) => factory.build(instance),
             AlgoFactories::HCFactory(factory) => factory.build(instance),
+            AlgoFactories::IslandGAFactory(factory) => factory.build(instance),
         }
     }
 }
@@ -100,7 +109,24 @@ pub struct AlgoParams {
     pub elitism_rate: Option<f32>,
     pub competition_participation_rate: Option<f32>,
     pub competition_type: Option<CompetitionType>,
+    pub inversion_rate: Option<f32>,
+    pub scramble_rate: Option<f32>,
+    pub insertion_rate: Option<f32>,
+    pub displacement_rate: Option<f32>,
+    pub local_search_rate: Option<f32>,
+    // Ancien taux de mutation, appliqué à la recherche locale quand local_search_rate n'est pas donné
     pub mutation_rate: Option<f32>,
+    pub replacement_type: Option<ReplacementType>,
+    pub sharing_radius: Option<f32>,
+    pub duplicate_elimination: Option<bool>,
+    pub restart_diversity_threshold: Option<f32>,
+    
+    // Island model Genetic Algorithm
+    pub island_count: Option<usize>,
+    pub migration_interval: Option<usize>,
+    pub migration_count: Option<usize>,
+    pub migration_topology: Option<MigrationTopology>,
+    pub migration_policy: Option<MigrationPolicy>,
     
     // Hill Climbing
     pub step: Option<usize>,
@@ -110,6 +136,15 @@ pub struct AlgoParams {
     pub alpha: Option<f32>,
     pub beta: Option<f32>,
     pub pheromone_deposit: Option<f32>,
+    pub aco_variant: Option<ACOVariant>,
+    pub q0: Option<f32>,
+    pub local_evaporation_rate: Option<f32>,
+    pub p_best: Option<f32>,
+    pub stagnation_limit: Option<usize>,
+    pub waiting_weight: Option<f32>,
+    pub urgency_weight: Option<f32>,
+    pub exclude_unreachable: Option<bool>,
+    pub candidate_lists: Option<bool>,
     
     // VNS
     pub neighborhoods: Option<Vec<NeighborhoodType>>,
@@ -189,11 +224,81 @@ impl AlgoParams {
         self
     }
     
+    pub fn inversion_rate(mut self, val: f32) -> Self {
+        self.inversion_rate = Some(val);
+        self
+    }
+    
+    pub fn scramble_rate(mut self, val: f32) -> Self {
+        self.scramble_rate = Some(val);
+        self
+    }
+    
+    pub fn insertion_rate(mut self, val: f32) -> Self {
+        self.insertion_rate = Some(val);
+        self
+    }
+    
+    pub fn displacement_rate(mut self, val: f32) -> Self {
+        self.displacement_rate = Some(val);
+        self
+    }
+    
+    pub fn local_search_rate(mut self, val: f32) -> Self {
+        self.local_search_rate = Some(val);
+        self
+    }
+    
     pub fn mutation_rate(mut self, val: f32) -> Self {
         self.mutation_rate = Some(val);
         self
     }
     
+    pub fn replacement_type(mut self, val: ReplacementType) -> Self {
+        self.replacement_type = Some(val);
+        self
+    }
+    
+    pub fn sharing_radius(mut self, val: f32) -> Self {
+        self.sharing_radius = Some(val);
+        self
+    }
+    
+    pub fn duplicate_elimination(mut self, val: bool) -> Self {
+        self.duplicate_elimination = Some(val);
+        self
+    }
+    
+    pub fn restart_diversity_threshold(mut self, val: f32) -> Self {
+        self.restart_diversity_threshold = Some(val);
+        self
+    }
+    
+    pub fn island_count(mut self, val: usize) -> Self {
+        self.island_count = Some(val);
+        self
+    }
+    
+    pub fn migration_interval(mut self, val: usize) -> Self {
+        self.migration_interval = Some(val);
+        self
+    }
+    
+    pub fn migration_count(mut self, val: usize) -> Self {
+        self.migration_count = Some(val);
+        self
+    }
+    
+    pub fn migration_topology(mut self, val: MigrationTopology) -> Self {
+        self.migration_topology = Some(val);
+        self
+    }
+    
+    pub fn migration_policy(mut self, val: MigrationPolicy) -> Self {
+        self.migration_policy = Some(val);
+        self
+    }
+    
     pub fn step(mut self, val: usize) -> Self {
         self.step = Some(val);
         self
@@ -219,6 +324,51 @@ impl AlgoParams {
         self
     }
     
+    pub fn aco_variant(mut self, val: ACOVariant) -> Self {
+        self.aco_variant = Some(val);
+        self
+    }
+    
+    pub fn q0(mut self, val: f32) -> Self {
+        self.q0 = Some(val);
+        self
+    }
+    
+    pub fn local_evaporation_rate(mut self, val: f32) -> Self {
+        self.local_evaporation_rate = Some(val);
+        self
+    }
+    
+    pub fn p_best(mut self, val: f32) -> Self {
+        self.p_best = Some(val);
+        self
+    }
+    
+    pub fn stagnation_limit(mut self, val: usize) -> Self {
+        self.stagnation_limit = Some(val);
+        self
+    }
+    
+    pub fn waiting_weight(mut self, val: f32) -> Self {
+        self.waiting_weight = Some(val);
+        self
+    }
+    
+    pub fn urgency_weight(mut self, val: f32) -> Self {
+        self.urgency_weight = Some(val);
+        self
+    }
+    
+    pub fn exclude_unreachable(mut self, val: bool) -> Self {
+        self.exclude_unreachable = Some(val);
+        self
+    }
+    
+    pub fn candidate_lists(mut self, val: bool) -> Self {
+        self.candidate_lists = Some(val);
+        self
+    }
+    
     pub fn neighborhoods(mut self, val: Vec<NeighborhoodType>) -> Self {
         self.neighborhoods = Some(val);
         self
@@ -277,27 +427,56 @@ impl AlgoParams {
     
     pub fn build_ga_config(&self) -> Result<GAConfig, String> {
         Ok(GAConfig {
-            crossover_rate: self.crossover_rate
-                .ok_or("Missing parameter: crossover_rate for Genetic Algorithm")?,
-            crossover_type: self.crossover_type
-                .ok_or("Missing parameter: crossover_type for Genetic Algorithm")?,
-            elitism_rate: self.elitism_rate
-                .ok_or("Missing parameter: elitism_rate for Genetic Algorithm")?,
-            competition_participation_rate: self.competition_participation_rate
-                .ok_or("Missing parameter: competition_participation_rate for Genetic Algorithm")?,
-            competition_type: self.competition_type
-                .ok_or("Missing parameter: competition_type for Genetic Algorithm")?,
-            max_iter: self.max_iter
-                .ok_or("Missing parameter: max_iter for Genetic Algorithm")?,
-            population_size: self.population_size
-                .ok_or("Missing parameter: population_size for Genetic Algorithm")?,
-            mutation_rate: self.mutation_rate
-                .ok_or("Missing parameter: mutation_rate for Genetic Algorithm")?,
+            parameters: GAParameters {
+                crossover_rate: self.crossover_rate
+                    .ok_or("Missing parameter: crossover_rate for Genetic Algorithm")?,
+                crossover_type: self.crossover_type
+                    .ok_or("Missing parameter: crossover_type for Genetic Algorithm")?,
+                elitism_rate: self.elitism_rate
+                    .ok_or("Missing parameter: elitism_rate for Genetic Algorithm")?,
+                competition_participation_rate: self.competition_participation_rate
+                    .ok_or("Missing parameter: competition_participation_rate for Genetic Algorithm")?,
+                competition_type: self.competition_type
+                    .ok_or("Missing parameter: competition_type for Genetic Algorithm")?,
+                max_iter: self.max_iter
+                    .ok_or("Missing parameter: max_iter for Genetic Algorithm")?,
+                population_size: self.population_size
+                    .ok_or("Missing parameter: population_size for Genetic Algorithm")?,
+                mutation_rates: MutationRates {
+                    inversion: self.inversion_rate.unwrap_or(MutationRates::default().inversion),
+                    scramble: self.scramble_rate.unwrap_or(MutationRates::default().scramble),
+                    insertion: self.insertion_rate.unwrap_or(MutationRates::default().insertion),
+                    displacement: self.displacement_rate.unwrap_or(MutationRates::default().displacement),
+                },
+                local_search_rate: self.local_search_rate
+                    .or(self.mutation_rate)
+                    .unwrap_or(DEFAULT_LOCAL_SEARCH_RATE),
+                diversity_config: DiversityConfig {
+                    replacement_type: self.replacement_type.unwrap_or_default(),
+                    sharing_radius: self.sharing_radius.unwrap_or(DiversityConfig::default().sharing_radius),
+                    duplicate_elimination: self.duplicate_elimination.unwrap_or(false),
+                    restart_threshold: self.restart_diversity_threshold.unwrap_or(0.0),
+                },
+            },
             local_search_type: self.local_search_type
                 .ok_or("Missing parameter: local_search_type for Genetic Algorithm")?,
         })
     }
     
+    pub fn build_island_ga_config(&self) -> Result<IslandGAConfig, String> {
+        Ok(IslandGAConfig {
+            ga_config: self.build_ga_config()?,
+            island_count: self.island_count
+                .ok_or("Missing parameter: island_count for Island Genetic Algorithm")?,
+            migration_interval: self.migration_interval
+                .ok_or("Missing parameter: migration_interval for Island Genetic Algorithm")?,
+            migration_count: self.migration_count
+                .ok_or("Missing parameter: migration_count for Island Genetic Algorithm")?,
+            migration_topology: self.migration_topology.unwrap_or_default(),
+            migration_policy: self.migration_policy.unwrap_or_default(),
+        })
+    }
+    
     pub fn build_hc_config(&self) -> Result<HCConfig, String> {
         Ok(HCConfig {
             step: self.step
@@ -311,14 +490,27 @@ impl AlgoParams {
     
     pub fn build_aco_config(&self) -> Result<ACOConfig, String> {
         Ok(ACOConfig {
-            evaporation_rate: self.evaporation_rate
-                .ok_or("Missing parameter: evaporation_rate for Ant Colony Optimization")?,
-            alpha: self.alpha
-                .ok_or("Missing parameter: alpha for Ant Colony Optimization")?,
-            beta: self.beta
-                .ok_or("Missing parameter: beta for Ant Colony Optimization")?,
-            pheromone_deposit: self.pheromone_deposit
-                .ok_or("Missing parameter: pheromone_deposit for Ant Colony Optimization")?,
+            variant_config: ACOVariantConfig {
+                evaporation_rate: self.evaporation_rate
+                    .ok_or("Missing parameter: evaporation_rate for Ant Colony Optimization")?,
+                pheromone_deposit: self.pheromone_deposit
+                    .ok_or("Missing parameter: pheromone_deposit for Ant Colony Optimization")?,
+                variant: self.aco_variant.unwrap_or_default(),
+                q0: self.q0.unwrap_or(ACOVariantConfig::default().q0),
+                local_evaporation_rate: self.local_evaporation_rate.unwrap_or(ACOVariantConfig::default().local_evaporation_rate),
+                p_best: self.p_best.unwrap_or(ACOVariantConfig::default().p_best),
+                stagnation_limit: self.stagnation_limit.unwrap_or(ACOVariantConfig::default().stagnation_limit),
+            },
+            heuristic_config: ACOHeuristicConfig {
+                alpha: self.alpha
+                    .ok_or("Missing parameter: alpha for Ant Colony Optimization")?,
+                beta: self.beta
+                    .ok_or("Missing parameter: beta for Ant Colony Optimization")?,
+                waiting_weight: self.waiting_weight.unwrap_or(ACOHeuristicConfig::default().waiting_weight),
+                urgency_weight: self.urgency_weight.unwrap_or(ACOHeuristicConfig::default().urgency_weight),
+                exclude_unreachable: self.exclude_unreachable.unwrap_or(ACOHeuristicConfig::default().exclude_unreachable),
+                candidate_lists: self.candidate_lists.unwrap_or(ACOHeuristicConfig::default().candidate_lists),
+            },
             max_iter: self.max_iter
                 .ok_or("Missing parameter: max_iter for Ant Colony Optimization")?,
             local_search_type: self.local_search_type
@@ -353,6 +545,9 @@ impl AlgoParams {
             AlgoType::VariableNeighborhoodSearch => {
                 Ok(AlgoConfig::VariableNeighborhoodSearch(self.build_vns_config()?))
             },
+            AlgoType::IslandGeneticAlgorithm => {
+                Ok(AlgoConfig::IslandGeneticAlgorithm(self.build_island_ga_config()?))
+            },
         }
     }
 }
@@ -364,4 +559,60 @@ pub enum AlgoType {
     HillClimbing,
     AntColonyOptimization,
     VariableNeighborhoodSearch,
+    IslandGeneticAlgorithm,
+}
+
+impl AlgoType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlgoType::SimulatedAnnealing => "Simulated Annealing",
+            AlgoType::GeneticAlgorithm => "Genetic Algorithm",
+            AlgoType::HillClimbing => "Hill Climbing",
+            AlgoType::AntColonyOptimization => "Ant Colony Optimization",
+            AlgoType::VariableNeighborhoodSearch => "Variable Neighborhood Search",
+            AlgoType::IslandGeneticAlgorithm => "Island Genetic Algorithm",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::MutationType;
+
+    // Paramètres obligatoires de l'algorithme génétique, sans taux de mutation ni de recherche locale
+    fn ga_params() -> AlgoParams {
+        AlgoParams::new()
+            .crossover_rate(0.8)
+            .crossover_type(CrossoverType::PMX)
+            .elitism_rate(0.1)
+            .competition_participation_rate(0.5)
+            .competition_type(CompetitionType::Tournament)
+            .population_size(10)
+            .max_iter(10)
+            .local_search_type(LocalSearchType::Swap)
+    }
+
+    #[test]
+    fn ga_rates_default_to_mutation_and_local_search() {
+        let parameters = ga_params().build_ga_config().unwrap().parameters;
+        assert!(parameters.mutation_rates == MutationRates::default());
+        assert_eq!(parameters.local_search_rate, DEFAULT_LOCAL_SEARCH_RATE);
+        assert!(MutationType::ALL.iter().any(|&m| parameters.mutation_rates.rate(m) > 0.0));
+    }
+
+    #[test]
+    fn mutation_rate_sets_the_local_search_rate() {
+        let parameters = ga_params().mutation_rate(0.3).build_ga_config().unwrap().parameters;
+        assert_eq!(parameters.local_search_rate, 0.3);
+
+        let parameters = ga_params().mutation_rate(0.3).local_search_rate(0.0).build_ga_config().unwrap().parameters;
+        assert_eq!(parameters.local_search_rate, 0.0);
+    }
+
+    #[test]
+    fn missing_ga_parameter_is_an_error() {
+        let params = AlgoParams::new().crossover_rate(0.8);
+        assert!(params.build_ga_config().is_err());
+    }
 }
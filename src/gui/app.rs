@@ -18,7 +18,14 @@ impl eframe::App for TspApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Logic
         self.state.update_solvers();
-        if self.state.runs.iter().any(|r| r.is_running) {
+        let screenshot = ctx.input(|i| i.raw.events.iter().find_map(|event| match event {
+            egui::Event::Screenshot { image, .. } => Some((image.clone(), i.pixels_per_point)),
+            _ => None,
+        }));
+        if let Some((image, pixels_per_point)) = screenshot {
+            self.state.save_screenshot(&image, pixels_per_point);
+        }
+        if self.state.runs.iter().any(|r| r.is_running()) {
             ctx.request_repaint();
         }
 
@@ -31,15 +38,24 @@ impl eframe::App for TspApp {
             AppPhase::Running => {
                 // Top bar for navigation/control could go here if needed
                 egui::TopBottomPanel::top("top_bar").show(ctx, |ui| {
-                    ui.horizontal(|ui| {
+                    ui.horizontal_wrapped(|ui| {
                         if ui.button("Back to Config").clicked() {
                             self.state.phase = AppPhase::Configuration;
-                            self.state.runs.clear();
-                            self.state.selected_run_index = None;
+                            self.state.clear_runs();
                         }
                         ui.separator();
                         ui.selectable_value(&mut self.state.view_mode, ViewMode::Grid, "Grid View");
                         ui.selectable_value(&mut self.state.view_mode, ViewMode::Statistics, "Statistics View");
+                        ui.separator();
+                        components::controls::global_controls(ui, &mut self.state);
+                        ui.separator();
+                        components::controls::solution_loader(ui, &mut self.state);
+                        ui.separator();
+                        ui.label("Export dir:");
+                        ui.add(egui::TextEdit::singleline(&mut self.state.export_dir).desired_width(120.0));
+                        if let Some(status) = &self.state.export_status {
+                            ui.label(status);
+                        }
                     });
                 });
 
@@ -49,7 +65,7 @@ impl eframe::App for TspApp {
                     } else {
                         match self.state.view_mode {
                             ViewMode::Grid => components::grid::show(ui, &mut self.state),
-                            ViewMode::Statistics => components::statistics::show(ui, &self.state),
+                            ViewMode::Statistics => components::statistics::show(ui, &mut self.state),
                         }
                     }
                 });
@@ -0,0 +1,130 @@
+use eframe::egui;
+use crate::factories::AlgoType;
+use crate::gui::state::{AppState, RunState};
+use crate::gui::worker::{RunCommand, RunStatus};
+
+// Algorithmes proposés pour poursuivre un run (ceux configurables depuis l'écran de configuration)
+const CONTINUE_ALGO_TYPES: [AlgoType; 5] = [
+    AlgoType::SimulatedAnnealing,
+    AlgoType::HillClimbing,
+    AlgoType::GeneticAlgorithm,
+    AlgoType::IslandGeneticAlgorithm,
+    AlgoType::AntColonyOptimization,
+];
+
+/// Action demandée depuis les contrôles d'un run, appliquée une fois l'affichage terminé
+pub enum RunAction {
+    Command(RunCommand),
+    Restart,
+    ContinueFromBest(AlgoType),
+}
+
+/// Boutons Pause/Resume, Step, Stop et Restart d'un run
+pub fn run_controls(ui: &mut egui::Ui, run: &RunState, step_count: usize) -> Option<RunAction> {
+    let mut action = None;
+    ui.horizontal(|ui| {
+        match run.status {
+            RunStatus::Running => {
+                if ui.small_button("Pause").clicked() {
+                    action = Some(RunAction::Command(RunCommand::Pause));
+                }
+            }
+            RunStatus::Paused => {
+                if ui.small_button("Resume").clicked() {
+                    action = Some(RunAction::Command(RunCommand::Resume));
+                }
+            }
+            RunStatus::Finished | RunStatus::Stopped | RunStatus::Loaded => {}
+        }
+        if run.status.is_active() {
+            if ui.small_button(format!("Step {}", step_count)).clicked() {
+                action = Some(RunAction::Command(RunCommand::Step(step_count)));
+            }
+            if ui.small_button("Stop").clicked() {
+                action = Some(RunAction::Command(RunCommand::Stop));
+            }
+        }
+        // Une solution chargée n'a pas de run à relancer
+        if run.status != RunStatus::Loaded
+            && ui.small_button("Restart").on_hover_text("Restart from scratch with the same seed").clicked()
+        {
+            action = Some(RunAction::Restart);
+        }
+    });
+    action
+}
+
+/// Choix de l'algorithme et bouton pour poursuivre depuis la meilleure solution du run
+pub fn continue_controls(ui: &mut egui::Ui, run: &RunState, algo_type: &mut AlgoType) -> Option<RunAction> {
+    let mut action = None;
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt("continue_algo_type")
+            .selected_text(algo_type.name())
+            .show_ui(ui, |ui| {
+                for candidate in CONTINUE_ALGO_TYPES {
+                    ui.selectable_value(algo_type, candidate, candidate.name());
+                }
+            });
+        // Une solution invalide (doublons, villes manquantes) ne peut pas servir de point de départ
+        let enabled = !run.current_solution_path.is_empty() && run.validation_issues.is_empty();
+        let label = if run.status == RunStatus::Loaded { "Use as seed" } else { "Continue from best" };
+        if ui.add_enabled(enabled, egui::Button::new(label)).clicked() {
+            action = Some(RunAction::ContinueFromBest(*algo_type));
+        }
+    });
+    action
+}
+
+/// Contrôles appliqués à tous les runs
+pub fn global_controls(ui: &mut egui::Ui, state: &mut AppState) {
+    if ui.button("Pause All").clicked() {
+        state.send_command_all(RunCommand::Pause);
+    }
+    if ui.button("Resume All").clicked() {
+        state.send_command_all(RunCommand::Resume);
+    }
+    ui.add(egui::DragValue::new(&mut state.step_count).range(1..=1_000_000).prefix("N = "));
+    if ui.button("Step N All").clicked() {
+        state.send_command_all(RunCommand::Step(state.step_count));
+    }
+    if ui.button("Stop All").clicked() {
+        state.send_command_all(RunCommand::Stop);
+    }
+    if ui.button("Restart All").clicked() {
+        state.restart_all();
+    }
+}
+
+pub fn apply(state: &mut AppState, run_idx: usize, action: RunAction) {
+    match action {
+        RunAction::Command(command) => {
+            if let Some(run) = state.runs.get(run_idx) {
+                state.send_command(run.id, command);
+            }
+        }
+        RunAction::Restart => state.restart_run(run_idx),
+        RunAction::ContinueFromBest(algo_type) => state.continue_from_best(run_idx, algo_type),
+    }
+}
+
+/// Chargement d'un fichier `.sol` comme run en lecture seule
+pub fn solution_loader(ui: &mut egui::Ui, state: &mut AppState) -> bool {
+    let mut loaded = false;
+    ui.horizontal(|ui| {
+        ui.label("Solution:");
+        ui.add(egui::TextEdit::singleline(&mut state.solution_path).desired_width(150.0));
+        if ui.add_enabled(state.instance.is_some(), egui::Button::new("Load solution")).clicked() {
+            match state.load_solution_file() {
+                Ok(()) => {
+                    state.solution_status = None;
+                    loaded = true;
+                }
+                Err(e) => state.solution_status = Some(format!("Failed to load solution: {}", e)),
+            }
+        }
+        if let Some(status) = &state.solution_status {
+            ui.colored_label(egui::Color32::RED, status);
+        }
+    });
+    loaded
+}
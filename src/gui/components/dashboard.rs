@@ -1,13 +1,41 @@
 use eframe::egui;
-use crate::gui::state::AppState;
+use crate::gui::state::{AppState, ViewTab};
+use super::controls::{self, continue_controls, run_controls};
+use super::export_menu::run_export_menu;
+use crate::gui::export::PlotKind;
 use crate::gui::tabs;
+use crate::factories::AlgoType;
 
 pub fn show(ui: &mut egui::Ui, state: &mut AppState, run_idx: usize) {
     // Detailed View (Dashboard)
-    if ui.button("Back to Grid").clicked() {
-        state.selected_run_index = None;
+    let mut action = None;
+    ui.horizontal(|ui| {
+        if ui.button("Back to Grid").clicked() {
+            state.selected_run_index = None;
+        }
+        if let Some(run) = state.runs.get(run_idx) {
+            ui.separator();
+            ui.label(egui::RichText::new(&run.name).strong());
+            let run_action = run_controls(ui, run, state.step_count);
+            ui.separator();
+            let continue_action = continue_controls(ui, run, &mut state.continue_algo_type);
+            action = run_action.or(continue_action);
+            ui.separator();
+            run_export_menu(ui, state, run_idx);
+        }
+    });
+    if let Some(action) = action {
+        controls::apply(state, run_idx, action);
+        // Une poursuite sélectionne le nouveau run, qui sera affiché à la prochaine frame
+        if state.selected_run_index != Some(run_idx) {
+            return;
+        }
     }
-    
+
+    if let Some(run) = state.runs.get(run_idx) {
+        tabs::report::show(ui, run);
+    }
+
     if run_idx < state.runs.len() {
         // We need to split state borrowing here to avoid conflicts if possible, 
         // but since we are passing mutable state to tabs, we might need to be careful.
@@ -19,6 +47,11 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState, run_idx: usize) {
         let log_scale = &mut state.violation_log_scale;
         let left_col_ratio = &mut state.left_col_ratio;
         let right_top_ratio = &mut state.right_top_ratio;
+        let edit_move = &mut state.route_edit_move;
+        let x_axis = &mut state.x_axis;
+        let view_tab = &mut state.view_tab;
+        let heatmap_pheromone = &mut state.heatmap_pheromone;
+        let mut launch_local_search = false;
 
         use egui::{Layout, Align, Vec2};
         
@@ -30,11 +63,27 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState, run_idx: usize) {
         let left_w = (available.x - divider_w) * left_col_ratio.clamp(0.1, 0.9);
         let right_w = available.x - divider_w - left_w;
 
+        // Zones des figures, mémorisées pour l'export PNG
+        let mut rects = Vec::new();
+
         ui.horizontal(|ui| {
-            // Left column (Route)
-            ui.allocate_ui_with_layout(Vec2::new(left_w, available.y), Layout::top_down(Align::Min), |ui| {
-                tabs::route::show(ui, run, graph_instance);
+            // Left column (Route or population diagnostics)
+            let left = ui.allocate_ui_with_layout(Vec2::new(left_w, available.y), Layout::top_down(Align::Min), |ui| {
+                ui.horizontal(|ui| {
+                    for tab in [ViewTab::Route, ViewTab::Heatmap, ViewTab::Population, ViewTab::Annealing] {
+                        ui.selectable_value(view_tab, tab, tab.name());
+                    }
+                });
+                match *view_tab {
+                    ViewTab::Heatmap => tabs::population::show_heatmap(ui, run, graph_instance, heatmap_pheromone),
+                    ViewTab::Population => tabs::population::show_fitness_distribution(ui, run),
+                    ViewTab::Annealing => tabs::population::show_annealing(ui, run, *x_axis),
+                    _ => launch_local_search = tabs::route::show(ui, run, graph_instance, edit_move),
+                }
             });
+            if *view_tab == ViewTab::Route {
+                rects.push((PlotKind::Route, left.response.rect));
+            }
 
             // Vertical divider (draggable)
             let (divider_rect, _divider_resp) = ui.allocate_exact_size(Vec2::new(divider_w, available.y), egui::Sense::drag());
@@ -56,9 +105,10 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState, run_idx: usize) {
                 let bottom_h = right_av.y - h_div_h - top_h;
 
                 // Top (Metrics)
-                right_ui.allocate_ui_with_layout(Vec2::new(right_av.x, top_h), Layout::top_down(Align::Min), |ui| {
-                    tabs::metrics::show(ui, run, log_scale);
+                let metrics = right_ui.allocate_ui_with_layout(Vec2::new(right_av.x, top_h), Layout::top_down(Align::Min), |ui| {
+                    tabs::metrics::show(ui, run, log_scale, x_axis);
                 });
+                rects.push((PlotKind::Convergence, metrics.response.rect));
 
                 // Horizontal divider (draggable)
                 let (hdiv_rect, _hdiv_resp) = right_ui.allocate_exact_size(Vec2::new(right_av.x, h_div_h), egui::Sense::drag());
@@ -72,10 +122,16 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState, run_idx: usize) {
                 }
 
                 // Bottom (Gantt)
-                right_ui.allocate_ui_with_layout(Vec2::new(right_av.x, bottom_h), Layout::top_down(Align::Min), |ui| {
+                let gantt = right_ui.allocate_ui_with_layout(Vec2::new(right_av.x, bottom_h), Layout::top_down(Align::Min), |ui| {
                     tabs::gantt::show(ui, run);
                 });
+                rects.push((PlotKind::Gantt, gantt.response.rect));
             });
         });
+
+        state.plot_rects = rects.into_iter().collect();
+        if launch_local_search {
+            state.continue_from_best(run_idx, AlgoType::HillClimbing);
+        }
     }
 }
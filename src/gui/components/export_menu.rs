@@ -0,0 +1,64 @@
+use eframe::egui;
+use crate::gui::export::{self, PlotKind};
+use crate::gui::state::AppState;
+
+/// Menu d'export d'un run : données (.sol, CSV) et figures (PNG, SVG)
+pub fn run_export_menu(ui: &mut egui::Ui, state: &mut AppState, run_idx: usize) {
+    ui.menu_button("Export", |ui| {
+        let Some(run) = state.runs.get(run_idx) else { return };
+        let dir = state.export_dir.clone();
+
+        let mut exported = None;
+        if ui.button("Best tour (.sol)").clicked() {
+            let path = export::run_file(&dir, run, "best.sol");
+            exported = Some((export::export_best_tour(run, &path), path));
+        }
+        if ui.button("History (CSV)").clicked() {
+            let path = export::run_file(&dir, run, "history.csv");
+            exported = Some((export::export_history_csv(run, &path), path));
+        }
+        if ui.button("Schedule (CSV)").clicked() {
+            let path = export::run_file(&dir, run, "schedule.csv");
+            exported = Some((export::export_schedule_csv(run, &path), path));
+        }
+        ui.separator();
+
+        let mut screenshot = None;
+        for kind in [PlotKind::Route, PlotKind::Convergence, PlotKind::Gantt] {
+            ui.horizontal(|ui| {
+                ui.label(kind.name());
+                if ui.small_button("PNG").clicked() {
+                    screenshot = Some((kind, export::run_file(&dir, run, &format!("{}.png", kind.name()))));
+                }
+                if ui.small_button("SVG").clicked() {
+                    let path = export::run_file(&dir, run, &format!("{}.svg", kind.name()));
+                    exported = Some((export::export_svg(kind, run, &state.graph_instance, &path), path));
+                }
+            });
+        }
+
+        if let Some((result, path)) = exported {
+            state.report_export(&path, result);
+            ui.close_menu();
+        }
+        if let Some((kind, path)) = screenshot {
+            state.request_screenshot(ui.ctx(), kind, path);
+            ui.close_menu();
+        }
+    });
+}
+
+/// Export des valeurs finales de tous les runs
+pub fn statistics_export_buttons(ui: &mut egui::Ui, state: &mut AppState) {
+    let dir = std::path::PathBuf::from(&state.export_dir);
+    if ui.button("Export Statistics (CSV)").clicked() {
+        let path = dir.join("statistics.csv");
+        let result = export::export_statistics_csv(&state.runs, &path);
+        state.report_export(&path, result);
+    }
+    if ui.button("Export Statistics (JSON)").clicked() {
+        let path = dir.join("statistics.json");
+        let result = export::export_statistics_json(&state.runs, &path);
+        state.report_export(&path, result);
+    }
+}
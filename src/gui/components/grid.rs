@@ -1,7 +1,9 @@
 use eframe::egui;
 use egui_plot::{Line, Plot, PlotPoints};
-use crate::gui::state::AppState;
+use crate::gui::state::{AppState, RunState};
+use crate::gui::worker::RunStatus;
 use super::statistics::draw_aggregated_plot;
+use super::controls::{self, run_controls};
 
 pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
     use egui::{Vec2, Layout, Align};
@@ -10,11 +12,13 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
     let aggregation_height = 250.0;
     let grid_height = (available.y - aggregation_height).max(100.0);
 
+    let mut pending_action = None;
+
     // Grid Area
     ui.allocate_ui_with_layout(Vec2::new(available.x, grid_height), Layout::top_down(Align::Min), |ui| {
         egui::ScrollArea::vertical().show(ui, |ui| {
             let card_width = 300.0;
-            let card_height = 220.0;
+            let card_height = 245.0;
             let spacing = 10.0;
             let available_width = ui.available_width();
             let cols = ((available_width + spacing) / (card_width + spacing)).floor() as usize;
@@ -64,16 +68,21 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                             // Draw content inside card
                             ui.allocate_new_ui(egui::UiBuilder::new().max_rect(rect.shrink(10.0)).layout(egui::Layout::top_down(egui::Align::Min)), |ui| {
                                 ui.label(egui::RichText::new(&run.name).strong().size(16.0));
+                                if let Some(action) = run_controls(ui, run, state.step_count) {
+                                    pending_action = Some((real_i, action));
+                                }
                                 ui.label(format!("Iter: {}", run.history.last().map(|l| l.iteration).unwrap_or(0)));
                                 if let Some(last) = run.history.last() {
                                     ui.label(format!("Dist: {:.2}", last.current_dist));
                                     ui.label(format!("Viol: {:.2}", last.current_viol));
                                 }
-                                if run.is_running {
-                                    ui.colored_label(egui::Color32::GREEN, "Running");
-                                } else {
-                                    ui.colored_label(egui::Color32::RED, "Stopped");
-                                }
+                                match run.status {
+                                    RunStatus::Running => ui.colored_label(egui::Color32::GREEN, "Running"),
+                                    RunStatus::Paused => ui.colored_label(egui::Color32::YELLOW, "Paused"),
+                                    RunStatus::Finished => ui.colored_label(egui::Color32::LIGHT_BLUE, "Finished"),
+                                    RunStatus::Stopped => ui.colored_label(egui::Color32::RED, "Stopped"),
+                                    RunStatus::Loaded => ui.colored_label(egui::Color32::LIGHT_GRAY, "Loaded"),
+                                };
 
                                 // Sparklines
                                 let history_len = run.history.len();
@@ -121,9 +130,15 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
         });
     });
 
+    if let Some((run_idx, action)) = pending_action {
+        controls::apply(state, run_idx, action);
+    }
+
     ui.separator();
 
     // Aggregation Area (Bottom)
+    let all_runs: Vec<&RunState> = state.runs.iter().collect();
+    let x_axis = state.x_axis;
     ui.allocate_ui_with_layout(Vec2::new(available.x, aggregation_height), Layout::top_down(Align::Min), |ui| {
         ui.heading("Aggregated Results");
         ui.horizontal(|ui| {
@@ -135,7 +150,7 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                     .width(plot_width)
                     .height(aggregation_height - 30.0)
                     .show(ui, |plot_ui| {
-                        draw_aggregated_plot(state, plot_ui, |h| h.current_dist, egui::Color32::BLUE);
+                        draw_aggregated_plot(&all_runs, plot_ui, |h| h.current_dist, egui::Color32::BLUE, "Mean", x_axis);
                     });
             });
 
@@ -145,7 +160,7 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                     .width(plot_width)
                     .height(aggregation_height - 30.0)
                     .show(ui, |plot_ui| {
-                        draw_aggregated_plot(state, plot_ui, |h| h.current_viol, egui::Color32::RED);
+                        draw_aggregated_plot(&all_runs, plot_ui, |h| h.current_viol, egui::Color32::RED, "Mean", x_axis);
                     });
             });
         });
@@ -0,0 +1,139 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Orientation, Plot, PlotPoint, PlotPoints, Points, Text};
+
+use crate::shared::{GraphInstance, InstanceAnalysis};
+
+const WIDTH_BINS: usize = 20;
+
+// Du bleu (fenêtre ouvrant tôt) au rouge (fenêtre ouvrant tard), pour une position dans [0, 1]
+fn start_color(t: f32) -> egui::Color32 {
+    let t = t.clamp(0.0, 1.0);
+    egui::Color32::from_rgb((60.0 + 195.0 * t) as u8, 90, (255.0 - 195.0 * t) as u8)
+}
+
+/// Anomalies de faisabilité de l'instance, affichées avant tout lancement
+pub fn feasibility_flags(ui: &mut egui::Ui, analysis: &InstanceAnalysis) {
+    let issues = analysis.issues();
+    if issues.is_empty() {
+        ui.colored_label(egui::Color32::GREEN, "No obvious infeasibility detected");
+        return;
+    }
+    ui.colored_label(egui::Color32::RED, "This instance is infeasible:");
+    for issue in issues {
+        ui.colored_label(egui::Color32::RED, format!("• {}", issue));
+    }
+}
+
+/// Carte des clients colorée par ouverture de fenêtre, chronologie des fenêtres et statistiques
+pub fn show(ui: &mut egui::Ui, analysis: &InstanceAnalysis, graph_instance: &Option<GraphInstance>) {
+    let Some(graph_instance) = graph_instance else { return };
+    let nodes = &graph_instance.graph;
+    if nodes.is_empty() {
+        return;
+    }
+    let first_start = nodes.iter().map(|n| n.windows.first_start()).fold(f32::INFINITY, f32::min);
+    let last_start = nodes.iter().map(|n| n.windows.first_start()).fold(f32::NEG_INFINITY, f32::max);
+    let start_position = |wstart: f32| {
+        if last_start > first_start { (wstart - first_start) / (last_start - first_start) } else { 0.0 }
+    };
+
+    // Clients seulement, comme la moyenne
+    let customer_widths = analysis.widths.get(1..).unwrap_or_default();
+    let min_width = customer_widths.iter().copied().fold(f32::INFINITY, f32::min);
+    let max_width = customer_widths.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let pairs = analysis.size.saturating_sub(1) * analysis.size.saturating_sub(2) / 2;
+    egui::Grid::new("instance_stats").striped(true).show(ui, |ui| {
+        ui.label("Customers");
+        ui.label(format!("{} (+ depot)", analysis.size.saturating_sub(1)));
+        ui.end_row();
+        ui.label("Horizon");
+        ui.label(format!("{:.1}", analysis.horizon));
+        ui.end_row();
+        ui.label("Multi-window customers");
+        ui.label(nodes.iter().filter(|n| n.windows.slots().len() > 1).count().to_string());
+        ui.end_row();
+        ui.label("Window width");
+        ui.label(format!("min {:.1} | mean {:.1} | max {:.1}", min_width, analysis.mean_width, max_width));
+        ui.end_row();
+        ui.label("Tightness");
+        ui.label(format!("{:.3} (mean width / horizon)", analysis.tightness));
+        ui.end_row();
+        ui.label("Forced precedences");
+        let share = if pairs > 0 { 100.0 * analysis.forced_precedences.len() as f32 / pairs as f32 } else { 0.0 };
+        ui.label(format!("{} of {} pairs ({:.1}%)", analysis.forced_precedences.len(), pairs, share));
+        ui.end_row();
+        ui.label("Unreachable in time");
+        ui.label(analysis.unreachable.len().to_string());
+        ui.end_row();
+    });
+
+    ui.label("Customers colored by window start (blue: early, red: late)");
+    Plot::new("Instance Map")
+        .data_aspect(1.0)
+        .height(300.0)
+        .show(ui, |plot_ui| {
+            for (i, node) in nodes.iter().enumerate() {
+                let (radius, color) = if i == 0 {
+                    (8.0, egui::Color32::WHITE)
+                } else if analysis.unreachable.contains(&i) {
+                    (7.0, egui::Color32::YELLOW)
+                } else {
+                    (5.0, start_color(start_position(node.windows.first_start())))
+                };
+                let point = Points::new(PlotPoints::new(vec![[node.x as f64, node.y as f64]])).radius(radius).color(color);
+                plot_ui.points(point);
+            }
+            plot_ui.text(Text::new(PlotPoint::new(nodes[0].x as f64, nodes[0].y as f64 + 2.0), "Depot"));
+        });
+
+    // Fenêtres triées par première ouverture (une barre par fenêtre), avec la date d'arrivée au plus tôt depuis le dépôt
+    ui.label("Time windows (sorted by first start, dots: earliest arrival from the depot)");
+    let mut order: Vec<usize> = (1..nodes.len()).collect();
+    order.sort_by(|&a, &b| nodes[a].windows.first_start().total_cmp(&nodes[b].windows.first_start()));
+    Plot::new("Instance Timeline")
+        .height(300.0)
+        .x_axis_label("Time")
+        .y_axis_label("Customers by window start")
+        .show(ui, |plot_ui| {
+            let bars: Vec<Bar> = order.iter().enumerate()
+                .flat_map(|(rank, &i)| {
+                    let color = start_color(start_position(nodes[i].windows.first_start()));
+                    nodes[i].windows.slots().iter().map(move |slot| {
+                        let mut bar = Bar::new(rank as f64, (slot.wend - slot.wstart) as f64)
+                            .base_offset(slot.wstart as f64)
+                            .fill(color)
+                            .name(format!("Customer {}", i));
+                        bar.orientation = Orientation::Horizontal;
+                        bar
+                    })
+                })
+                .collect();
+            plot_ui.bar_chart(BarChart::new(bars).name("Windows"));
+            let arrivals: Vec<[f64; 2]> = order.iter().enumerate()
+                .map(|(rank, &i)| [analysis.earliest_arrival[i] as f64, rank as f64])
+                .collect();
+            plot_ui.points(Points::new(PlotPoints::new(arrivals)).radius(2.5).color(egui::Color32::WHITE));
+        });
+
+    ui.label("Window width distribution");
+    let range = max_width - min_width;
+    let bars: Vec<Bar> = if range <= 1e-6 {
+        vec![Bar::new(min_width as f64, customer_widths.len() as f64).width(1.0)]
+    } else {
+        let step = range / WIDTH_BINS as f32;
+        let mut counts = [0usize; WIDTH_BINS];
+        for width in customer_widths {
+            counts[(((width - min_width) / step) as usize).min(WIDTH_BINS - 1)] += 1;
+        }
+        counts.iter().enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(i, count)| Bar::new((min_width + (i as f32 + 0.5) * step) as f64, *count as f64).width(step as f64))
+            .collect()
+    };
+    Plot::new("Window Widths")
+        .height(200.0)
+        .x_axis_label("Width")
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(BarChart::new(bars).color(egui::Color32::LIGHT_BLUE).name("Customers"));
+        });
+}
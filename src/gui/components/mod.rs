@@ -2,3 +2,6 @@ pub mod grid;
 pub mod statistics;
 pub mod dashboard;
 pub mod welcome;
+pub mod controls;
+pub mod export_menu;
+pub mod instance_view;
@@ -1,23 +1,84 @@
 use eframe::egui;
-use egui_plot::{Line, Plot, PlotPoints, Polygon, Bar, BarChart};
-use crate::gui::state::AppState;
+use egui_plot::{Legend, Line, Plot, PlotPoints, Polygon, Bar, BarChart};
+use crate::gui::state::{AppState, LogEntry, RunState, XAxis};
+use crate::gui::tabs::metrics::x_axis_selector;
+use crate::utils::stats::{mann_whitney_u, wilcoxon_signed_rank, TestResult};
+use super::export_menu::statistics_export_buttons;
 
-pub fn show(ui: &mut egui::Ui, state: &AppState) {
+// Couleurs des groupes de configuration lorsqu'il y en a plusieurs
+const GROUP_COLORS: [egui::Color32; 8] = [
+    egui::Color32::from_rgb(31, 119, 180),
+    egui::Color32::from_rgb(255, 127, 14),
+    egui::Color32::from_rgb(44, 160, 44),
+    egui::Color32::from_rgb(214, 39, 40),
+    egui::Color32::from_rgb(148, 103, 189),
+    egui::Color32::from_rgb(140, 86, 75),
+    egui::Color32::from_rgb(227, 119, 194),
+    egui::Color32::from_rgb(188, 189, 34),
+];
+
+// Runs d'un groupe de configuration
+struct Group<'a> {
+    name: String,
+    runs: Vec<&'a RunState>,
+    color: egui::Color32,
+}
+
+fn groups(state: &AppState) -> Vec<Group<'_>> {
+    state.run_groups().into_iter().enumerate().map(|(i, name)| Group {
+        runs: state.runs.iter().filter(|r| r.spec.group == name).collect(),
+        name,
+        color: GROUP_COLORS[i % GROUP_COLORS.len()],
+    }).collect()
+}
+
+// Avec un seul groupe, chaque grandeur garde sa couleur ; sinon chaque groupe a la sienne
+fn series_color(groups: &[Group], group: &Group, default: egui::Color32) -> egui::Color32 {
+    if groups.len() > 1 { group.color } else { default }
+}
+
+pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
     use egui::{Layout, Align, Vec2};
     let available = ui.available_size();
-    
+
+    ui.horizontal(|ui| {
+        ui.heading("Aggregated Results");
+        ui.separator();
+        statistics_export_buttons(ui, state);
+    });
+    ui.horizontal(|ui| {
+        x_axis_selector(ui, &mut state.x_axis);
+        ui.separator();
+        ui.add(egui::Slider::new(&mut state.attainment_gap, 0.0..=50.0).text("Attainment Target Gap (%)"));
+    });
+    let x_axis = state.x_axis;
+    let gap = state.attainment_gap;
+
+    let groups = groups(state);
+    // Métriques propres à chaque algorithme : un groupe n'est tracé que pour les siennes
+    let mut metric_names: Vec<String> = Vec::new();
+    for group in &groups {
+        for name in group.runs.first().map(|r| r.metric_names.as_slice()).unwrap_or_default() {
+            if !metric_names.contains(name) {
+                metric_names.push(name.clone());
+            }
+        }
+    }
+    let has_metric = |group: &Group, name: &str| group.runs.first().is_some_and(|r| r.metric_names.iter().any(|m| m == name));
+
     egui::ScrollArea::vertical().show(ui, |ui| {
         ui.allocate_ui_with_layout(Vec2::new(available.x, available.y), Layout::top_down(Align::Min), |ui| {
-            ui.heading("Aggregated Results");
-            
             let plot_height = 250.0;
 
             // Distance Plot
             ui.label("Distance (Mean ± Std Dev)");
             Plot::new("Aggregated_Dist")
                 .height(plot_height)
+                .legend(Legend::default())
                 .show(ui, |plot_ui| {
-                    draw_aggregated_plot(state, plot_ui, |h| h.current_dist, egui::Color32::BLUE);
+                    for group in &groups {
+                        draw_aggregated_plot(&group.runs, plot_ui, |h| h.current_dist, series_color(&groups, group, egui::Color32::BLUE), &group.name, x_axis);
+                    }
                 });
 
             ui.separator();
@@ -26,135 +87,328 @@ pub fn show(ui: &mut egui::Ui, state: &AppState) {
             ui.label("Violation (Mean ± Std Dev)");
             Plot::new("Aggregated_Viol")
                 .height(plot_height)
+                .legend(Legend::default())
                 .show(ui, |plot_ui| {
-                    draw_aggregated_plot(state, plot_ui, |h| h.current_viol, egui::Color32::RED);
+                    for group in &groups {
+                        draw_aggregated_plot(&group.runs, plot_ui, |h| h.current_viol, series_color(&groups, group, egui::Color32::RED), &group.name, x_axis);
+                    }
                 });
 
             // Dynamic Metric Plots
-            if let Some(first_run) = state.runs.first() {
-                for metric_name in &first_run.metric_names {
-                    ui.separator();
-                    ui.label(format!("{} (Mean ± Std Dev)", metric_name));
-                    let name_clone = metric_name.clone();
-                    Plot::new(format!("Aggregated_{}", metric_name))
-                        .height(plot_height)
-                        .show(ui, |plot_ui| {
-                            draw_aggregated_plot(state, plot_ui, |h| *h.metrics.get(&name_clone).unwrap_or(&0.0), egui::Color32::GOLD);
-                        });
-                }
+            for metric_name in &metric_names {
+                ui.separator();
+                ui.label(format!("{} (Mean ± Std Dev)", metric_name));
+                Plot::new(format!("Aggregated_{}", metric_name))
+                    .height(plot_height)
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        for group in groups.iter().filter(|g| has_metric(g, metric_name)) {
+                            draw_aggregated_plot(&group.runs, plot_ui, |h| *h.metrics.get(metric_name).unwrap_or(&0.0), series_color(&groups, group, egui::Color32::GOLD), &group.name, x_axis);
+                        }
+                    });
             }
 
             ui.add_space(20.0);
             ui.heading("Final Value Distributions");
-            
+
             // Histograms
             ui.horizontal(|ui| {
                 let width = (ui.available_width() / 2.0) - 10.0;
                 ui.vertical(|ui| {
-                    draw_histogram(ui, state, "Final Distance Distribution", |h| h.current_dist, egui::Color32::BLUE, width);
+                    draw_histogram(ui, &groups, "Final Distance Distribution", |h| h.current_dist, egui::Color32::BLUE, width);
                 });
                 ui.vertical(|ui| {
-                    draw_histogram(ui, state, "Final Violation Distribution", |h| h.current_viol, egui::Color32::RED, width);
+                    draw_histogram(ui, &groups, "Final Violation Distribution", |h| h.current_viol, egui::Color32::RED, width);
                 });
             });
 
-            if let Some(first_run) = state.runs.first() {
-                let mut metrics = first_run.metric_names.iter();
-                while let Some(m1) = metrics.next() {
-                    ui.horizontal(|ui| {
-                        let width = (ui.available_width() / 2.0) - 10.0;
+            let mut metrics = metric_names.iter();
+            while let Some(m1) = metrics.next() {
+                ui.horizontal(|ui| {
+                    let width = (ui.available_width() / 2.0) - 10.0;
+                    ui.vertical(|ui| {
+                        draw_histogram(ui, &groups, &format!("Final {} Distribution", m1), |h| *h.metrics.get(m1).unwrap_or(&0.0), egui::Color32::GOLD, width);
+                    });
+
+                    if let Some(m2) = metrics.next() {
                         ui.vertical(|ui| {
-                            let name_clone = m1.clone();
-                            draw_histogram(ui, state, &format!("Final {} Distribution", m1), |h| *h.metrics.get(&name_clone).unwrap_or(&0.0), egui::Color32::GOLD, width);
+                            draw_histogram(ui, &groups, &format!("Final {} Distribution", m2), |h| *h.metrics.get(m2).unwrap_or(&0.0), egui::Color32::GOLD, width);
                         });
-                        
-                        if let Some(m2) = metrics.next() {
-                            ui.vertical(|ui| {
-                                let name_clone = m2.clone();
-                                draw_histogram(ui, state, &format!("Final {} Distribution", m2), |h| *h.metrics.get(&name_clone).unwrap_or(&0.0), egui::Color32::GOLD, width);
-                            });
+                    }
+                });
+            }
+
+            ui.add_space(20.0);
+            ui.heading("Anytime Profiles");
+            draw_attainment(ui, &groups, x_axis, gap);
+            draw_performance_profile(ui, &groups);
+
+            if groups.len() > 1 {
+                ui.add_space(20.0);
+                draw_pairwise_tests(ui, &groups);
+            }
+        });
+    });
+}
+
+// Distance d'une entrée de log si la tournée est réalisable : seules celles-ci servent de référence aux profils
+fn feasible_distance(entry: &LogEntry) -> Option<f32> {
+    (entry.current_viol <= 0.0).then_some(entry.current_dist)
+}
+
+// Courbe en escalier de la proportion de valeurs de `sorted` inférieures ou égales à x, sur [start, end]
+fn step_curve(sorted: &[f64], total: usize, start: f64, end: f64) -> Vec<[f64; 2]> {
+    let mut points = vec![[start, 0.0]];
+    for (k, &x) in sorted.iter().enumerate() {
+        points.push([x, k as f64 / total as f64]);
+        points.push([x, (k + 1) as f64 / total as f64]);
+    }
+    points.push([end, sorted.len() as f64 / total as f64]);
+    points
+}
+
+/// Courbes d'atteinte empiriques : proportion des runs de chaque groupe ayant trouvé une tournée
+/// réalisable à moins de `gap` % de la meilleure distance réalisable, en fonction de `x_axis`
+fn draw_attainment(ui: &mut egui::Ui, groups: &[Group], x_axis: XAxis, gap: f32) {
+    let runs = || groups.iter().flat_map(|g| g.runs.iter());
+    let best = runs()
+        .filter_map(|r| r.history.last().and_then(feasible_distance))
+        .min_by(|a, b| a.total_cmp(b));
+    let Some(best) = best else {
+        ui.label("Attainment curves need at least one feasible run.");
+        return;
+    };
+    let target = best * (1.0 + gap / 100.0);
+    let max_x = runs().filter_map(|r| r.history.last()).map(|e| x_axis.value(e)).fold(0.0, f64::max);
+
+    ui.label(format!("Attainment: fraction of runs with a feasible tour of distance ≤ {:.1} (best {:.1} + {}%)", target, best, gap));
+    Plot::new("Attainment")
+        .height(250.0)
+        .legend(Legend::default())
+        .x_axis_label(x_axis.name())
+        .include_y(0.0)
+        .include_y(1.0)
+        .show(ui, |plot_ui| {
+            for group in groups {
+                let mut hits: Vec<f64> = group.runs.iter()
+                    .filter_map(|r| r.history.iter().find(|e| feasible_distance(e).is_some_and(|d| d <= target)))
+                    .map(|e| x_axis.value(e))
+                    .collect();
+                hits.sort_by(|a, b| a.total_cmp(b));
+                let points = step_curve(&hits, group.runs.len(), 0.0, max_x);
+                plot_ui.line(Line::new(PlotPoints::new(points)).color(group.color).name(&group.name));
+            }
+        });
+}
+
+/// Profils de performance (Dolan-Moré) : pour chaque graine commune aux groupes, rapport entre la
+/// distance finale d'un groupe et la meilleure obtenue sur cette graine (infini si non réalisable)
+fn draw_performance_profile(ui: &mut egui::Ui, groups: &[Group]) {
+    if groups.len() < 2 {
+        return;
+    }
+    let final_distance = |group: &Group, seed: u64| {
+        group.runs.iter()
+            .find(|r| r.spec.seed == seed)
+            .and_then(|r| r.history.last().and_then(feasible_distance))
+            .unwrap_or(f32::INFINITY)
+    };
+
+    let mut ratios: Vec<Vec<f64>> = vec![Vec::new(); groups.len()];
+    let mut problems = 0;
+    for run in &groups[0].runs {
+        let seed = run.spec.seed;
+        if !groups.iter().all(|g| g.runs.iter().any(|r| r.spec.seed == seed)) {
+            continue;
+        }
+        let costs: Vec<f32> = groups.iter().map(|g| final_distance(g, seed)).collect();
+        let best = costs.iter().copied().fold(f32::INFINITY, f32::min);
+        if !best.is_finite() || best <= 0.0 {
+            continue;
+        }
+        problems += 1;
+        for (g, cost) in costs.iter().enumerate() {
+            if cost.is_finite() {
+                ratios[g].push((cost / best) as f64);
+            }
+        }
+    }
+    if problems == 0 {
+        ui.label("Performance profiles need runs sharing a seed across groups, with at least one feasible result.");
+        return;
+    }
+
+    let tau_max = ratios.iter().flatten().copied().fold(1.01, f64::max) * 1.05;
+    ui.label(format!("Performance profile over {} seeds: fraction of seeds where a group is within a factor τ of the best group", problems));
+    Plot::new("Performance_Profile")
+        .height(250.0)
+        .legend(Legend::default())
+        .x_axis_label("τ")
+        .include_y(0.0)
+        .include_y(1.0)
+        .show(ui, |plot_ui| {
+            for (group, group_ratios) in groups.iter().zip(&mut ratios) {
+                group_ratios.sort_by(|a, b| a.total_cmp(b));
+                let points = step_curve(group_ratios, problems, 1.0, tau_max);
+                plot_ui.line(Line::new(PlotPoints::new(points)).color(group.color).name(&group.name));
+            }
+        });
+}
+
+// Valeurs finales des runs d'un groupe, avec la graine qui sert à apparier les runs entre groupes
+fn final_values(group: &Group, value_extractor: &impl Fn(&LogEntry) -> f32) -> Vec<(u64, f32)> {
+    group.runs.iter()
+        .filter_map(|r| r.history.last().map(|l| (r.spec.seed, value_extractor(l))))
+        .collect()
+}
+
+// Grandeur comparée par les tests : nom et valeur lue dans le log
+type Quantity = (&'static str, fn(&LogEntry) -> f32);
+
+fn draw_pairwise_tests(ui: &mut egui::Ui, groups: &[Group]) {
+    ui.heading("Pairwise Tests on Final Values");
+    ui.label("Two-sided p-values. Mann-Whitney U compares the groups as independent samples; Wilcoxon signed-rank pairs runs sharing the same seed.");
+
+    let format_test = |test: Option<TestResult>| match test {
+        Some(test) => (format!("{:.1}", test.statistic), format!("{:.4}", test.p_value), test.p_value < 0.05),
+        None => ("-".to_string(), "-".to_string(), false),
+    };
+    let quantities: [Quantity; 2] = [("Distance", |h| h.current_dist), ("Violation", |h| h.current_viol)];
+
+    egui::Grid::new("pairwise_tests").striped(true).show(ui, |ui| {
+        for header in ["Group A", "Group B", "Value", "U", "p (Mann-Whitney)", "W", "p (Wilcoxon)"] {
+            ui.label(egui::RichText::new(header).strong());
+        }
+        ui.end_row();
+
+        for (i, a) in groups.iter().enumerate() {
+            for b in &groups[i + 1..] {
+                for (quantity, extractor) in quantities {
+                    let values_a = final_values(a, &extractor);
+                    let values_b = final_values(b, &extractor);
+                    let sample_a: Vec<f32> = values_a.iter().map(|(_, v)| *v).collect();
+                    let sample_b: Vec<f32> = values_b.iter().map(|(_, v)| *v).collect();
+                    let (paired_a, paired_b): (Vec<f32>, Vec<f32>) = values_a.iter()
+                        .filter_map(|(seed, v)| values_b.iter().find(|(s, _)| s == seed).map(|(_, w)| (*v, *w)))
+                        .unzip();
+
+                    ui.colored_label(a.color, &a.name);
+                    ui.colored_label(b.color, &b.name);
+                    ui.label(quantity);
+                    for (statistic, p_value, significant) in [
+                        format_test(mann_whitney_u(&sample_a, &sample_b)),
+                        format_test(wilcoxon_signed_rank(&paired_a, &paired_b)),
+                    ] {
+                        ui.label(statistic);
+                        if significant {
+                            ui.colored_label(egui::Color32::GREEN, p_value);
+                        } else {
+                            ui.label(p_value);
                         }
-                    });
+                    }
+                    ui.end_row();
                 }
             }
-        });
+        }
     });
 }
 
-fn draw_histogram(ui: &mut egui::Ui, state: &AppState, title: &str, value_extractor: impl Fn(&crate::gui::state::LogEntry) -> f32, color: egui::Color32, width: f32) {
-    let mut values: Vec<f32> = state.runs.iter()
-        .filter_map(|r| r.history.last().map(|l| value_extractor(l)))
+fn draw_histogram(ui: &mut egui::Ui, groups: &[Group], title: &str, value_extractor: impl Fn(&LogEntry) -> f32, color: egui::Color32, width: f32) {
+    let group_values: Vec<Vec<f32>> = groups.iter()
+        .map(|g| g.runs.iter().filter_map(|r| r.history.last().map(&value_extractor)).collect())
         .collect();
-    
+    let mut values: Vec<f32> = group_values.iter().flatten().copied().collect();
+
     if values.is_empty() { return; }
-    
+
     values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-    
+
     let min = *values.first().unwrap();
     let max = *values.last().unwrap();
     let range = max - min;
     let bins = 15;
     let step = if range > 1e-6 { range / bins as f32 } else { 1.0 };
-    
-    let mut bars = Vec::new();
-    // If range is effectively zero, just show one bar
-    if range <= 1e-6 {
-        bars.push(Bar::new(min as f64, values.len() as f64).width(0.5).fill(color));
-    } else {
-        for i in 0..bins {
-            let start = min + i as f32 * step;
-            let end = start + step;
-            // Inclusive of end for the last bin
-            let count = values.iter().filter(|&&v| v >= start && (v < end || (i == bins - 1 && v <= end))).count();
-            
-            if count > 0 {
-                bars.push(Bar::new((start + step/2.0) as f64, count as f64).width(step as f64 * 0.9).fill(color));
+    // Les barres des groupes sont côte à côte dans chaque intervalle
+    let bar_width = step * 0.9 / groups.len() as f32;
+
+    let mut charts = Vec::new();
+    for (g, (group, values)) in groups.iter().zip(&group_values).enumerate() {
+        let color = series_color(groups, group, color);
+        let offset = (g as f32 - (groups.len() as f32 - 1.0) / 2.0) * bar_width;
+        let mut bars = Vec::new();
+        // If range is effectively zero, just show one bar
+        if range <= 1e-6 {
+            if !values.is_empty() {
+                bars.push(Bar::new((min + offset) as f64, values.len() as f64).width(bar_width.min(0.5) as f64).fill(color));
+            }
+        } else {
+            for i in 0..bins {
+                let start = min + i as f32 * step;
+                let end = start + step;
+                // Inclusive of end for the last bin
+                let count = values.iter().filter(|&&v| v >= start && (v < end || (i == bins - 1 && v <= end))).count();
+
+                if count > 0 {
+                    bars.push(Bar::new((start + step/2.0 + offset) as f64, count as f64).width(bar_width as f64).fill(color));
+                }
             }
         }
+        charts.push(BarChart::new(bars).color(color).name(&group.name));
     }
-    
+
     ui.label(title);
     Plot::new(format!("Hist_{}", title))
         .width(width)
         .height(200.0)
+        .legend(Legend::default())
         .show(ui, |plot_ui| {
-            plot_ui.bar_chart(BarChart::new(bars).color(color));
+            for chart in charts {
+                plot_ui.bar_chart(chart);
+            }
         });
 }
 
-pub fn draw_aggregated_plot(state: &AppState, plot_ui: &mut egui_plot::PlotUi, value_extractor: impl Fn(&crate::gui::state::LogEntry) -> f32, color: egui::Color32) {
-    let max_iter = state.runs.iter().map(|r| r.history.len()).max().unwrap_or(0);
-    if max_iter > 0 {
+/// Moyenne des runs (et intervalle de confiance à 95 %) en fonction de `x_axis`. Chaque run est
+/// échantillonné sur une grille commune : sa valeur en x est celle de sa dernière entrée de log avant x.
+pub fn draw_aggregated_plot(runs: &[&RunState], plot_ui: &mut egui_plot::PlotUi, value_extractor: impl Fn(&LogEntry) -> f32, color: egui::Color32, name: &str, x_axis: XAxis) {
+    let max_x = runs.iter()
+        .filter_map(|r| r.history.last())
+        .map(|e| x_axis.value(e))
+        .fold(0.0, f64::max);
+    if runs.iter().any(|r| !r.history.is_empty()) {
         let mut mean_points = Vec::new();
         let mut upper_points = Vec::new();
         let mut lower_points = Vec::new();
-        
-        // Sample every N iterations to save perf
-        let step = (max_iter / 200).max(1);
-        
+
+        // Sample 200 points to save perf
+        let samples = 200;
+
         // Smoothing window size
         let window_size = 5;
         let mut history_buffer: Vec<f32> = Vec::new();
 
-        for i in (0..max_iter).step_by(step) {
+        for k in 0..=samples {
+            let x = max_x * k as f64 / samples as f64;
             let mut values: Vec<f32> = Vec::new();
-            for run in &state.runs {
-                if i < run.history.len() {
-                    values.push(value_extractor(&run.history[i]));
+            for run in runs {
+                // Les entrées sont triées selon chacune des abscisses
+                let logged = run.history.partition_point(|e| x_axis.value(e) <= x);
+                if logged > 0 {
+                    values.push(value_extractor(&run.history[logged - 1]));
                 }
             }
-            
+
             if !values.is_empty() {
                 let sum: f32 = values.iter().sum();
                 let count = values.len() as f32;
                 let mean = sum / count;
-                
+
                 let variance: f32 = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / count;
                 let std_dev = variance.sqrt();
-                
+
                 // 95% Confidence Interval of the Mean: 1.96 * (std_dev / sqrt(N))
                 let ci95 = 1.96 * (std_dev / count.sqrt());
-                
+
                 // Apply simple moving average smoothing
                 history_buffer.push(mean);
                 if history_buffer.len() > window_size {
@@ -162,27 +416,27 @@ pub fn draw_aggregated_plot(state: &AppState, plot_ui: &mut egui_plot::PlotUi, v
                 }
                 let smoothed_mean = history_buffer.iter().sum::<f32>() / history_buffer.len() as f32;
 
-                mean_points.push([i as f64, smoothed_mean as f64]);
-                upper_points.push([i as f64, (smoothed_mean + ci95) as f64]);
-                lower_points.push([i as f64, (smoothed_mean - ci95) as f64]);
+                mean_points.push([x, smoothed_mean as f64]);
+                upper_points.push([x, (smoothed_mean + ci95) as f64]);
+                lower_points.push([x, (smoothed_mean - ci95) as f64]);
             }
         }
-        
+
         if !mean_points.is_empty() {
             let fill_color = egui::Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), 50);
-            
+
             // Draw CI as strips (quads) to avoid triangulation artifacts with complex/degenerate polygons
             for i in 0..mean_points.len().saturating_sub(1) {
                 let p1_u = upper_points[i];
                 let p2_u = upper_points[i+1];
                 let p2_l = lower_points[i+1];
                 let p1_l = lower_points[i];
-                
+
                 let quad = vec![p1_u, p2_u, p2_l, p1_l];
                 plot_ui.polygon(Polygon::new(PlotPoints::new(quad)).fill_color(fill_color));
             }
 
-            plot_ui.line(Line::new(PlotPoints::new(mean_points)).color(color).name("Mean"));
+            plot_ui.line(Line::new(PlotPoints::new(mean_points)).color(color).name(name));
         }
     }
 }
@@ -1,9 +1,13 @@
 use eframe::egui;
 
+use crate::algorithms::{ACOVariant, MigrationPolicy, MigrationTopology, ReplacementType};
 use crate::factories::AlgoType;
 use crate::gui::state::{AppState, AppPhase};
-use crate::neighborhood::NeighborhoodType;
-use crate::eval::EvaluationType;
+use crate::gui::components;
+use crate::neighborhood::{NeighborhoodType, LocalSearchType};
+use crate::eval::{EvaluationType, Objective};
+use crate::io::io_instance::{InstanceFormat, LoadOptions, Rounding};
+use crate::utils::history::{HistoryLimit, RecordPolicy};
 
 pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
         //ui.set_max_width(600.0);
@@ -23,8 +27,23 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                           state.load_instance();
                       }
                   });
+                  egui::CollapsingHeader::new("Loading Options").show(ui, |ui| {
+                      load_options(ui, &mut state.load_options);
+                  });
+                  if let Some(error) = &state.instance_error {
+                      ui.colored_label(egui::Color32::RED, error);
+                  }
+                  if components::controls::solution_loader(ui, state) {
+                      state.phase = AppPhase::Running;
+                  }
                   if let Some(inst) = &state.instance {
                       ui.label(format!("Loaded: {} nodes", inst.size()));
+                      if let Some(analysis) = &state.instance_analysis {
+                          components::instance_view::feasibility_flags(ui, analysis);
+                          egui::CollapsingHeader::new("Instance Explorer").show(ui, |ui| {
+                              components::instance_view::show(ui, analysis, &state.graph_instance);
+                          });
+                      }
                   } else {
                       ui.colored_label(egui::Color32::RED, "No instance loaded");
                   }
@@ -38,6 +57,7 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                       ui.label("Algorithm:");
                       ui.radio_value(&mut state.algo_type, AlgoType::SimulatedAnnealing, "Simulated Annealing");
                       ui.radio_value(&mut state.algo_type, AlgoType::GeneticAlgorithm, "Genetic Algorithm");
+                      ui.radio_value(&mut state.algo_type, AlgoType::IslandGeneticAlgorithm, "Island GA");
                       ui.radio_value(&mut state.algo_type, AlgoType::HillClimbing, "Hill Climbing");
                       ui.radio_value(&mut state.algo_type, AlgoType::AntColonyOptimization, "Ant Colony Optimization");
                   });
@@ -46,6 +66,7 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                       ui.label("Neighborhood:");
                       ui.radio_value(&mut state.algo_config.neighborhood, NeighborhoodType::Swap, "Swap");
                       ui.radio_value(&mut state.algo_config.neighborhood, NeighborhoodType::TwoOpt, "2-Opt");
+                      ui.radio_value(&mut state.algo_config.neighborhood, NeighborhoodType::OrOpt, "Or-Opt");
                   });
   
                   ui.horizontal(|ui| {
@@ -73,13 +94,17 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                   }
 
                   // Genetic Algorithm parameters
-                  if state.algo_type == AlgoType::GeneticAlgorithm {
+                  if matches!(state.algo_type, AlgoType::GeneticAlgorithm | AlgoType::IslandGeneticAlgorithm) {
                       ui.label(egui::RichText::new("Genetic Algorithm").strong());
                       ui.add(egui::Slider::new(&mut state.algo_config.ga_crossover_rate, 0.0..=1.0).text("Crossover Rate"));
                       ui.horizontal(|ui| {
                           ui.label("Crossover Type:");
                           ui.radio_value(&mut state.algo_config.ga_crossover_type, crate::algorithms::CrossoverType::PMX, "PMX");
                           ui.radio_value(&mut state.algo_config.ga_crossover_type, crate::algorithms::CrossoverType::OX, "OX");
+                          ui.radio_value(&mut state.algo_config.ga_crossover_type, crate::algorithms::CrossoverType::ERX, "ERX");
+                          ui.radio_value(&mut state.algo_config.ga_crossover_type, crate::algorithms::CrossoverType::EAX, "EAX");
+                          ui.radio_value(&mut state.algo_config.ga_crossover_type, crate::algorithms::CrossoverType::CX, "CX");
+                          ui.radio_value(&mut state.algo_config.ga_crossover_type, crate::algorithms::CrossoverType::TWMerge, "TW Merge");
                       });
                       ui.add(egui::Slider::new(&mut state.algo_config.ga_elitism_rate, 0.0..=1.0).text("Elitism Rate"));
                       ui.add(egui::Slider::new(&mut state.algo_config.ga_competition_participation_rate, 0.0..=1.0).text("Competition Participation"));
@@ -88,7 +113,53 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                           ui.radio_value(&mut state.algo_config.ga_competition_type, crate::algorithms::CompetitionType::Tournament, "Tournament");
                           ui.radio_value(&mut state.algo_config.ga_competition_type, crate::algorithms::CompetitionType::Roulette, "Roulette");
                       });
-                      ui.add(egui::Slider::new(&mut state.algo_config.population_size, 10..=500).text("Population Size"));
+                      let population_label = if state.algo_type == AlgoType::IslandGeneticAlgorithm { "Population Size (per island)" } else { "Population Size" };
+                      ui.add(egui::Slider::new(&mut state.algo_config.population_size, 10..=500).text(population_label));
+                      ui.label("Mutation Rates:");
+                      ui.add(egui::Slider::new(&mut state.algo_config.ga_inversion_rate, 0.0..=1.0).text("Inversion"));
+                      ui.add(egui::Slider::new(&mut state.algo_config.ga_scramble_rate, 0.0..=1.0).text("Scramble"));
+                      ui.add(egui::Slider::new(&mut state.algo_config.ga_insertion_rate, 0.0..=1.0).text("Insertion"));
+                      ui.add(egui::Slider::new(&mut state.algo_config.ga_displacement_rate, 0.0..=1.0).text("Displacement"));
+                      ui.horizontal(|ui| {
+                          ui.label("Memetic Local Search:");
+                          ui.radio_value(&mut state.algo_config.local_search_type, LocalSearchType::Swap, "Swap");
+                          ui.radio_value(&mut state.algo_config.local_search_type, LocalSearchType::TwoOpt, "2-Opt");
+                          ui.radio_value(&mut state.algo_config.local_search_type, LocalSearchType::OrOpt, "Or-Opt");
+                          ui.radio_value(&mut state.algo_config.local_search_type, LocalSearchType::HillClimbing, "Hill Climbing");
+                          ui.radio_value(&mut state.algo_config.local_search_type, LocalSearchType::SimulatedAnnealing, "Simulated Annealing");
+                      });
+                      ui.add(egui::Slider::new(&mut state.algo_config.ga_local_search_rate, 0.0..=1.0).text("Local Search Rate (0 = off)"));
+                      ui.label("Diversity:");
+                      ui.horizontal(|ui| {
+                          ui.label("Replacement:");
+                          ui.radio_value(&mut state.algo_config.ga_replacement_type, ReplacementType::Generational, "Generational");
+                          ui.radio_value(&mut state.algo_config.ga_replacement_type, ReplacementType::Crowding, "Crowding");
+                          ui.radio_value(&mut state.algo_config.ga_replacement_type, ReplacementType::FitnessSharing, "Fitness Sharing");
+                      });
+                      if state.algo_config.ga_replacement_type == ReplacementType::FitnessSharing {
+                          ui.add(egui::Slider::new(&mut state.algo_config.ga_sharing_radius, 0.0..=1.0).text("Sharing Radius"));
+                      }
+                      ui.checkbox(&mut state.algo_config.ga_duplicate_elimination, "Eliminate Duplicates");
+                      ui.add(egui::Slider::new(&mut state.algo_config.ga_restart_threshold, 0.0..=1.0).text("Restart Below Diversity (0 = off)"));
+                  }
+
+                  // Island model parameters
+                  if state.algo_type == AlgoType::IslandGeneticAlgorithm {
+                      ui.label(egui::RichText::new("Island Model").strong());
+                      ui.add(egui::Slider::new(&mut state.algo_config.island_count, 2..=32).text("Islands"));
+                      ui.add(egui::Slider::new(&mut state.algo_config.migration_interval, 0..=500).text("Migration Interval (0 = off)"));
+                      ui.add(egui::Slider::new(&mut state.algo_config.migration_count, 1..=50).text("Migrants per Island"));
+                      ui.horizontal(|ui| {
+                          ui.label("Topology:");
+                          ui.radio_value(&mut state.algo_config.migration_topology, MigrationTopology::Ring, "Ring");
+                          ui.radio_value(&mut state.algo_config.migration_topology, MigrationTopology::FullyConnected, "Fully Connected");
+                          ui.radio_value(&mut state.algo_config.migration_topology, MigrationTopology::Random, "Random");
+                      });
+                      ui.horizontal(|ui| {
+                          ui.label("Policy:");
+                          ui.radio_value(&mut state.algo_config.migration_policy, MigrationPolicy::BestReplaceWorst, "Best Replace Worst");
+                          ui.radio_value(&mut state.algo_config.migration_policy, MigrationPolicy::Random, "Random");
+                      });
                   }
 
                   // Hill Climbing parameters
@@ -100,10 +171,29 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                   // Ant Colony Optimization parameters
                   if state.algo_type == AlgoType::AntColonyOptimization {
                       ui.label(egui::RichText::new("Ant Colony Optimization").strong());
+                      ui.horizontal(|ui| {
+                          ui.label("Variant:");
+                          ui.radio_value(&mut state.algo_config.aco_variant, ACOVariant::AntSystem, "Ant System");
+                          ui.radio_value(&mut state.algo_config.aco_variant, ACOVariant::MaxMin, "MAX-MIN");
+                          ui.radio_value(&mut state.algo_config.aco_variant, ACOVariant::AntColonySystem, "Ant Colony System");
+                      });
                       ui.add(egui::Slider::new(&mut state.algo_config.aco_evaporation, 0.0..=1.0).text("Evaporation Rate"));
                       ui.add(egui::Slider::new(&mut state.algo_config.aco_alpha, 0.0..=10.0).text("Alpha (Pheromone)"));
                       ui.add(egui::Slider::new(&mut state.algo_config.aco_beta, 0.0..=10.0).text("Beta (Heuristic)"));
                       ui.add(egui::Slider::new(&mut state.algo_config.aco_deposit, 0.1..=100.0).text("Pheromone Deposit"));
+                      ui.label("Time Window Heuristic:");
+                      ui.add(egui::Slider::new(&mut state.algo_config.aco_waiting_weight, 0.0..=10.0).text("Waiting Weight"));
+                      ui.add(egui::Slider::new(&mut state.algo_config.aco_urgency_weight, 0.0..=10.0).text("Urgency Weight"));
+                      ui.checkbox(&mut state.algo_config.aco_exclude_unreachable, "Exclude Moves Making Customers Unreachable");
+                      ui.checkbox(&mut state.algo_config.aco_candidate_lists, "Use Candidate Lists");
+                      if state.algo_config.aco_variant == ACOVariant::MaxMin {
+                          ui.add(egui::Slider::new(&mut state.algo_config.aco_p_best, 0.001..=1.0).text("p_best (tau_min / tau_max)"));
+                          ui.add(egui::Slider::new(&mut state.algo_config.aco_stagnation_limit, 0..=1000).text("Stagnation Limit (0 = never reset)"));
+                      }
+                      if state.algo_config.aco_variant == ACOVariant::AntColonySystem {
+                          ui.add(egui::Slider::new(&mut state.algo_config.aco_q0, 0.0..=1.0).text("q0 (Exploitation)"));
+                          ui.add(egui::Slider::new(&mut state.algo_config.aco_local_evaporation, 0.0..=1.0).text("Local Evaporation Rate"));
+                      }
                       ui.add(egui::Slider::new(&mut state.algo_config.population_size, 10..=500).text("Population Size"));
                   }
                   
@@ -112,15 +202,16 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                   // Evaluation parameters
                   if state.evaluation_type == EvaluationType::Weighted {
                       ui.label(egui::RichText::new("Weighted Evaluation").strong());
-                      ui.add(egui::Slider::new(&mut state.eval_config.total_distance_weight, 0.0..=10.0).text("Distance Weight"));
-                      ui.add(egui::Slider::new(&mut state.eval_config.violation_time_weight, 0.0..=10000.0).text("Violation Time Weight"));
-                      ui.add(egui::Slider::new(&mut state.eval_config.total_time_weight, 0.0..=10.0).text("Total Time Weight"));
-                      ui.add(egui::Slider::new(&mut state.eval_config.delay_weight, 0.0..=10.0).text("Delay Weight"));
+                      for (objective, weight) in &mut state.eval_config.objective_weights {
+                          // Les retards servent de pénalités, avec des poids bien plus forts
+                          let max = if objective.is_lateness() { 10000.0 } else { 10.0 };
+                          ui.add(egui::Slider::new(weight, 0.0..=max).text(format!("{} Weight", objective.name())));
+                      }
                   }
 
                   if state.evaluation_type == EvaluationType::Lexicographic {
                       ui.label(egui::RichText::new("Lexicographic Evaluation").strong());
-                      ui.checkbox(&mut state.eval_config.lexicographic_distance_first, "Prioritize Distance over Violation");
+                      lexicographic_order(ui, &mut state.eval_config.lexicographic_order);
                   }
               });
   
@@ -128,9 +219,46 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
   
               ui.group(|ui| {
                   ui.heading("Execution");
-                  ui.add(egui::Slider::new(&mut state.steps_per_frame, 1..=10000).text("Steps/Frame"));
+                  ui.add(egui::Slider::new(&mut state.steps_per_frame, 1..=10000).text("Steps per Batch"));
                   ui.add(egui::Slider::new(&mut state.algo_config.max_steps, 100..=100000000).text("Max Steps"));
                   ui.add(egui::Slider::new(&mut state.parallel_runs_count, 1..=1000).text("Parallel Runs"));
+                  history_settings(ui, &mut state.record_policy, &mut state.history_limit);
+              });
+
+              ui.add_space(10.0);
+
+              ui.group(|ui| {
+                  ui.heading("Configuration Groups");
+                  ui.label("Each group launches its own runs with the configuration above; the statistics view compares groups.");
+                  ui.horizontal(|ui| {
+                      ui.label("Name:");
+                      ui.text_edit_singleline(&mut state.group_name);
+                      if ui.button("Add Current Configuration").clicked() {
+                          state.add_group();
+                      }
+                  });
+                  let mut load = None;
+                  let mut remove = None;
+                  for (i, group) in state.groups.iter().enumerate() {
+                      ui.horizontal(|ui| {
+                          ui.label(format!("{}: {} x {}", group.name, group.algo_type.name(), group.runs_count));
+                          if ui.small_button("Load").clicked() {
+                              load = Some(i);
+                          }
+                          if ui.small_button("Remove").clicked() {
+                              remove = Some(i);
+                          }
+                      });
+                  }
+                  if let Some(i) = load {
+                      state.load_group(i);
+                  }
+                  if let Some(i) = remove {
+                      state.groups.remove(i);
+                  }
+                  if state.groups.is_empty() {
+                      ui.label("No group: all runs use the configuration above.");
+                  }
               });
   
               ui.add_space(20.0);
@@ -140,15 +268,147 @@ pub fn show(ui: &mut egui::Ui, state: &mut AppState) {
                   let btn = egui::Button::new("START SIMULATION").min_size(egui::Vec2::new(200.0, 50.0));
                   if ui.add(btn).clicked() {
                       state.phase = AppPhase::Running;
-                      for _ in 0..state.parallel_runs_count {
-                          state.start_new_run();
-                      }
+                      state.start_session();
                   }
               });
               if !start_enabled {
                   ui.label("Please load an instance first.");
+              } else if state.instance_analysis.as_ref().is_some_and(|analysis| analysis.is_infeasible()) {
+                  ui.colored_label(egui::Color32::RED, "Warning: the instance is infeasible, every tour will violate some window.");
               }
           });  
         });
         
 }
+
+// Politique d'enregistrement de l'historique des runs et borne sur sa taille
+fn history_settings(ui: &mut egui::Ui, policy: &mut RecordPolicy, limit: &mut HistoryLimit) {
+    ui.horizontal(|ui| {
+        ui.label("Record:");
+        let every = if let RecordPolicy::EveryN(n) = *policy { n } else { 10 };
+        let per_decade = if let RecordPolicy::LogSpaced(k) = *policy { k } else { 20 };
+        if ui.selectable_label(*policy == RecordPolicy::EveryImprovement, "Every Improvement").clicked() {
+            *policy = RecordPolicy::EveryImprovement;
+        }
+        if ui.selectable_label(matches!(policy, RecordPolicy::EveryN(_)), "Every N").clicked() {
+            *policy = RecordPolicy::EveryN(every);
+        }
+        if ui.selectable_label(matches!(policy, RecordPolicy::LogSpaced(_)), "Log-Spaced").clicked() {
+            *policy = RecordPolicy::LogSpaced(per_decade);
+        }
+        match policy {
+            RecordPolicy::EveryN(n) => { ui.add(egui::DragValue::new(n).range(1..=1000000).prefix("N: ")); }
+            RecordPolicy::LogSpaced(k) => { ui.add(egui::DragValue::new(k).range(1..=1000).suffix(" / decade")); }
+            RecordPolicy::EveryImprovement => {}
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("History:");
+        let capacity = match *limit {
+            HistoryLimit::Downsample(n) | HistoryLimit::RingBuffer(n) => n,
+            HistoryLimit::Unbounded => 2000,
+        };
+        if ui.selectable_label(*limit == HistoryLimit::Unbounded, "Unbounded").clicked() {
+            *limit = HistoryLimit::Unbounded;
+        }
+        if ui.selectable_label(matches!(limit, HistoryLimit::Downsample(_)), "Downsample").clicked() {
+            *limit = HistoryLimit::Downsample(capacity);
+        }
+        if ui.selectable_label(matches!(limit, HistoryLimit::RingBuffer(_)), "Last Entries").clicked() {
+            *limit = HistoryLimit::RingBuffer(capacity);
+        }
+        if let HistoryLimit::Downsample(n) | HistoryLimit::RingBuffer(n) = limit {
+            ui.add(egui::DragValue::new(n).range(2..=1000000).suffix(" entries"));
+        }
+    });
+}
+
+// Objectifs comparés un à un, le premier départageant d'abord les solutions
+fn lexicographic_order(ui: &mut egui::Ui, order: &mut Vec<Objective>) {
+    let mut move_up = None;
+    let mut remove = None;
+    for (rank, objective) in order.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}. {}", rank + 1, objective.name()));
+            if ui.add_enabled(rank > 0, egui::Button::new("⬆")).clicked() {
+                move_up = Some(rank);
+            }
+            if ui.add_enabled(order.len() > 1, egui::Button::new("✖")).clicked() {
+                remove = Some(rank);
+            }
+        });
+    }
+    if let Some(rank) = move_up {
+        order.swap(rank - 1, rank);
+    }
+    if let Some(rank) = remove {
+        order.remove(rank);
+    }
+
+    let missing: Vec<Objective> = Objective::ALL.into_iter().filter(|o| !order.contains(o)).collect();
+    if !missing.is_empty() {
+        egui::ComboBox::from_id_salt("lexicographic_add")
+            .selected_text("Add objective")
+            .show_ui(ui, |ui| {
+                for objective in missing {
+                    if ui.selectable_label(false, objective.name()).clicked() {
+                        order.push(objective);
+                    }
+                }
+            });
+    }
+}
+
+// Format, métrique et matrices externes utilisés au prochain chargement d'instance
+fn load_options(ui: &mut egui::Ui, options: &mut LoadOptions) {
+    egui::ComboBox::from_label("Format")
+        .selected_text(options.format.map(|f| f.name()).unwrap_or("Auto-detect"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut options.format, None, "Auto-detect");
+            for format in [InstanceFormat::Solomon, InstanceFormat::SolomonPotvinBengio, InstanceFormat::Dumas, InstanceFormat::Tsplib] {
+                ui.selectable_value(&mut options.format, Some(format), format.name());
+            }
+        });
+
+    ui.horizontal(|ui| {
+        let rounding_name = |rounding: Option<Rounding>| match rounding {
+            None => "Format default",
+            Some(Rounding::Floor) => "Floor",
+            Some(Rounding::Round) => "Round",
+            Some(Rounding::Ceil) => "Ceil",
+            Some(Rounding::Exact) => "Exact",
+            Some(Rounding::Scaled(_)) => "Scaled",
+        };
+        egui::ComboBox::from_label("Rounding")
+            .selected_text(rounding_name(options.rounding))
+            .show_ui(ui, |ui| {
+                for rounding in [None, Some(Rounding::Floor), Some(Rounding::Round), Some(Rounding::Ceil), Some(Rounding::Exact), Some(Rounding::Scaled(10.0))] {
+                    // Le facteur d'échelle ne compte pas dans la sélection
+                    let selected = options.rounding.map(|r| std::mem::discriminant(&r)) == rounding.map(|r| std::mem::discriminant(&r));
+                    if ui.selectable_label(selected, rounding_name(rounding)).clicked() && !selected {
+                        options.rounding = rounding;
+                    }
+                }
+            });
+        if let Some(Rounding::Scaled(factor)) = &mut options.rounding {
+            ui.add(egui::DragValue::new(factor).range(1.0..=10000.0).prefix("x"));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Triangle relaxation:");
+        ui.radio_value(&mut options.relax_triangle, None, "Format default");
+        ui.radio_value(&mut options.relax_triangle, Some(true), "On");
+        ui.radio_value(&mut options.relax_triangle, Some(false), "Off");
+    });
+
+    for (label, path) in [("Distance matrix:", &mut options.distance_matrix), ("Travel-time matrix:", &mut options.time_matrix)] {
+        ui.horizontal(|ui| {
+            ui.label(label);
+            let mut text = path.clone().unwrap_or_default();
+            if ui.text_edit_singleline(&mut text).on_hover_text("CSV or dense text file, leave empty to use the instance file").changed() {
+                *path = (!text.is_empty()).then_some(text);
+            }
+        });
+    }
+}
@@ -0,0 +1,138 @@
+// Édition manuelle de la tournée depuis l'onglet route, avec historique d'annulation
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EditMove {
+    /// Déplace le premier client à la position du second
+    Move,
+    Swap,
+    /// Inverse la portion de tournée entre les deux clients
+    TwoOpt,
+}
+
+impl EditMove {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EditMove::Move => "Move",
+            EditMove::Swap => "Swap",
+            EditMove::TwoOpt => "2-Opt",
+        }
+    }
+}
+
+/// Applique le mouvement entre les villes `a` et `b` de la tournée, renvoie faux s'il est sans effet
+pub fn apply_move(path: &mut Vec<u32>, edit: EditMove, a: u32, b: u32) -> bool {
+    let (Some(i), Some(j)) = (path.iter().position(|&v| v == a), path.iter().position(|&v| v == b)) else {
+        return false;
+    };
+    if i == j {
+        return false;
+    }
+    match edit {
+        EditMove::Move => {
+            let node = path.remove(i);
+            path.insert(j, node);
+        }
+        EditMove::Swap => path.swap(i, j),
+        EditMove::TwoOpt => path[i.min(j)..=i.max(j)].reverse(),
+    }
+    true
+}
+
+#[derive(Default)]
+pub struct RouteEditor {
+    pub enabled: bool,
+    // Ville choisie par un premier clic (Swap, 2-Opt) ou en cours de glissement (Move)
+    pub selected: Option<u32>,
+    undo: Vec<Vec<u32>>,
+    redo: Vec<Vec<u32>>,
+}
+
+impl RouteEditor {
+    pub fn apply(&mut self, path: &mut Vec<u32>, edit: EditMove, a: u32, b: u32) {
+        let previous = path.clone();
+        if apply_move(path, edit, a, b) {
+            self.undo.push(previous);
+            self.redo.clear();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self, path: &mut Vec<u32>) {
+        if let Some(previous) = self.undo.pop() {
+            self.redo.push(std::mem::replace(path, previous));
+        }
+    }
+
+    pub fn redo(&mut self, path: &mut Vec<u32>) {
+        if let Some(next) = self.redo.pop() {
+            self.undo.push(std::mem::replace(path, next));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moved(edit: EditMove, a: u32, b: u32) -> Vec<u32> {
+        let mut path = vec![0, 1, 2, 3, 4, 5];
+        assert!(apply_move(&mut path, edit, a, b));
+        path
+    }
+
+    #[test]
+    fn moves_apply_in_both_orders() {
+        assert_eq!(moved(EditMove::Move, 1, 4), [0, 2, 3, 4, 1, 5]);
+        assert_eq!(moved(EditMove::Move, 4, 1), [0, 4, 1, 2, 3, 5]);
+        for (a, b) in [(1, 4), (4, 1)] {
+            assert_eq!(moved(EditMove::Swap, a, b), [0, 4, 2, 3, 1, 5]);
+            assert_eq!(moved(EditMove::TwoOpt, a, b), [0, 4, 3, 2, 1, 5]);
+        }
+    }
+
+    #[test]
+    fn no_op_moves_are_not_recorded() {
+        let mut path = vec![0, 1, 2, 3];
+        let mut editor = RouteEditor::default();
+        for edit in [EditMove::Move, EditMove::Swap, EditMove::TwoOpt] {
+            editor.apply(&mut path, edit, 2, 2);
+            editor.apply(&mut path, edit, 1, 9);
+        }
+        assert_eq!(path, [0, 1, 2, 3]);
+        assert!(!editor.can_undo() && !editor.can_redo());
+    }
+
+    #[test]
+    fn undo_redo_and_new_edit_clears_redo() {
+        let mut path = vec![0, 1, 2, 3];
+        let mut editor = RouteEditor::default();
+        editor.apply(&mut path, EditMove::Swap, 1, 2);
+        editor.apply(&mut path, EditMove::TwoOpt, 1, 3);
+        assert_eq!(path, [0, 2, 3, 1]);
+
+        editor.undo(&mut path);
+        assert_eq!(path, [0, 2, 1, 3]);
+        editor.undo(&mut path);
+        assert_eq!(path, [0, 1, 2, 3]);
+        assert!(!editor.can_undo());
+        editor.redo(&mut path);
+        assert_eq!(path, [0, 2, 1, 3]);
+        assert!(editor.can_redo());
+
+        // Une nouvelle édition abandonne la branche annulée
+        editor.apply(&mut path, EditMove::Move, 3, 2);
+        assert_eq!(path, [0, 3, 2, 1]);
+        assert!(!editor.can_redo());
+        editor.redo(&mut path);
+        assert_eq!(path, [0, 3, 2, 1]);
+        editor.undo(&mut path);
+        assert_eq!(path, [0, 2, 1, 3]);
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use serde::Serialize;
+
+use crate::eval::utils::run_solution;
+use crate::io::io_solution::save_solution;
+use crate::shared::{GraphInstance, WindowKind};
+use crate::utils::history::write_history_csv;
+
+use super::state::{LogEntry, RunState, VisitInfo};
+
+// Dimensions des figures SVG exportées
+const SVG_WIDTH: f64 = 800.0;
+const SVG_HEIGHT: f64 = 600.0;
+const SVG_MARGIN: f64 = 50.0;
+
+/// Figures exportables en PNG (capture d'écran) ou SVG (redessinées)
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlotKind {
+    Route,
+    Convergence,
+    Gantt,
+}
+
+impl PlotKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlotKind::Route => "route",
+            PlotKind::Convergence => "convergence",
+            PlotKind::Gantt => "gantt",
+        }
+    }
+}
+
+/// Chemin du fichier exporté pour un run : `<dir>/<run>_<suffix>`
+pub fn run_file(dir: &str, run: &RunState, suffix: &str) -> PathBuf {
+    let name: String = run.name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    Path::new(dir).join(format!("{}_{}", name, suffix))
+}
+
+fn create_parent(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => fs::create_dir_all(parent),
+        _ => Ok(()),
+    }
+}
+
+fn csv_error(e: csv::Error) -> io::Error {
+    io::Error::other(e)
+}
+
+// --- 1. Données ---
+
+/// Meilleure tournée au format `.sol` (villes numérotées à partir de 1, distance totale)
+pub fn export_best_tour(run: &RunState, path: &Path) -> io::Result<()> {
+    let instance = run.instance.as_ref()
+        .ok_or_else(|| io::Error::other("Run has no instance"))?;
+    if run.current_solution_path.is_empty() {
+        return Err(io::Error::other("Run has no solution yet"));
+    }
+    create_parent(path)?;
+    let eval = run_solution(instance, &run.current_solution_path);
+    let sol_list: Vec<u32> = run.current_solution_path.iter().map(|&v| v + 1).collect();
+    save_solution(&path.to_string_lossy().to_string(), &sol_list, Some(eval.total_distance.round() as u32))
+}
+
+/// Historique du run : une ligne par entrée de log, une colonne par métrique
+pub fn export_history_csv(run: &RunState, path: &Path) -> io::Result<()> {
+    create_parent(path)?;
+    write_history_csv(&run.history, &run.metric_names, path)
+}
+
+#[derive(Serialize)]
+struct ScheduleRow {
+    position: usize,
+    node: usize,
+    arrival_time: f32,
+    wait_time: f32,
+    window_start: f32,
+    window_end: f32,
+    window_type: &'static str,
+    violation: f32,
+    penalty: f32,
+}
+
+/// Planning de la meilleure tournée : arrivée, attente, violation et pénalité à chaque nœud
+pub fn export_schedule_csv(run: &RunState, path: &Path) -> io::Result<()> {
+    create_parent(path)?;
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    for (position, visit) in run.get_schedule().iter().enumerate() {
+        writer.serialize(ScheduleRow {
+            position,
+            node: visit.node_idx,
+            arrival_time: visit.arrival_time,
+            wait_time: visit.wait_time,
+            window_start: visit.window_start,
+            window_end: visit.window_end,
+            window_type: visit.kind.name(),
+            violation: visit.violation,
+            penalty: visit.penalty,
+        }).map_err(csv_error)?;
+    }
+    writer.flush()
+}
+
+// --- 2. Statistiques agrégées ---
+
+#[derive(Serialize)]
+pub struct Summary {
+    pub group: String,
+    pub name: String,
+    pub count: usize,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub min: f32,
+    pub median: f32,
+    pub max: f32,
+}
+
+impl Summary {
+    fn new(group: &str, name: &str, mut values: Vec<f32>) -> Self {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let count = values.len();
+        let (group, name) = (group.to_string(), name.to_string());
+        if count == 0 {
+            return Self { group, name, count, mean: 0.0, std_dev: 0.0, min: 0.0, median: 0.0, max: 0.0 };
+        }
+        let mean = values.iter().sum::<f32>() / count as f32;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / count as f32;
+        let median = if count % 2 == 1 {
+            values[count / 2]
+        } else {
+            (values[count / 2 - 1] + values[count / 2]) / 2.0
+        };
+        Self { group, name, count, mean, std_dev: variance.sqrt(), min: values[0], median, max: values[count - 1] }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RunResult {
+    pub name: String,
+    pub group: String,
+    pub algorithm: String,
+    pub seed: u64,
+    pub iteration: usize,
+    pub distance: f32,
+    pub violation: f32,
+    pub metrics: BTreeMap<String, f32>,
+}
+
+#[derive(Serialize)]
+pub struct Statistics {
+    pub runs: Vec<RunResult>,
+    pub summary: Vec<Summary>,
+}
+
+/// Valeurs finales de chaque run et leur résumé par groupe de configuration (moyenne, écart-type, quantiles)
+pub fn aggregate(runs: &[RunState]) -> Statistics {
+    let results: Vec<RunResult> = runs.iter()
+        .filter_map(|run| run.history.last().map(|last| (run, last)))
+        .map(|(run, last): (&RunState, &LogEntry)| RunResult {
+            name: run.name.clone(),
+            group: run.spec.group.clone(),
+            algorithm: run.spec.algo_type.name().to_string(),
+            seed: run.spec.seed,
+            iteration: last.iteration,
+            distance: last.current_dist,
+            violation: last.current_viol,
+            metrics: run.metric_names.iter()
+                .map(|name| (name.clone(), last.metrics.get(name).copied().unwrap_or(0.0)))
+                .collect(),
+        })
+        .collect();
+
+    let mut groups: Vec<&str> = Vec::new();
+    for result in &results {
+        if !groups.contains(&result.group.as_str()) {
+            groups.push(&result.group);
+        }
+    }
+
+    let mut summary = Vec::new();
+    for group in groups {
+        let group_results: Vec<&RunResult> = results.iter().filter(|r| r.group == group).collect();
+        summary.push(Summary::new(group, "distance", group_results.iter().map(|r| r.distance).collect()));
+        summary.push(Summary::new(group, "violation", group_results.iter().map(|r| r.violation).collect()));
+        let metric_names: Vec<&String> = group_results.first().map(|r| r.metrics.keys().collect()).unwrap_or_default();
+        for name in metric_names {
+            summary.push(Summary::new(group, name, group_results.iter().filter_map(|r| r.metrics.get(name).copied()).collect()));
+        }
+    }
+
+    Statistics { runs: results, summary }
+}
+
+/// Résumé des valeurs finales sur tous les runs, une ligne par grandeur
+pub fn export_statistics_csv(runs: &[RunState], path: &Path) -> io::Result<()> {
+    create_parent(path)?;
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    for summary in aggregate(runs).summary {
+        writer.serialize(summary).map_err(csv_error)?;
+    }
+    writer.flush()
+}
+
+/// Valeurs finales de chaque run et leur résumé
+pub fn export_statistics_json(runs: &[RunState], path: &Path) -> io::Result<()> {
+    create_parent(path)?;
+    let json = serde_json::to_string_pretty(&aggregate(runs)).map_err(io::Error::other)?;
+    fs::write(path, json)
+}
+
+// --- 3. Figures ---
+
+/// Enregistre la zone `rect` (en points) d'une capture d'écran de la fenêtre
+pub fn save_screenshot_region(image: &egui::ColorImage, rect: egui::Rect, pixels_per_point: f32, path: &Path) -> io::Result<()> {
+    create_parent(path)?;
+    let region = image.region(&rect, Some(pixels_per_point));
+    let [width, height] = region.size;
+    image::save_buffer(path, region.as_raw(), width as u32, height as u32, image::ColorType::Rgba8)
+        .map_err(io::Error::other)
+}
+
+pub fn export_svg(kind: PlotKind, run: &RunState, graph_instance: &Option<GraphInstance>, path: &Path) -> io::Result<()> {
+    create_parent(path)?;
+    let svg = match kind {
+        PlotKind::Route => route_svg(run, graph_instance),
+        PlotKind::Convergence => convergence_svg(run),
+        PlotKind::Gantt => gantt_svg(run),
+    };
+    fs::write(path, svg)
+}
+
+// Repère d'une zone de tracé : conversion des coordonnées données -> pixels (y vers le haut)
+struct Frame {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+}
+
+impl Frame {
+    fn new(left: f64, top: f64, width: f64, height: f64, points: impl Iterator<Item = [f64; 2]>) -> Self {
+        let (mut x_min, mut x_max, mut y_min, mut y_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+        for [x, y] in points {
+            x_min = x_min.min(x);
+            x_max = x_max.max(x);
+            y_min = y_min.min(y);
+            y_max = y_max.max(y);
+        }
+        if x_min > x_max {
+            (x_min, x_max, y_min, y_max) = (0.0, 1.0, 0.0, 1.0);
+        }
+        // Évite une échelle dégénérée lorsque toutes les valeurs sont égales
+        if x_max - x_min < 1e-9 { x_max = x_min + 1.0; }
+        if y_max - y_min < 1e-9 { y_max = y_min + 1.0; }
+        Self { left, top, width, height, x_range: (x_min, x_max), y_range: (y_min, y_max) }
+    }
+
+    fn x(&self, x: f64) -> f64 {
+        self.left + (x - self.x_range.0) / (self.x_range.1 - self.x_range.0) * self.width
+    }
+
+    fn y(&self, y: f64) -> f64 {
+        self.top + self.height - (y - self.y_range.0) / (self.y_range.1 - self.y_range.0) * self.height
+    }
+
+    fn axes(&self, svg: &mut String, title: &str, x_label: &str, y_label: &str) {
+        let (bottom, right) = (self.top + self.height, self.left + self.width);
+        let _ = writeln!(svg, r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="none" stroke="#888"/>"##, self.left, self.top, self.width, self.height);
+        let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="14" text-anchor="middle">{}</text>"#, self.left + self.width / 2.0, self.top - 8.0, title);
+        let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="middle">{}</text>"#, self.left + self.width / 2.0, bottom + 30.0, x_label);
+        let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="middle" transform="rotate(-90 {:.1} {:.1})">{}</text>"#, self.left - 35.0, self.top + self.height / 2.0, self.left - 35.0, self.top + self.height / 2.0, y_label);
+        for (value, anchor, x) in [(self.x_range.0, "start", self.left), (self.x_range.1, "end", right)] {
+            let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="10" text-anchor="{}">{:.1}</text>"#, x, bottom + 14.0, anchor, value);
+        }
+        for (value, y) in [(self.y_range.0, bottom), (self.y_range.1, self.top + 10.0)] {
+            let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="10" text-anchor="end">{:.1}</text>"#, self.left - 4.0, y, value);
+        }
+    }
+
+    fn polyline(&self, svg: &mut String, points: &[[f64; 2]], color: &str) {
+        let coords: Vec<String> = points.iter().map(|&[x, y]| format!("{:.1},{:.1}", self.x(x), self.y(y))).collect();
+        let _ = writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1.5"/>"#, coords.join(" "), color);
+    }
+}
+
+fn svg_document(content: String) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n{content}</svg>\n",
+        w = SVG_WIDTH, h = SVG_HEIGHT, content = content,
+    )
+}
+
+fn route_svg(run: &RunState, graph_instance: &Option<GraphInstance>) -> String {
+    let mut svg = String::new();
+    let Some(graph_instance) = graph_instance else { return svg_document(svg) };
+
+    let frame = Frame::new(
+        SVG_MARGIN, SVG_MARGIN, SVG_WIDTH - 2.0 * SVG_MARGIN, SVG_HEIGHT - 2.0 * SVG_MARGIN,
+        graph_instance.graph.iter().map(|n| [n.x as f64, n.y as f64]),
+    );
+    frame.axes(&mut svg, &format!("Route - {}", run.name), "x", "y");
+
+    if !run.current_solution_path.is_empty() {
+        let mut points: Vec<[f64; 2]> = run.current_solution_path.iter()
+            .map(|&v| {
+                let n = &graph_instance.graph[v as usize];
+                [n.x as f64, n.y as f64]
+            })
+            .collect();
+        points.push(points[0]);
+        frame.polyline(&mut svg, &points, "blue");
+    }
+    for (i, n) in graph_instance.graph.iter().enumerate() {
+        let (x, y) = (frame.x(n.x as f64), frame.y(n.y as f64));
+        let _ = writeln!(svg, r#"<circle cx="{:.1}" cy="{:.1}" r="4" fill="red"/>"#, x, y);
+        let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="10">{}</text>"#, x + 5.0, y - 5.0, i);
+    }
+    svg_document(svg)
+}
+
+// Courbe tracée dans la figure de convergence : nom, couleur et valeur lue dans le log
+type Series = (&'static str, &'static str, fn(&LogEntry) -> f32);
+
+fn convergence_svg(run: &RunState) -> String {
+    let mut svg = String::new();
+    let panel_height = (SVG_HEIGHT - 3.0 * SVG_MARGIN) / 2.0;
+    let width = SVG_WIDTH - 2.0 * SVG_MARGIN;
+
+    let series: [Series; 2] = [
+        ("Distance", "blue", |e| e.current_dist),
+        ("Violation", "red", |e| e.current_viol),
+    ];
+    for (i, (name, color, value)) in series.into_iter().enumerate() {
+        let points: Vec<[f64; 2]> = run.history.iter().map(|e| [e.iteration as f64, value(e) as f64]).collect();
+        let top = SVG_MARGIN + i as f64 * (panel_height + SVG_MARGIN);
+        let frame = Frame::new(SVG_MARGIN, top, width, panel_height, points.iter().copied());
+        frame.axes(&mut svg, &format!("{} - {}", name, run.name), "Iteration", name);
+        frame.polyline(&mut svg, &points, color);
+        for event in &run.events {
+            let x = frame.x(event.iteration as f64);
+            let _ = writeln!(svg, r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#c8c864" stroke-dasharray="3"/>"##, x, top, x, top + panel_height);
+        }
+    }
+    svg_document(svg)
+}
+
+fn gantt_svg(run: &RunState) -> String {
+    let mut svg = String::new();
+    let schedule = run.get_schedule();
+
+    // Enveloppe de toutes les fenêtres de chaque ville visitée
+    let envelope = |v: &VisitInfo| match &run.instance {
+        Some(instance) => (instance.windows[v.node_idx].first_start(), instance.windows[v.node_idx].last_end()),
+        None => (v.window_start, v.window_end),
+    };
+    let extent = schedule.iter().flat_map(|v| {
+        let (start, end) = envelope(v);
+        [[start as f64, v.node_idx as f64 - 0.5], [end.max(v.arrival_time) as f64, v.node_idx as f64 + 0.5]]
+    });
+    let frame = Frame::new(SVG_MARGIN, SVG_MARGIN, SVG_WIDTH - 2.0 * SVG_MARGIN, SVG_HEIGHT - 2.0 * SVG_MARGIN, extent);
+    frame.axes(&mut svg, &format!("Schedule - {}", run.name), "Time", "Node Index");
+
+    let row_height = (frame.y(0.0) - frame.y(0.6)).abs();
+    let rect = |svg: &mut String, start: f32, end: f32, node: usize, color: &str| {
+        let x = frame.x(start as f64);
+        let _ = writeln!(svg, r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+            x, frame.y(node as f64) - row_height / 2.0, (frame.x(end as f64) - x).max(0.5), row_height, color);
+    };
+    for visit in &schedule {
+        let color = if visit.kind == WindowKind::Hard { "#c8c8c8" } else { "#b4d2f0" };
+        match &run.instance {
+            Some(instance) => {
+                for slot in instance.windows[visit.node_idx].slots() {
+                    rect(&mut svg, slot.wstart, slot.wend, visit.node_idx, color);
+                }
+            }
+            None => rect(&mut svg, visit.window_start, visit.window_end, visit.node_idx, color),
+        }
+    }
+    for visit in schedule.iter().filter(|v| v.wait_time > 0.0) {
+        rect(&mut svg, visit.arrival_time, visit.arrival_time + visit.wait_time, visit.node_idx, "gold");
+    }
+    for (i, visit) in schedule.iter().enumerate() {
+        let (x, y) = (frame.x(visit.arrival_time as f64), frame.y(visit.node_idx as f64));
+        let color = if visit.violation > 0.0 { "red" } else if visit.penalty > 0.0 { "orange" } else { "green" };
+        let _ = writeln!(svg, r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{}"/>"#, x, y, color);
+        let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" font-size="8">#{}</text>"#, x + 3.0, y - 3.0, i);
+    }
+    svg_document(svg)
+}
@@ -1,4 +1,7 @@
 pub mod app;
 pub mod state;
+pub mod worker;
+pub mod export;
+pub mod editor;
 pub mod tabs;
 pub mod components;
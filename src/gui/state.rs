@@ -1,17 +1,28 @@
-use rayon::prelude::*;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
 use std::sync::Arc; // Crucial pour partager l'instance entre les threads
 
+use eframe::egui;
+use ndarray::Array2;
+
 // Vos imports existants...
-use crate::algorithms::{Metaheuristic};
-use crate::eval::{Evaluation, Lexicographic, Weighted, EvaluationType};
+use crate::algorithms::{AlgoEvent, Elite, Metaheuristic};
+use crate::eval::{Counted, Evaluation, Lexicographic, Objective, Weighted, EvaluationType};
+use crate::eval::utils::{run_solution, visits};
 use crate::initializer::{Initializer, RandomInitializer};
-use crate::io::io_instance::load_instance;
+use crate::io::io_instance::{LoadOptions, load_instance_with};
+use crate::io::io_solution::{load_solution, validate_solution};
 use crate::neighborhood::{NeighborhoodType, LocalSearchType};
-use crate::shared::{GraphInstance, Instance, Solution, Fitness};
+use crate::shared::{GraphInstance, Instance, InstanceAnalysis, Solution, Fitness, WindowKind};
 use crate::factories::*;
 use crate::factories::AlgoType;
-use crate::algorithms::{CrossoverType, CompetitionType};
+use crate::utils::history::{History, HistoryLimit, RecordPolicy};
+use crate::utils::rng::{next_seed, with_seed};
+use crate::gui::editor::{EditMove, RouteEditor};
+use crate::gui::export::PlotKind;
+use crate::gui::worker::{PopulationSnapshot, RunCommand, RunSnapshot, RunStatus, SolverPool, SolverTask};
+use crate::algorithms::{ACOVariant, CrossoverType, CompetitionType, MigrationPolicy, MigrationTopology, ReplacementType};
 
 // --- 1. Configuration & Enums (Nettoyage) ---
 
@@ -23,6 +34,25 @@ pub enum ViewTab {
     Route,
     Metrics,
     Gantt,
+    /// Fréquence des arêtes dans la population (ou phéromones) sur la carte
+    Heatmap,
+    /// Distribution des fitness de la population courante
+    Population,
+    /// Température et taux d'acceptation du recuit simulé
+    Annealing,
+}
+
+impl ViewTab {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ViewTab::Route => "Route",
+            ViewTab::Metrics => "Metrics",
+            ViewTab::Gantt => "Gantt",
+            ViewTab::Heatmap => "Edge Heatmap",
+            ViewTab::Population => "Fitness Distribution",
+            ViewTab::Annealing => "Annealing",
+        }
+    }
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -31,6 +61,32 @@ pub enum ViewMode {
     Statistics,
 }
 
+/// Abscisse des courbes de convergence : seuls le temps et les évaluations sont comparables entre algorithmes
+#[derive(PartialEq, Clone, Copy)]
+pub enum XAxis {
+    Iteration,
+    Time,
+    Evaluations,
+}
+
+impl XAxis {
+    pub fn name(&self) -> &'static str {
+        match self {
+            XAxis::Iteration => "Iteration",
+            XAxis::Time => "Time (s)",
+            XAxis::Evaluations => "Evaluations",
+        }
+    }
+
+    pub fn value(&self, entry: &LogEntry) -> f64 {
+        match self {
+            XAxis::Iteration => entry.iteration as f64,
+            XAxis::Time => entry.elapsed as f64,
+            XAxis::Evaluations => entry.evaluations as f64,
+        }
+    }
+}
+
 // Regroupement des paramètres d'algorithme
 #[derive(Clone)]
 pub struct AlgoConfigParams {
@@ -49,7 +105,22 @@ pub struct AlgoConfigParams {
     pub ga_elitism_rate: f32,
     pub ga_competition_participation_rate: f32,
     pub ga_competition_type: CompetitionType,
-    pub ga_mutation_rate: f32,
+    pub ga_inversion_rate: f32,
+    pub ga_scramble_rate: f32,
+    pub ga_insertion_rate: f32,
+    pub ga_displacement_rate: f32,
+    pub ga_local_search_rate: f32,
+    pub ga_replacement_type: ReplacementType,
+    pub ga_sharing_radius: f32,
+    pub ga_duplicate_elimination: bool,
+    pub ga_restart_threshold: f32,
+    
+    // Island model Genetic Algorithm
+    pub island_count: usize,
+    pub migration_interval: usize,
+    pub migration_count: usize,
+    pub migration_topology: MigrationTopology,
+    pub migration_policy: MigrationPolicy,
     
     // Hill Climbing
     pub hc_step: usize,
@@ -59,6 +130,15 @@ pub struct AlgoConfigParams {
     pub aco_alpha: f32,
     pub aco_beta: f32,
     pub aco_deposit: f32,
+    pub aco_variant: ACOVariant,
+    pub aco_q0: f32,
+    pub aco_local_evaporation: f32,
+    pub aco_p_best: f32,
+    pub aco_stagnation_limit: usize,
+    pub aco_waiting_weight: f32,
+    pub aco_urgency_weight: f32,
+    pub aco_exclude_unreachable: bool,
+    pub aco_candidate_lists: bool,
     
     // Common parameters
     pub neighborhood: NeighborhoodType,
@@ -85,7 +165,22 @@ impl Default for AlgoConfigParams {
             ga_elitism_rate: 0.1,
             ga_competition_participation_rate: 0.5,
             ga_competition_type: CompetitionType::default(),
-            ga_mutation_rate: 0.1,
+            ga_inversion_rate: 0.1,
+            ga_scramble_rate: 0.0,
+            ga_insertion_rate: 0.05,
+            ga_displacement_rate: 0.05,
+            ga_local_search_rate: 0.1,
+            ga_replacement_type: ReplacementType::default(),
+            ga_sharing_radius: 0.1,
+            ga_duplicate_elimination: false,
+            ga_restart_threshold: 0.0,
+            
+            // Island model Genetic Algorithm
+            island_count: 4,
+            migration_interval: 20,
+            migration_count: 2,
+            migration_topology: MigrationTopology::default(),
+            migration_policy: MigrationPolicy::default(),
             
             // Hill Climbing
             hc_step: 100,
@@ -95,6 +190,15 @@ impl Default for AlgoConfigParams {
             aco_alpha: 1.0,
             aco_beta: 2.0,
             aco_deposit: 1.0,
+            aco_variant: ACOVariant::default(),
+            aco_q0: 0.9,
+            aco_local_evaporation: 0.1,
+            aco_p_best: 0.05,
+            aco_stagnation_limit: 100,
+            aco_waiting_weight: 1.0,
+            aco_urgency_weight: 1.0,
+            aco_exclude_unreachable: true,
+            aco_candidate_lists: true,
             
             // Common parameters
             neighborhood: NeighborhoodType::default(),
@@ -110,24 +214,30 @@ impl Default for AlgoConfigParams {
 pub struct EvalConfigParams {
     // Weighted evaluation
     pub violation_coefficient: f32,
-    pub total_distance_weight: f32,
-    pub violation_time_weight: f32,
-    pub total_time_weight: f32,
-    pub delay_weight: f32,
-    
-    // Lexicographic evaluation
-    pub lexicographic_distance_first: bool,
+    // Un poids par objectif, dans l'ordre de `Objective::ALL`
+    pub objective_weights: Vec<(Objective, f32)>,
+
+    // Lexicographic evaluation, du plus prioritaire au moins prioritaire
+    pub lexicographic_order: Vec<Objective>,
 }
 
 impl Default for EvalConfigParams {
     fn default() -> Self {
         Self {
-            total_distance_weight: 1.0,
-            violation_time_weight: 10.0,
-            total_time_weight: 0.0,
-            delay_weight: 5.0,
+            objective_weights: Objective::ALL.iter()
+                .map(|&objective| {
+                    let weight = match objective {
+                        Objective::Distance => 1.0,
+                        Objective::Tardiness => 10.0,
+                        Objective::Waiting => 5.0,
+                        Objective::Penalty => 1.0,
+                        _ => 0.0,
+                    };
+                    (objective, weight)
+                })
+                .collect(),
             violation_coefficient: 100.0,
-            lexicographic_distance_first: true,
+            lexicographic_order: vec![Objective::Distance, Objective::Tardiness],
         }
     }
 }
@@ -136,32 +246,30 @@ impl Default for EvalConfigParams {
 
 // On déplace la logique d'exécution "Weighted" vs "Lexicographic" ici
 // pour ne pas polluer RunState.
+// L'évaluation est comptée pour tracer la convergence en fonction du nombre d'évaluations
 pub enum Runner {
-    Weighted(Box<dyn Metaheuristic<Weighted>>, Weighted),
-    Lexicographic(Box<dyn Metaheuristic<Lexicographic>>, Lexicographic),
+    Weighted(Box<dyn Metaheuristic<Counted<Weighted>>>, Counted<Weighted>),
+    Lexicographic(Box<dyn Metaheuristic<Counted<Lexicographic>>>, Counted<Lexicographic>),
 }
 
 impl Runner {
-    /// Exécute un lot d'étapes (batch) et retourne si l'algo est terminé
-    pub fn step_batch(
-        &mut self, 
-        population: &mut [Solution], 
-        fitnesses: &mut [Fitness], 
-        instance: &Instance, 
-        steps: usize, 
+    /// Exécute une itération et retourne si l'algo est terminé
+    pub fn step(
+        &mut self,
+        population: &mut [Solution],
+        fitnesses: &mut [Fitness],
+        instance: &Instance,
         max_total_steps: usize
     ) -> bool {
         match self {
             Runner::Weighted(algo, eval) => {
-                for _ in 0..steps {
-                    if algo.get_iteration() >= max_total_steps { return true; }
+                if algo.get_iteration() < max_total_steps {
                     algo.step(population, fitnesses, instance, eval);
                 }
                 algo.get_iteration() >= max_total_steps
             },
             Runner::Lexicographic(algo, eval) => {
-                for _ in 0..steps {
-                    if algo.get_iteration() >= max_total_steps { return true; }
+                if algo.get_iteration() < max_total_steps {
                     algo.step(population, fitnesses, instance, eval);
                 }
                 algo.get_iteration() >= max_total_steps
@@ -169,6 +277,23 @@ impl Runner {
         }
     }
 
+    /// Fitness de la meilleure solution, sans la cloner
+    pub fn best_fitness(&self) -> Option<Fitness> {
+        match self {
+            Runner::Weighted(algo, _) => algo.get_best_solution().map(|elite| elite.fitness),
+            Runner::Lexicographic(algo, _) => algo.get_best_solution().map(|elite| elite.fitness),
+        }
+    }
+
+    /// Entrée d'historique décrivant l'état courant
+    pub fn capture(&self, elapsed: Duration) -> Option<LogEntry> {
+        let evaluations = self.evaluation_count();
+        match self {
+            Runner::Weighted(algo, _) => LogEntry::capture(algo.as_ref(), elapsed, evaluations),
+            Runner::Lexicographic(algo, _) => LogEntry::capture(algo.as_ref(), elapsed, evaluations),
+        }
+    }
+
     pub fn get_metrics(&self) -> HashMap<String, f32> {
         match self {
             Runner::Weighted(algo, _) => algo.get_metrics(),
@@ -183,25 +308,35 @@ impl Runner {
         }
     }
 
-    pub fn get_best_solution(&self, pop: &[Solution], fitnesses: &[Fitness], instance: &Instance) -> Option<(Vec<u32>, f32, f32)> {
-        if pop.is_empty() || fitnesses.is_empty() {
-            return None;
+    pub fn get_best_solution(&self) -> Option<Elite> {
+        match self {
+            Runner::Weighted(algo, _) => algo.get_best_solution().cloned(),
+            Runner::Lexicographic(algo, _) => algo.get_best_solution().cloned(),
+        }
+    }
+    
+    pub fn take_events(&mut self) -> Vec<AlgoEvent> {
+        match self {
+            Runner::Weighted(algo, _) => algo.take_events(),
+            Runner::Lexicographic(algo, _) => algo.take_events(),
         }
-
-        let (best_index, _) = fitnesses
-            .iter()
-            .enumerate()
-            .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())
-            .unwrap();
-
-        let best_solution = pop[best_index].clone();
-        
-        // Calculate real metrics using evaluation
-        use crate::eval::utils::run_solution;
-        let result = run_solution(instance, &best_solution);
-        Some((best_solution, result.total_distance, result.violation_time))
     }
     
+    pub fn pheromone_matrix(&self) -> Option<Array2<f32>> {
+        match self {
+            Runner::Weighted(algo, _) => algo.get_pheromone_matrix().cloned(),
+            Runner::Lexicographic(algo, _) => algo.get_pheromone_matrix().cloned(),
+        }
+    }
+
+    /// Nombre cumulé de solutions évaluées, population initiale comprise
+    pub fn evaluation_count(&self) -> u64 {
+        match self {
+            Runner::Weighted(_, eval) => eval.count(),
+            Runner::Lexicographic(_, eval) => eval.count(),
+        }
+    }
+
     pub fn current_iteration(&self) -> usize {
         match self {
             Runner::Weighted(algo, _) => algo.get_iteration(),
@@ -212,12 +347,7 @@ impl Runner {
 
 // --- 3. Structures de Données (Logs & Visites) ---
 
-pub struct LogEntry {
-    pub iteration: usize,
-    pub current_dist: f32,
-    pub current_viol: f32,
-    pub metrics: HashMap<String, f32>,
-}
+pub use crate::utils::history::LogEntry;
 
 pub struct VisitInfo {
     pub node_idx: usize,
@@ -225,72 +355,94 @@ pub struct VisitInfo {
     pub wait_time: f32,
     pub window_start: f32,
     pub window_end: f32,
+    // Retard sur une fenêtre dure
     pub violation: f32,
+    // Pénalité de la visite : retard sur une fenêtre souple, arrivée en avance (voir `WindowPolicy`)
+    pub penalty: f32,
+    pub kind: WindowKind,
 }
 
 // --- 4. RunState (État d'une exécution) ---
 
+/// Tout ce qu'il faut pour (re)construire un run à l'identique
+#[derive(Clone)]
+pub struct RunSpec {
+    pub algo_type: AlgoType,
+    pub algo_config: AlgoConfigParams,
+    pub evaluation_type: EvaluationType,
+    pub eval_config: EvalConfigParams,
+    pub steps_per_batch: usize,
+    pub seed: u64,
+    // Solution de départ lorsqu'un run en poursuit un autre, le reste de la population est aléatoire
+    pub initial_solution: Option<Solution>,
+    // Groupe de configuration auquel le run appartient dans la vue statistiques
+    pub group: String,
+    // Itérations enregistrées dans l'historique et nombre d'entrées conservées
+    pub record_policy: RecordPolicy,
+    pub history_limit: HistoryLimit,
+}
+
+/// Configuration nommée lançant plusieurs runs, comparée aux autres groupes dans la vue statistiques
+#[derive(Clone)]
+pub struct ConfigGroup {
+    pub name: String,
+    pub algo_type: AlgoType,
+    pub evaluation_type: EvaluationType,
+    pub algo_config: AlgoConfigParams,
+    pub eval_config: EvalConfigParams,
+    pub runs_count: usize,
+}
+
+// Vue du GUI sur un run : le solveur lui-même vit dans le thread de calcul (voir `worker`)
 pub struct RunState {
     pub id: usize,
     pub name: String,
-    pub runner: Option<Runner>,
-    pub population: Vec<Solution>,
-    pub fitnesses: Vec<Fitness>,
+    pub spec: RunSpec,
     // Utilisation de Arc pour éviter le clone coûteux de l'instance
     pub instance: Option<Arc<Instance>>, 
-    pub is_running: bool,
-    pub history: Vec<LogEntry>,
+    pub status: RunStatus,
+    pub history: History,
+    pub events: Vec<AlgoEvent>,
     pub current_solution_path: Vec<u32>,
     pub metric_names: Vec<String>,
+    // Problèmes détectés dans une solution chargée depuis un fichier
+    pub validation_issues: Vec<String>,
+    pub editor: RouteEditor,
+    // Dernier état de la population, publié seulement pour le run affiché en détail
+    pub population: Option<PopulationSnapshot>,
 }
 
 impl RunState {
-    pub fn new(id: usize, name: String) -> Self {
+    pub fn new(id: usize, name: String, spec: RunSpec) -> Self {
+        let history = History::new(spec.history_limit);
         Self {
-            id, name,
-            runner: None, population: Vec::new(), fitnesses: Vec::new(),
-            instance: None, is_running: false,
-            history: Vec::new(), current_solution_path: Vec::new(), metric_names: Vec::new(),
+            id, name, spec,
+            instance: None, status: RunStatus::Running,
+            history, events: Vec::new(), current_solution_path: Vec::new(), metric_names: Vec::new(),
+            validation_issues: Vec::new(),
+            editor: RouteEditor::default(),
+            population: None,
         }
     }
 
-    pub fn update(&mut self, steps: usize, max_steps: usize) {
-        if !self.is_running || self.runner.is_none() || self.instance.is_none() {
-            return;
+    /// Applique un snapshot publié par le thread de calcul
+    pub fn apply_snapshot(&mut self, snapshot: RunSnapshot) {
+        self.status = snapshot.status;
+        if let Some(path) = snapshot.best_path {
+            self.current_solution_path = path;
         }
-
-        let instance = self.instance.as_ref().unwrap();
-        let runner = self.runner.as_mut().unwrap();
-
-        // Délégation au Runner (plus propre)
-        let finished = runner.step_batch(
-            &mut self.population, 
-            &mut self.fitnesses, 
-            instance, 
-            steps, 
-            max_steps
-        );
-
-        if finished {
-            self.is_running = false;
-        }
-
-        // Mise à jour des logs et de la meilleure solution (only every 10 steps to avoid cluttering)
-        let current_iter = runner.current_iteration();
-        if current_iter % 10 == 0 {
-            if let Some((path, dist, viol)) = runner.get_best_solution(&self.population, &self.fitnesses, instance) {
-                self.current_solution_path = path;
-                self.history.push(LogEntry {
-                    iteration: current_iter,
-                    current_dist: dist,
-                    current_viol: viol,
-                    metrics: runner.get_metrics(),
-                });
-            }
+        self.history.extend(snapshot.history);
+        self.events.extend(snapshot.events);
+        if snapshot.population.is_some() {
+            self.population = snapshot.population;
         }
     }
 
-    // Le calcul du schedule est purement logique, il peut rester ici ou aller dans un module utils
+    pub fn is_running(&self) -> bool {
+        self.status == RunStatus::Running
+    }
+
+    // Le calcul du schedule est celui de l'évaluation (`visits`), la ville de départ en plus
     pub fn get_schedule(&self) -> Vec<VisitInfo> {
         let instance = match &self.instance {
             Some(i) => i,
@@ -299,51 +451,31 @@ impl RunState {
 
         if self.current_solution_path.is_empty() { return Vec::new(); }
 
-        let mut schedule = Vec::with_capacity(self.current_solution_path.len());
-        let mut current_time = 0.0;
-
-        // Code de calcul de schedule simplifié (similaire à l'original mais plus compact)
-        // ... (Logique inchangée, juste l'accès via Arc<Instance>)
-        
-        // Pour l'exemple, je remets le début de la logique adaptée à Arc
         let start_node = self.current_solution_path[0] as usize;
-        schedule.push(VisitInfo {
+        let start_window = instance.windows[start_node].service(0.0).1;
+        let start = VisitInfo {
             node_idx: start_node,
             arrival_time: 0.0,
             wait_time: 0.0,
-            window_start: instance.windows[start_node].wstart,
-            window_end: instance.windows[start_node].wend,
+            window_start: start_window.wstart,
+            window_end: start_window.wend,
             violation: 0.0,
-        });
+            penalty: 0.0,
+            kind: instance.policies[start_node].kind,
+        };
 
-        for i in 0..self.current_solution_path.len() {
-             let from = self.current_solution_path[i] as usize;
-             let to = self.current_solution_path[(i + 1) % self.current_solution_path.len()] as usize;
-             
-             let travel = instance.distance_matrix[[from, to]];
-             let arrival = current_time + travel;
-             let win = &instance.windows[to];
-             
-             let (wait, effective_arrival) = if arrival < win.wstart {
-                 (win.wstart - arrival, win.wstart)
-             } else {
-                 (0.0, arrival)
-             };
-             
-             let violation = if effective_arrival > win.wend { effective_arrival - win.wend } else { 0.0 };
-             current_time = effective_arrival;
-
-             schedule.push(VisitInfo {
-                 node_idx: to,
-                 arrival_time: arrival,
-                 wait_time: wait,
-                 window_start: win.wstart,
-                 window_end: win.wend,
-                 violation,
-             });
-        }
-
-        schedule
+        std::iter::once(start)
+            .chain(visits(instance, &self.current_solution_path).map(|visit| VisitInfo {
+                node_idx: visit.node,
+                arrival_time: visit.arrival,
+                wait_time: visit.wait,
+                window_start: visit.window.wstart,
+                window_end: visit.window.wend,
+                violation: visit.violation,
+                penalty: visit.penalty,
+                kind: visit.kind,
+            }))
+            .collect()
     }
 }
 
@@ -352,10 +484,15 @@ impl RunState {
 pub struct AppState {
     pub phase: AppPhase,
     pub instance_path: String,
+    pub load_options: LoadOptions,
+    // Erreur du dernier chargement d'instance
+    pub instance_error: Option<String>,
     
     // Utilisation de Arc pour le stockage principal
     pub instance: Option<Arc<Instance>>, 
     pub graph_instance: Option<GraphInstance>,
+    // Fenêtres de temps et faisabilité de l'instance chargée
+    pub instance_analysis: Option<InstanceAnalysis>,
 
     // Configuration séparée
     pub algo_type: AlgoType,
@@ -364,17 +501,46 @@ pub struct AppState {
     pub eval_config: EvalConfigParams,
     
     pub steps_per_frame: usize,
+    pub record_policy: RecordPolicy,
+    pub history_limit: HistoryLimit,
 
+    // Les solveurs tournent hors du thread de rendu
+    pub solver_pool: SolverPool,
     pub runs: Vec<RunState>,
     pub selected_run_index: Option<usize>,
+    // Onglet affiché dans la colonne gauche du tableau de bord
+    pub view_tab: ViewTab,
+    // La carte de chaleur affiche les phéromones plutôt que les arêtes de la population
+    pub heatmap_pheromone: bool,
     pub next_run_id: usize,
     pub parallel_runs_count: usize,
+
+    // Groupes de configuration de la session (vide : tous les runs utilisent la configuration courante)
+    pub groups: Vec<ConfigGroup>,
+    pub group_name: String,
+
+    // Contrôles des runs
+    pub step_count: usize,
+    pub continue_algo_type: AlgoType,
+    pub solution_path: String,
+    pub solution_status: Option<String>,
+    pub route_edit_move: EditMove,
+
+    // Export des résultats
+    pub export_dir: String,
+    pub export_status: Option<String>,
+    // Zone de chaque figure à la dernière frame, pour découper les captures d'écran
+    pub plot_rects: HashMap<PlotKind, egui::Rect>,
+    pub pending_screenshot: Option<(PlotKind, PathBuf)>,
     
     // View state
     pub view_mode: ViewMode,
     pub left_col_ratio: f32,
     pub right_top_ratio: f32,
     pub violation_log_scale: bool,
+    pub x_axis: XAxis,
+    // Écart (en %) à la meilleure distance réalisable définissant la cible des courbes d'atteinte
+    pub attainment_gap: f32,
 }
 
 impl AppState {
@@ -382,152 +548,424 @@ impl AppState {
         Self {
             phase: AppPhase::Configuration,
             instance_path: "data/inst_concours".to_string(),
+            load_options: LoadOptions::default(),
+            instance_error: None,
             instance: None,
             graph_instance: None,
+            instance_analysis: None,
             algo_type: AlgoType::SimulatedAnnealing,
             evaluation_type: EvaluationType::Weighted,
             algo_config: AlgoConfigParams::default(),
             eval_config: EvalConfigParams::default(),
             steps_per_frame: 10000,
+            record_policy: RecordPolicy::EveryN(10),
+            history_limit: HistoryLimit::Downsample(2000),
+            solver_pool: SolverPool::new(),
             runs: Vec::new(),
             selected_run_index: None,
+            view_tab: ViewTab::Route,
+            heatmap_pheromone: false,
             next_run_id: 0,
             parallel_runs_count: 1000,
+            groups: Vec::new(),
+            group_name: String::new(),
+            step_count: 100,
+            continue_algo_type: AlgoType::SimulatedAnnealing,
+            solution_path: "data/inst1.sol".to_string(),
+            solution_status: None,
+            route_edit_move: EditMove::Move,
+            export_dir: "exports".to_string(),
+            export_status: None,
+            plot_rects: HashMap::new(),
+            pending_screenshot: None,
             view_mode: ViewMode::Grid,
             left_col_ratio: 0.6,
             right_top_ratio: 0.5,
             violation_log_scale: false,
+            x_axis: XAxis::Iteration,
+            attainment_gap: 5.0,
         }
     }
 
     pub fn load_instance(&mut self) {
         if std::path::Path::new(&self.instance_path).exists() {
-            match load_instance(&self.instance_path) {
+            self.instance_error = None;
+            match load_instance_with(&self.instance_path, &self.load_options) {
                 Ok((inst, graph)) => {
+                    self.instance_analysis = Some(InstanceAnalysis::new(&inst));
                     // On wrap dans un Arc immédiatement
                     self.instance = Some(Arc::new(inst));
                     self.graph_instance = Some(graph);
-                    self.runs.clear();
-                    self.selected_run_index = None;
+                    self.clear_runs();
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    self.instance_error = Some(e.to_string());
                 }
-                Err(e) => eprintln!("Error: {}", e),
             }
+        } else {
+            self.instance_error = Some(format!("{} does not exist", self.instance_path));
         }
     }
 
     // Helper pour créer la config spécifique aux factories en utilisant AlgoParams
-    fn build_algo_config(&self) -> Result<AlgoConfig, String> {
+    fn build_algo_config(algo_type: AlgoType, algo_config: &AlgoConfigParams) -> Result<AlgoConfig, String> {
         let params = AlgoParams::new()
             // Simulated Annealing parameters
-            .initial_temperature(self.algo_config.sa_temp)
-            .cooling_rate(self.algo_config.sa_cooling)
-            .stopping_temperature(self.algo_config.sa_stopping)
-            .acceptance_smoothing_factor(self.algo_config.sa_acceptance_smoothing)
-            .initial_acceptance_rate(self.algo_config.sa_initial_acceptance_rate)
-            .delta_fitness_smoothing_factor(self.algo_config.sa_delta_fitness_smoothing)
-            .sa_backtracking_interval(self.algo_config.sa_backtracking_interval)
+            .initial_temperature(algo_config.sa_temp)
+            .cooling_rate(algo_config.sa_cooling)
+            .stopping_temperature(algo_config.sa_stopping)
+            .acceptance_smoothing_factor(algo_config.sa_acceptance_smoothing)
+            .initial_acceptance_rate(algo_config.sa_initial_acceptance_rate)
+            .delta_fitness_smoothing_factor(algo_config.sa_delta_fitness_smoothing)
+            .sa_backtracking_interval(algo_config.sa_backtracking_interval)
             // Genetic Algorithm parameters
-            .crossover_rate(self.algo_config.ga_crossover_rate)
-            .crossover_type(self.algo_config.ga_crossover_type)
-            .elitism_rate(self.algo_config.ga_elitism_rate)
-            .competition_participation_rate(self.algo_config.ga_competition_participation_rate)
-            .competition_type(self.algo_config.ga_competition_type)
-            .mutation_rate(self.algo_config.ga_mutation_rate)
+            .crossover_rate(algo_config.ga_crossover_rate)
+            .crossover_type(algo_config.ga_crossover_type)
+            .elitism_rate(algo_config.ga_elitism_rate)
+            .competition_participation_rate(algo_config.ga_competition_participation_rate)
+            .competition_type(algo_config.ga_competition_type)
+            .inversion_rate(algo_config.ga_inversion_rate)
+            .scramble_rate(algo_config.ga_scramble_rate)
+            .insertion_rate(algo_config.ga_insertion_rate)
+            .displacement_rate(algo_config.ga_displacement_rate)
+            .local_search_rate(algo_config.ga_local_search_rate)
+            .replacement_type(algo_config.ga_replacement_type)
+            .sharing_radius(algo_config.ga_sharing_radius)
+            .duplicate_elimination(algo_config.ga_duplicate_elimination)
+            .restart_diversity_threshold(algo_config.ga_restart_threshold)
+            // Island model parameters
+            .island_count(algo_config.island_count)
+            .migration_interval(algo_config.migration_interval)
+            .migration_count(algo_config.migration_count)
+            .migration_topology(algo_config.migration_topology)
+            .migration_policy(algo_config.migration_policy)
             // Hill Climbing parameters
-            .step(self.algo_config.hc_step)
+            .step(algo_config.hc_step)
             // Ant Colony Optimization parameters
-            .evaporation_rate(self.algo_config.aco_evaporation)
-            .alpha(self.algo_config.aco_alpha)
-            .beta(self.algo_config.aco_beta)
-            .pheromone_deposit(self.algo_config.aco_deposit)
+            .evaporation_rate(algo_config.aco_evaporation)
+            .alpha(algo_config.aco_alpha)
+            .beta(algo_config.aco_beta)
+            .pheromone_deposit(algo_config.aco_deposit)
+            .aco_variant(algo_config.aco_variant)
+            .q0(algo_config.aco_q0)
+            .local_evaporation_rate(algo_config.aco_local_evaporation)
+            .p_best(algo_config.aco_p_best)
+            .stagnation_limit(algo_config.aco_stagnation_limit)
+            .waiting_weight(algo_config.aco_waiting_weight)
+            .urgency_weight(algo_config.aco_urgency_weight)
+            .exclude_unreachable(algo_config.aco_exclude_unreachable)
+            .candidate_lists(algo_config.aco_candidate_lists)
             // Common parameters
-            .neighborhood_type(self.algo_config.neighborhood)
-            .local_search_type(self.algo_config.local_search_type)
-            .max_steps(self.algo_config.max_steps)
-            .max_iter(self.algo_config.max_steps)
-            .population_size(self.algo_config.population_size);
+            .neighborhood_type(algo_config.neighborhood)
+            .local_search_type(algo_config.local_search_type)
+            .max_steps(algo_config.max_steps)
+            .max_iter(algo_config.max_steps)
+            .population_size(algo_config.population_size);
         
-        params.build_config(self.algo_type)
+        params.build_config(algo_type)
     }
 
-    pub fn start_new_run(&mut self) {
+    /// Spécification d'un nouveau run à partir de la configuration courante
+    fn current_spec(&self, algo_type: AlgoType, initial_solution: Option<Solution>) -> RunSpec {
+        RunSpec {
+            algo_type,
+            algo_config: self.algo_config.clone(),
+            evaluation_type: self.evaluation_type,
+            eval_config: self.eval_config.clone(),
+            steps_per_batch: self.steps_per_frame,
+            seed: next_seed(),
+            initial_solution,
+            group: algo_type.name().to_string(),
+            record_policy: self.record_policy,
+            history_limit: self.history_limit,
+        }
+    }
+
+    /// Construit le solveur décrit par `spec` et le confie au thread de calcul.
+    /// L'algorithme et la population initiale sont construits sous la graine du run, ce qui rend le run reproductible.
+    fn launch(&mut self, spec: RunSpec, name: String) -> Option<RunState> {
         let instance_arc = match &self.instance {
             Some(i) => i.clone(), // Clone l'Arc (pas cher), pas la structure
-            None => return,
+            None => return None,
         };
 
-        let mut run = RunState::new(self.next_run_id, format!("Run {}", self.next_run_id));
-        self.next_run_id += 1;
-
-        let factory_enum = match self.build_algo_config() {
+        let factory_enum = match Self::build_algo_config(spec.algo_type, &spec.algo_config) {
             Ok(config) => config.into_factory(),
             Err(e) => {
                 eprintln!("Failed to build algorithm config: {}", e);
-                return;
+                return None;
             }
         };
-        let mut initializer = RandomInitializer; // Ou configurable via self.config
 
         // Construction du Runner propre
-        let (runner, pop, fits) = match self.evaluation_type {
+        let (runner, pop, fits) = with_seed(spec.seed, || match spec.evaluation_type {
             EvaluationType::Weighted => {
-                let eval = Weighted {
-                    total_distance_weight: self.eval_config.total_distance_weight,
-                    violation_time_weight: self.eval_config.violation_time_weight,
-                    total_time_weight: self.eval_config.total_time_weight,
-                    delay_weight: self.eval_config.delay_weight,
-                };
+                let eval = Counted::new(Weighted::new(spec.eval_config.objective_weights.clone()));
                 let algo = factory_enum.build(&instance_arc);
-                let (p, f) = self.init_population(&mut initializer, &instance_arc, &eval);
+                let (p, f) = Self::init_population(&spec, &instance_arc, &eval);
                 (Runner::Weighted(algo, eval), p, f)
             },
             EvaluationType::Lexicographic => {
-                let eval = Lexicographic::new(self.eval_config.lexicographic_distance_first);
+                let eval = Counted::new(Lexicographic::with_objectives(spec.eval_config.lexicographic_order.clone()));
                 let algo = factory_enum.build(&instance_arc);
-                let (p, f) = self.init_population(&mut initializer, &instance_arc, &eval);
+                let (p, f) = Self::init_population(&spec, &instance_arc, &eval);
                 (Runner::Lexicographic(algo, eval), p, f)
             },
-        };
+        });
 
+        let mut run = RunState::new(self.next_run_id, name, spec);
+        self.next_run_id += 1;
         run.metric_names = runner.get_metric_names();
-        run.runner = Some(runner);
-        run.population = pop;
-        run.fitnesses = fits;
-        run.instance = Some(instance_arc);
-        run.is_running = true;
-        
+        run.instance = Some(instance_arc.clone());
+
+        self.solver_pool.add(SolverTask::new(
+            run.id,
+            runner,
+            pop,
+            fits,
+            instance_arc,
+            &run.spec,
+        ));
+        Some(run)
+    }
+
+    /// Ajoute la configuration courante comme groupe de `parallel_runs_count` runs
+    pub fn add_group(&mut self) {
+        let name = match self.group_name.trim() {
+            "" => format!("{} #{}", self.algo_type.name(), self.groups.len() + 1),
+            name => name.to_string(),
+        };
+        self.groups.push(ConfigGroup {
+            name,
+            algo_type: self.algo_type,
+            evaluation_type: self.evaluation_type,
+            algo_config: self.algo_config.clone(),
+            eval_config: self.eval_config.clone(),
+            runs_count: self.parallel_runs_count,
+        });
+        self.group_name.clear();
+    }
+
+    /// Recharge la configuration d'un groupe dans l'écran de configuration
+    pub fn load_group(&mut self, group_idx: usize) {
+        if let Some(group) = self.groups.get(group_idx) {
+            self.algo_type = group.algo_type;
+            self.evaluation_type = group.evaluation_type;
+            self.algo_config = group.algo_config.clone();
+            self.eval_config = group.eval_config.clone();
+            self.parallel_runs_count = group.runs_count;
+            self.group_name = group.name.clone();
+        }
+    }
+
+    /// Lance les runs de chaque groupe de configuration, ou à défaut `parallel_runs_count` runs
+    /// de la configuration courante
+    pub fn start_session(&mut self) {
+        if self.groups.is_empty() {
+            for _ in 0..self.parallel_runs_count {
+                self.start_new_run();
+            }
+            return;
+        }
+
+        // Le i-ème run de chaque groupe reçoit la même graine : les runs sont appariés entre groupes
+        let base_seed = next_seed();
+        for group in self.groups.clone() {
+            for i in 0..group.runs_count {
+                let spec = RunSpec {
+                    algo_type: group.algo_type,
+                    algo_config: group.algo_config.clone(),
+                    evaluation_type: group.evaluation_type,
+                    eval_config: group.eval_config.clone(),
+                    steps_per_batch: self.steps_per_frame,
+                    seed: base_seed.wrapping_add(i as u64),
+                    initial_solution: None,
+                    group: group.name.clone(),
+                    record_policy: self.record_policy,
+                    history_limit: self.history_limit,
+                };
+                let name = format!("Run {} ({})", self.next_run_id, group.name);
+                if let Some(run) = self.launch(spec, name) {
+                    self.runs.push(run);
+                }
+            }
+        }
+    }
+
+    /// Noms des groupes des runs, dans l'ordre de première apparition
+    pub fn run_groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = Vec::new();
+        for run in &self.runs {
+            if !groups.contains(&run.spec.group) {
+                groups.push(run.spec.group.clone());
+            }
+        }
+        groups
+    }
+
+    pub fn start_new_run(&mut self) {
+        let spec = self.current_spec(self.algo_type, None);
+        let name = format!("Run {}", self.next_run_id);
+        if let Some(run) = self.launch(spec, name) {
+            self.runs.push(run);
+        }
+    }
+
+    /// Relance un run depuis le début avec la même graine, à la place de l'ancien
+    pub fn restart_run(&mut self, run_idx: usize) {
+        let Some(old) = self.runs.get(run_idx) else { return };
+        if old.status == RunStatus::Loaded {
+            return;
+        }
+        let (old_id, spec, name) = (old.id, old.spec.clone(), old.name.clone());
+        self.solver_pool.send(old_id, RunCommand::Stop);
+        if let Some(run) = self.launch(spec, name) {
+            self.runs[run_idx] = run;
+        }
+    }
+
+    /// Ajoute la solution du fichier `solution_path` comme un run en lecture seule, validée sur l'instance
+    pub fn load_solution_file(&mut self) -> Result<(), String> {
+        let instance = self.instance.clone().ok_or("No instance loaded")?;
+        let solution = load_solution(&self.solution_path).map_err(|e| e.to_string())?;
+
+        let validation_issues = validate_solution(&solution.path, instance.size());
+        // Une ville hors de l'instance rend la solution impossible à évaluer
+        if solution.path.iter().any(|&node| node as usize >= instance.size()) {
+            return Err(validation_issues.join("; "));
+        }
+
+        let eval = run_solution(&instance, &solution.path);
+        let file_name = std::path::Path::new(&self.solution_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.solution_path.clone());
+
+        let mut spec = self.current_spec(self.algo_type, None);
+        spec.group = "Loaded".to_string();
+        let mut run = RunState::new(self.next_run_id, format!("Loaded {}", file_name), spec);
+        self.next_run_id += 1;
+        run.status = RunStatus::Loaded;
+        run.instance = Some(instance);
+        run.history.push(LogEntry {
+            iteration: 0,
+            elapsed: 0.0,
+            evaluations: 0,
+            current_dist: eval.total_distance,
+            current_viol: eval.violation_time,
+            metrics: HashMap::new(),
+        });
+        run.current_solution_path = solution.path;
+        run.validation_issues = validation_issues;
         self.runs.push(run);
+        self.selected_run_index = Some(self.runs.len() - 1);
+        Ok(())
+    }
+
+    /// Nouveau run de l'algorithme `algo_type` (avec les paramètres courants) partant de la meilleure solution du run
+    pub fn continue_from_best(&mut self, run_idx: usize, algo_type: AlgoType) {
+        let Some(source) = self.runs.get(run_idx) else { return };
+        if source.current_solution_path.is_empty() {
+            return;
+        }
+        let source_name = source.name.clone();
+        let mut spec = self.current_spec(algo_type, Some(source.current_solution_path.clone()));
+        spec.group = "Continued".to_string();
+        let name = format!("Run {} ({} from {})", self.next_run_id, algo_type.name(), source_name);
+        if let Some(run) = self.launch(spec, name) {
+            self.runs.push(run);
+            self.selected_run_index = Some(self.runs.len() - 1);
+        }
     }
 
     fn init_population<E: Evaluation>(
-        &self, 
-        initializer: &mut RandomInitializer, 
+        spec: &RunSpec,
         instance: &Instance, 
         eval: &E
     ) -> (Vec<Solution>, Vec<Fitness>) {
-        let size = match self.algo_type {
+        let size = match spec.algo_type {
             AlgoType::SimulatedAnnealing | AlgoType::HillClimbing => 1,
-            _ => self.algo_config.population_size
+            // Chaque île travaille sur une sous-population contiguë
+            AlgoType::IslandGeneticAlgorithm => spec.algo_config.population_size * spec.algo_config.island_count,
+            _ => spec.algo_config.population_size
         };
 
+        let mut initializer = RandomInitializer; // Ou configurable via self.config
         let mut pop = Vec::with_capacity(size);
         let mut fits = Vec::with_capacity(size);
 
-        for _ in 0..size {
-            let sol = initializer.initialize(instance);
+        for i in 0..size {
+            let sol = match &spec.initial_solution {
+                Some(initial) if i == 0 => initial.clone(),
+                _ => initializer.initialize(instance),
+            };
             fits.push(eval.score(instance, &sol));
             pop.push(sol);
         }
         (pop, fits)
     }
 
+    /// Intègre les snapshots publiés par le thread de calcul depuis la dernière frame
     pub fn update_solvers(&mut self) {
-        let steps = self.steps_per_frame;
-        let max_steps = self.algo_config.max_steps;
-        
-        // Utilisation de Rayon pour paralléliser l'update des runs
-        self.runs.par_iter_mut().for_each(|run| {
-            run.update(steps, max_steps);
+        let inspected = self.selected_run_index.and_then(|i| self.runs.get(i)).map(|run| run.id);
+        self.solver_pool.inspect(inspected);
+        let index: HashMap<usize, usize> = self.runs.iter().enumerate().map(|(i, run)| (run.id, i)).collect();
+        for snapshot in self.solver_pool.drain() {
+            // Les snapshots de runs supprimés entre-temps sont ignorés
+            if let Some(&i) = index.get(&snapshot.run_id) {
+                self.runs[i].apply_snapshot(snapshot);
+            }
+        }
+    }
+
+    pub fn send_command(&self, run_id: usize, command: RunCommand) {
+        self.solver_pool.send(run_id, command);
+    }
+
+    pub fn send_command_all(&self, command: RunCommand) {
+        for run in &self.runs {
+            self.solver_pool.send(run.id, command);
+        }
+    }
+
+    pub fn restart_all(&mut self) {
+        for run_idx in 0..self.runs.len() {
+            self.restart_run(run_idx);
+        }
+    }
+
+    /// Supprime tous les runs et abandonne leurs calculs
+    pub fn clear_runs(&mut self) {
+        self.solver_pool.clear();
+        self.runs.clear();
+        self.selected_run_index = None;
+    }
+
+    /// Affiche le résultat d'un export dans la barre du haut
+    pub fn report_export(&mut self, path: &std::path::Path, result: std::io::Result<()>) {
+        self.export_status = Some(match result {
+            Ok(()) => format!("Exported {}", path.display()),
+            Err(e) => format!("Export of {} failed: {}", path.display(), e),
         });
     }
+
+    /// Demande une capture de la fenêtre, découpée sur la figure à la réception (voir `save_screenshot`)
+    pub fn request_screenshot(&mut self, ctx: &egui::Context, kind: PlotKind, path: PathBuf) {
+        if !self.plot_rects.contains_key(&kind) {
+            self.export_status = Some(format!("The {} plot is not visible", kind.name()));
+            return;
+        }
+        self.pending_screenshot = Some((kind, path));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::default()));
+    }
+
+    pub fn save_screenshot(&mut self, image: &egui::ColorImage, pixels_per_point: f32) {
+        if let Some((kind, path)) = self.pending_screenshot.take()
+            && let Some(rect) = self.plot_rects.get(&kind)
+        {
+            let result = crate::gui::export::save_screenshot_region(image, *rect, pixels_per_point, &path);
+            self.report_export(&path, result);
+        }
+    }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use eframe::egui;
 use egui_plot::{Bar, BarChart, Legend, Orientation, Plot, PlotPoint, PlotPoints, Points, Text};
 use crate::gui::state::RunState;
+use crate::shared::WindowKind;
 
 pub fn show(ui: &mut egui::Ui, run_state: &RunState) {
     ui.push_id("gantt_plot", |ui| {
@@ -10,9 +11,11 @@ pub fn show(ui: &mut egui::Ui, run_state: &RunState) {
             ui.label("• Y-Axis: Node Index");
             ui.horizontal(|ui| {
                 ui.label("Legend:");
-                ui.colored_label(egui::Color32::GRAY, "■ Time Window");
+                ui.colored_label(egui::Color32::GRAY, "■ Hard Window");
+                ui.colored_label(egui::Color32::LIGHT_BLUE, "■ Soft Window");
                 ui.colored_label(egui::Color32::YELLOW, "■ Wait Time");
                 ui.colored_label(egui::Color32::GREEN, "● On Time");
+                ui.colored_label(egui::Color32::ORANGE, "● Penalized");
                 ui.colored_label(egui::Color32::RED, "● Late (Violation)");
             });
         });
@@ -22,39 +25,53 @@ pub fn show(ui: &mut egui::Ui, run_state: &RunState) {
             .x_axis_label("Time")
             .y_axis_label("Node Index")
             .show(ui, |plot_ui| {
-                if let Some(_instance) = &run_state.instance {
+                if let Some(instance) = &run_state.instance {
                     let schedule = run_state.get_schedule();
                     
-                    // Time Windows
+                    // Time Windows, dures et souples dans deux séries, toutes les fenêtres de chaque ville
                     let mut bars = Vec::new();
-                    for (_i, visit) in schedule.iter().enumerate() {
-                        let mut bar = Bar::new(visit.node_idx as f64, (visit.window_end - visit.window_start) as f64)
-                            .base_offset(visit.window_start as f64)
-                            .fill(egui::Color32::from_gray(200).linear_multiply(0.5))
-                            .name("Time Window");
-                        bar.orientation = Orientation::Horizontal;
-                        bars.push(bar);
+                    let mut soft_bars = Vec::new();
+                    for visit in &schedule {
+                        let hard = visit.kind == WindowKind::Hard;
+                        let color = if hard { egui::Color32::from_gray(200) } else { egui::Color32::LIGHT_BLUE };
+                        for slot in instance.windows[visit.node_idx].slots() {
+                            // La fenêtre où commence le service est plus marquée que les autres
+                            let used = slot.wstart == visit.window_start && slot.wend == visit.window_end;
+                            let mut bar = Bar::new(visit.node_idx as f64, (slot.wend - slot.wstart) as f64)
+                                .base_offset(slot.wstart as f64)
+                                .fill(color.linear_multiply(if used { 0.5 } else { 0.2 }))
+                                .name(format!("Time Window ({})", visit.kind.name()));
+                            bar.orientation = Orientation::Horizontal;
+                            if hard { bars.push(bar) } else { soft_bars.push(bar) }
+                        }
                     }
                     plot_ui.bar_chart(BarChart::new(bars).name("Time Windows").color(egui::Color32::GRAY));
+                    plot_ui.bar_chart(BarChart::new(soft_bars).name("Soft Windows").color(egui::Color32::LIGHT_BLUE));
 
                     // Arrivals
                     let mut arrival_points = Vec::new();
+                    let mut penalized_points = Vec::new();
                     let mut violation_points = Vec::new();
                     
                     for (i, visit) in schedule.iter().enumerate() {
+                        let point = [visit.arrival_time as f64, visit.node_idx as f64];
                         if visit.violation > 0.0 {
-                            violation_points.push([visit.arrival_time as f64, visit.node_idx as f64]);
+                            violation_points.push(point);
+                        } else if visit.penalty > 0.0 {
+                            penalized_points.push(point);
                         } else {
-                            arrival_points.push([visit.arrival_time as f64, visit.node_idx as f64]);
+                            arrival_points.push(point);
                         }
                         
-                        // Add visit order label
+                        // Add visit order label, with the penalty incurred
+                        let label = if visit.penalty > 0.0 { format!("#{} (+{:.1})", i, visit.penalty) } else { format!("#{}", i) };
                         plot_ui.text(Text::new(
                             PlotPoint::new(visit.arrival_time as f64, visit.node_idx as f64 + 0.3), 
-                            format!("#{}", i)
+                            label
                         ).color(egui::Color32::WHITE));
                     }
                     plot_ui.points(Points::new(PlotPoints::new(arrival_points)).radius(4.0).color(egui::Color32::GREEN).name("On Time"));
+                    plot_ui.points(Points::new(PlotPoints::new(penalized_points)).radius(5.0).color(egui::Color32::ORANGE).name("Penalized"));
                     plot_ui.points(Points::new(PlotPoints::new(violation_points)).radius(6.0).color(egui::Color32::RED).name("Violation"));
 
                     // Wait times
@@ -1,9 +1,32 @@
 use eframe::egui;
-use egui_plot::{Line, Plot, PlotPoints};
-use crate::gui::state::RunState;
+use egui_plot::{Line, Plot, PlotPoints, VLine};
+use crate::gui::state::{RunState, XAxis};
 
-pub fn show(ui: &mut egui::Ui, run_state: &RunState, log_scale: &mut bool) {
+/// Choix de l'abscisse des courbes de convergence
+pub fn x_axis_selector(ui: &mut egui::Ui, x_axis: &mut XAxis) {
+    ui.label("X-Axis:");
+    for candidate in [XAxis::Iteration, XAxis::Time, XAxis::Evaluations] {
+        ui.radio_value(x_axis, candidate, candidate.name());
+    }
+}
+
+// Marque les événements notables du run (migrations, ...) par des lignes verticales
+fn draw_events(plot_ui: &mut egui_plot::PlotUi, run_state: &RunState, x_axis: XAxis) {
+    for event in &run_state.events {
+        // Position de l'événement : première entrée de log qui le suit
+        let Some(entry) = run_state.history.iter().find(|e| e.iteration >= event.iteration) else { continue };
+        plot_ui.vline(
+            VLine::new(x_axis.value(entry))
+                .name(&event.description)
+                .color(egui::Color32::from_rgba_unmultiplied(200, 200, 100, 80)),
+        );
+    }
+}
+
+pub fn show(ui: &mut egui::Ui, run_state: &RunState, log_scale: &mut bool, x_axis: &mut XAxis) {
     ui.push_id("metrics_plot", |ui| {
+        ui.horizontal(|ui| x_axis_selector(ui, x_axis));
+        let x_axis = *x_axis;
         let available_height = ui.available_height();
         let num_plots = 2 + run_state.metric_names.len(); // Distance + Violation + metrics
         let plot_height = (available_height - (num_plots as f32 * 20.0)) / num_plots as f32;
@@ -14,9 +37,10 @@ pub fn show(ui: &mut egui::Ui, run_state: &RunState, log_scale: &mut bool) {
             .height(plot_height)
             .show(ui, |plot_ui| {
                 let fitness_points: PlotPoints = run_state.history.iter()
-                    .map(|e| [e.iteration as f64, e.current_dist as f64])
+                    .map(|e| [x_axis.value(e), e.current_dist as f64])
                     .collect();
                 plot_ui.line(Line::new(fitness_points).name("Distance"));
+                draw_events(plot_ui, run_state, x_axis);
             });
 
         // Violation Plot
@@ -33,13 +57,14 @@ pub fn show(ui: &mut egui::Ui, run_state: &RunState, log_scale: &mut bool) {
                     .map(|e| {
                         let val = e.current_viol as f64;
                         if use_log {
-                            [e.iteration as f64, (val + 1.0).ln()]
+                            [x_axis.value(e), (val + 1.0).ln()]
                         } else {
-                            [e.iteration as f64, val]
+                            [x_axis.value(e), val]
                         }
                     })
                     .collect();
                 plot_ui.line(Line::new(points).name("Violation").color(egui::Color32::RED));
+                draw_events(plot_ui, run_state, x_axis);
             });
 
         // Metric Plots
@@ -49,9 +74,10 @@ pub fn show(ui: &mut egui::Ui, run_state: &RunState, log_scale: &mut bool) {
                 .height(plot_height)
                 .show(ui, |plot_ui| {
                     let points: PlotPoints = run_state.history.iter()
-                        .filter_map(|e| e.metrics.get(name).map(|v| [e.iteration as f64, *v as f64]))
+                        .filter_map(|e| e.metrics.get(name).map(|v| [x_axis.value(e), *v as f64]))
                         .collect();
                     plot_ui.line(Line::new(points).name(name));
+                    draw_events(plot_ui, run_state, x_axis);
                 });
         }
     });
@@ -1,3 +1,5 @@
 pub mod route;
 pub mod metrics;
 pub mod gantt;
+pub mod report;
+pub mod population;
@@ -0,0 +1,182 @@
+// Vues de diagnostic de la recherche : population courante (arêtes, fitness) et traces du recuit simulé.
+// La population n'est publiée que pour le run affiché en détail.
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Line, Plot, PlotPoints, Points};
+
+use crate::gui::state::{RunState, XAxis};
+use crate::shared::GraphInstance;
+
+// Arêtes tracées au plus, les plus fréquentes d'abord
+const MAX_HEATMAP_EDGES: usize = 2000;
+// Arêtes de poids relatif inférieur ignorées
+const MIN_HEATMAP_WEIGHT: f32 = 0.02;
+const FITNESS_BINS: usize = 30;
+
+// Du jaune pâle (faible) au rouge foncé (fort), pour un poids relatif dans [0, 1]
+fn heat_color(t: f32) -> egui::Color32 {
+    let t = t.clamp(0.0, 1.0);
+    egui::Color32::from_rgb(
+        (255.0 - 80.0 * t) as u8,
+        (230.0 * (1.0 - t)) as u8,
+        (120.0 * (1.0 - t)) as u8,
+    )
+}
+
+fn waiting_label(ui: &mut egui::Ui, run_state: &RunState) -> bool {
+    if run_state.population.is_some() {
+        return false;
+    }
+    if run_state.status.is_active() {
+        ui.label("Waiting for the solver to publish the population...");
+    } else {
+        // Seul le run affiché publie sa population, un run terminé ailleurs n'en a pas
+        ui.label("No population available: it is only captured while the run is displayed here.");
+    }
+    true
+}
+
+/// Fréquence des arêtes dans la population (ou niveaux de phéromones) sur la carte des villes
+pub fn show_heatmap(ui: &mut egui::Ui, run_state: &RunState, graph_instance: &Option<GraphInstance>, show_pheromone: &mut bool) {
+    if waiting_label(ui, run_state) {
+        return;
+    }
+    let Some(population) = &run_state.population else { return };
+
+    ui.horizontal(|ui| {
+        let has_pheromone = population.pheromone.is_some();
+        ui.radio_value(show_pheromone, false, "Population edges");
+        let pheromone_button = ui.add_enabled(has_pheromone, egui::RadioButton::new(*show_pheromone, "Pheromones"))
+            .on_disabled_hover_text("Only Ant Colony runs maintain pheromones");
+        if pheromone_button.clicked() {
+            *show_pheromone = true;
+        }
+    });
+    let matrix = match (&population.pheromone, *show_pheromone) {
+        (Some(pheromone), true) => pheromone,
+        _ => &population.edge_frequency,
+    };
+
+    // Arêtes non orientées, normalisées par la plus forte
+    let size = matrix.nrows();
+    let mut edges: Vec<(usize, usize, f32)> = (0..size)
+        .flat_map(|i| (i + 1..size).map(move |j| (i, j)))
+        .map(|(i, j)| (i, j, matrix[[i, j]].max(matrix[[j, i]])))
+        .filter(|&(_, _, w)| w.is_finite() && w > 0.0)
+        .collect();
+    edges.sort_by(|a, b| b.2.total_cmp(&a.2));
+    let max_weight = edges.first().map(|e| e.2).unwrap_or(1.0);
+    edges.retain(|&(_, _, w)| w / max_weight >= MIN_HEATMAP_WEIGHT);
+    edges.truncate(MAX_HEATMAP_EDGES);
+
+    ui.label(format!(
+        "Iteration {} | {} edges shown | max weight {:.3}",
+        population.iteration, edges.len(), max_weight
+    ));
+
+    Plot::new("Edge Heatmap")
+        .data_aspect(1.0)
+        .show(ui, |plot_ui| {
+            let Some(graph_instance) = graph_instance else { return };
+            // Les arêtes faibles d'abord, pour que les fortes soient dessinées au-dessus
+            for &(i, j, w) in edges.iter().rev() {
+                let (Some(a), Some(b)) = (graph_instance.graph.get(i), graph_instance.graph.get(j)) else { continue };
+                let t = w / max_weight;
+                let line = Line::new(PlotPoints::new(vec![[a.x as f64, a.y as f64], [b.x as f64, b.y as f64]]))
+                    .color(heat_color(t))
+                    .width(0.5 + 3.5 * t);
+                plot_ui.line(line);
+            }
+            let points: PlotPoints = graph_instance.graph.iter().map(|n| [n.x as f64, n.y as f64]).collect();
+            plot_ui.points(Points::new(points).radius(4.0).color(egui::Color32::LIGHT_BLUE));
+        });
+}
+
+/// Histogramme des fitness de la population courante
+pub fn show_fitness_distribution(ui: &mut egui::Ui, run_state: &RunState) {
+    if waiting_label(ui, run_state) {
+        return;
+    }
+    let Some(population) = &run_state.population else { return };
+
+    let values: Vec<f32> = population.fitnesses.iter().copied().filter(|v| v.is_finite()).collect();
+    if values.is_empty() {
+        ui.label("The population has no finite fitness yet.");
+        return;
+    }
+    let n = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / n).sqrt();
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    // Nombre de fitness distinctes : un indicateur grossier de la diversité
+    let mut sorted = values.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted.dedup();
+
+    ui.label(format!(
+        "Iteration {} | size {} | min {:.1} | mean {:.1} | max {:.1} | std {:.1} | distinct {}",
+        population.iteration, values.len(), min, mean, max, std_dev, sorted.len()
+    ));
+
+    let range = max - min;
+    let bars: Vec<Bar> = if range <= 1e-6 {
+        vec![Bar::new(min as f64, n as f64).width(1.0)]
+    } else {
+        let step = range / FITNESS_BINS as f32;
+        let mut counts = [0usize; FITNESS_BINS];
+        for v in &values {
+            counts[(((v - min) / step) as usize).min(FITNESS_BINS - 1)] += 1;
+        }
+        counts.iter().enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(i, count)| Bar::new((min + (i as f32 + 0.5) * step) as f64, *count as f64).width(step as f64))
+            .collect()
+    };
+
+    Plot::new("Fitness Distribution")
+        .x_axis_label("Fitness")
+        .y_axis_label("Solutions")
+        .show(ui, |plot_ui| {
+            plot_ui.bar_chart(BarChart::new(bars).color(egui::Color32::LIGHT_BLUE).name("Fitness"));
+        });
+}
+
+/// Traces de la température et du taux d'acceptation calculés par le recuit simulé
+pub fn show_annealing(ui: &mut egui::Ui, run_state: &RunState, x_axis: XAxis) {
+    let has_trace = |name: &str| run_state.metric_names.iter().any(|m| m == name);
+    if !has_trace("temperature") {
+        ui.label("Temperature and acceptance traces are only available for Simulated Annealing runs.");
+        return;
+    }
+    let trace = |name: &'static str, transform: fn(f64) -> f64| -> PlotPoints {
+        run_state.history.iter()
+            .filter_map(|e| e.metrics.get(name).map(|v| [x_axis.value(e), transform(*v as f64)]))
+            .collect()
+    };
+    let plot_height = (ui.available_height() - 60.0) / 3.0;
+
+    ui.label("Temperature (log10)");
+    Plot::new("Annealing Temperature")
+        .height(plot_height)
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(trace("temperature", |t| t.max(f64::MIN_POSITIVE).log10())).name("Temperature"));
+        });
+
+    ui.label("Average acceptance probability");
+    Plot::new("Annealing Acceptance")
+        .height(plot_height)
+        .include_y(0.0)
+        .include_y(1.0)
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(trace("acceptance_probability_avg", |p| p)).name("Acceptance").color(egui::Color32::ORANGE));
+        });
+
+    ui.label("Current fitness (smoothed) vs best distance");
+    Plot::new("Annealing Fitness")
+        .height(plot_height)
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new(trace("fitness_avg", |f| f)).name("Fitness (avg)").color(egui::Color32::GRAY));
+            let best: PlotPoints = run_state.history.iter().map(|e| [x_axis.value(e), e.current_dist as f64]).collect();
+            plot_ui.line(Line::new(best).name("Best distance").color(egui::Color32::BLUE));
+        });
+}
@@ -0,0 +1,51 @@
+use eframe::egui;
+use crate::eval::utils::run_solution;
+use crate::gui::state::RunState;
+
+/// Détail de l'évaluation de la solution courante : validité, coûts et clients en retard
+pub fn show(ui: &mut egui::Ui, run_state: &RunState) {
+    let Some(instance) = &run_state.instance else { return };
+    if run_state.current_solution_path.is_empty() { return; }
+
+    egui::CollapsingHeader::new("Solution Report")
+        .id_salt("solution_report")
+        .default_open(!run_state.validation_issues.is_empty())
+        .show(ui, |ui| {
+            if run_state.validation_issues.is_empty() {
+                ui.colored_label(egui::Color32::GREEN, format!("Valid tour of {} nodes", instance.size()));
+            } else {
+                for issue in &run_state.validation_issues {
+                    ui.colored_label(egui::Color32::RED, issue);
+                }
+            }
+
+            let eval = run_solution(instance, &run_state.current_solution_path);
+            ui.label(format!(
+                "Distance: {:.1} | Total time: {:.1} | Violation: {:.1} | Late customers: {} | Delay: {:.1}",
+                eval.total_distance, eval.total_time, eval.violation_time, eval.nb_violations, eval.delay
+            ));
+            ui.label(format!(
+                "Completion time: {:.1} | Weighted lateness: {:.1} | Penalty: {:.1}",
+                eval.completion_time, eval.weighted_lateness, eval.penalty
+            ));
+
+            let late: Vec<String> = run_state.get_schedule().iter()
+                .filter(|visit| visit.violation > 0.0)
+                .map(|visit| format!("{} (+{:.1})", visit.node_idx, visit.violation))
+                .collect();
+            if !late.is_empty() {
+                egui::ScrollArea::vertical().id_salt("late_customers").max_height(40.0).show(ui, |ui| {
+                    ui.label(format!("Late: {}", late.join(", ")));
+                });
+            }
+            let penalized: Vec<String> = run_state.get_schedule().iter()
+                .filter(|visit| visit.penalty > 0.0)
+                .map(|visit| format!("{} ({}, {:.1})", visit.node_idx, visit.kind.name(), visit.penalty))
+                .collect();
+            if !penalized.is_empty() {
+                egui::ScrollArea::vertical().id_salt("penalized_customers").max_height(40.0).show(ui, |ui| {
+                    ui.label(format!("Penalized: {}", penalized.join(", ")));
+                });
+            }
+        });
+}
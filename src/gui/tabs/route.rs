@@ -1,14 +1,69 @@
 use eframe::egui;
 use egui_plot::{Arrows, Line, Plot, PlotPoint, PlotPoints, Points, Text};
 
+use crate::eval::utils::run_solution;
+use crate::gui::editor::EditMove;
 use crate::gui::state::RunState;
 use crate::shared::GraphInstance;
 
-pub fn show(ui: &mut egui::Ui, run_state: &RunState, graph_instance: &Option<GraphInstance>) {
-    ui.push_id("route_plot", |ui| {
+// Distance maximale (en pixels) entre le pointeur et une ville pour la sélectionner
+const PICK_RADIUS: f32 = 12.0;
+
+// Ville la plus proche du pointeur dans le tracé
+fn node_under_pointer(plot_ui: &egui_plot::PlotUi, graph_instance: &GraphInstance) -> Option<u32> {
+    let pointer = plot_ui.response().hover_pos()?;
+    graph_instance.graph.iter().enumerate()
+        .map(|(i, n)| (i, plot_ui.screen_from_plot(PlotPoint::new(n.x as f64, n.y as f64)).distance(pointer)))
+        .filter(|&(_, distance)| distance <= PICK_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i as u32)
+}
+
+/// Affiche la tournée et, en mode édition, la modifie. Renvoie vrai si une recherche locale
+/// doit être lancée depuis la tournée éditée.
+pub fn show(ui: &mut egui::Ui, run_state: &mut RunState, graph_instance: &Option<GraphInstance>, edit_move: &mut EditMove) -> bool {
+    let mut launch_local_search = false;
+    // Le solveur d'un run actif écraserait les modifications
+    let editable = !run_state.status.is_active() && !run_state.current_solution_path.is_empty();
+
+    ui.horizontal_wrapped(|ui| {
+        ui.add_enabled(editable, egui::Checkbox::new(&mut run_state.editor.enabled, "Edit route"))
+            .on_disabled_hover_text("Stop the run to edit its route");
+        let editing = editable && run_state.editor.enabled;
+        if editing {
+            for candidate in [EditMove::Move, EditMove::Swap, EditMove::TwoOpt] {
+                ui.radio_value(edit_move, candidate, candidate.name());
+            }
+            let path = &mut run_state.current_solution_path;
+            if ui.add_enabled(run_state.editor.can_undo(), egui::Button::new("Undo")).clicked() {
+                run_state.editor.undo(path);
+            }
+            if ui.add_enabled(run_state.editor.can_redo(), egui::Button::new("Redo")).clicked() {
+                run_state.editor.redo(path);
+            }
+            let valid = run_state.validation_issues.is_empty();
+            launch_local_search = ui.add_enabled(valid, egui::Button::new("Local Search"))
+                .on_hover_text("Start a Hill Climbing run from this tour")
+                .clicked();
+        }
+        if let Some(instance) = &run_state.instance && !run_state.current_solution_path.is_empty() {
+            let eval = run_solution(instance, &run_state.current_solution_path);
+            ui.label(format!("Distance: {:.1} | Violation: {:.1}", eval.total_distance, eval.violation_time));
+        }
+    });
+    let editing = editable && run_state.editor.enabled;
+    if !editing {
+        run_state.editor.selected = None;
+    }
+
+    let edit = ui.push_id("route_plot", |ui| {
         Plot::new("TSP Route")
             .data_aspect(1.0)
+            // En édition, le glissement déplace une ville plutôt que la vue
+            .allow_drag(!editing)
+            .allow_boxed_zoom(!editing)
             .show(ui, |plot_ui| {
+                let mut edit = None;
                 if let Some(graph_instance) = graph_instance {
                     // Draw cities
                     let points: PlotPoints = graph_instance.graph.iter().map(|n| [n.x as f64, n.y as f64]).collect();
@@ -75,13 +130,71 @@ pub fn show(ui: &mut egui::Ui, run_state: &RunState, graph_instance: &Option<Gra
                         plot_ui.line(Line::new(PlotPoints::new(line_points)).color(egui::Color32::BLUE));
                         plot_ui.arrows(Arrows::new(arrow_origins, arrow_tips).color(egui::Color32::from_rgb(255, 165, 0))); // Orange arrows
                     }
+
+                    if editing {
+                        edit = handle_edit(plot_ui, graph_instance, run_state.editor.selected, *edit_move);
+                    }
                 }
-            });
-    });
+                edit
+            }).inner
+    }).inner;
+
+    match edit {
+        Some(EditAction::Select(node)) => run_state.editor.selected = node,
+        Some(EditAction::Apply(a, b)) => {
+            run_state.editor.selected = None;
+            run_state.editor.apply(&mut run_state.current_solution_path, *edit_move, a, b);
+        }
+        None => {}
+    }
     
     ui.separator();
     ui.label(egui::RichText::new("Path Sequence:").strong());
     egui::ScrollArea::vertical().max_height(60.0).show(ui, |ui| {
         ui.label(run_state.current_solution_path.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" -> "));
     });
+    launch_local_search
+}
+
+enum EditAction {
+    Select(Option<u32>),
+    Apply(u32, u32),
+}
+
+// Move : glisser une ville sur une autre. Swap et 2-Opt : cliquer successivement sur deux villes.
+fn handle_edit(plot_ui: &mut egui_plot::PlotUi, graph_instance: &GraphInstance, selected: Option<u32>, edit_move: EditMove) -> Option<EditAction> {
+    let hovered = node_under_pointer(plot_ui, graph_instance);
+    let response = plot_ui.response().clone();
+
+    if let Some(node) = selected {
+        let n = &graph_instance.graph[node as usize];
+        plot_ui.points(Points::new(vec![[n.x as f64, n.y as f64]]).radius(11.0).color(egui::Color32::YELLOW));
+        if edit_move == EditMove::Move && let Some(pointer) = plot_ui.pointer_coordinate() {
+            plot_ui.line(Line::new(PlotPoints::new(vec![[n.x as f64, n.y as f64], [pointer.x, pointer.y]])).color(egui::Color32::YELLOW));
+        }
+    }
+
+    match edit_move {
+        EditMove::Move => {
+            if response.drag_started() {
+                Some(EditAction::Select(hovered))
+            } else if response.drag_stopped() {
+                match (selected, hovered) {
+                    (Some(a), Some(b)) => Some(EditAction::Apply(a, b)),
+                    _ => Some(EditAction::Select(None)),
+                }
+            } else {
+                None
+            }
+        }
+        EditMove::Swap | EditMove::TwoOpt => {
+            if !response.clicked() {
+                return None;
+            }
+            match (selected, hovered) {
+                (Some(a), Some(b)) => Some(EditAction::Apply(a, b)),
+                (_, hovered) => Some(EditAction::Select(hovered)),
+            }
+        }
+    }
 }
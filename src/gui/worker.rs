@@ -0,0 +1,377 @@
+use rayon::prelude::*;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ndarray::Array2;
+
+use crate::algorithms::AlgoEvent;
+use crate::shared::{Fitness, Instance, Solution};
+
+use crate::utils::history::HistoryRecorder;
+
+use super::state::{LogEntry, RunSpec, Runner};
+
+// Intervalle minimal entre deux snapshots d'un même run, pour ne pas saturer le canal
+const PUBLISH_INTERVAL: Duration = Duration::from_millis(50);
+// Durée visée d'un tour du pool sur tous les runs actifs : les commandes (pause, arrêt, pas-à-pas)
+// sont relues entre deux tours
+const ROUND_TIME_BUDGET: Duration = Duration::from_millis(20);
+
+// --- 1. Messages échangés avec le thread de calcul ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RunCommand {
+    Pause,
+    Resume,
+    /// Met le run en pause puis exécute ce nombre d'itérations
+    Step(usize),
+    Stop,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RunStatus {
+    Running,
+    Paused,
+    Finished,
+    Stopped,
+    /// Solution chargée depuis un fichier, sans solveur associé
+    Loaded,
+}
+
+impl RunStatus {
+    pub fn is_active(&self) -> bool {
+        matches!(self, RunStatus::Running | RunStatus::Paused)
+    }
+}
+
+/// État publié par le thread de calcul : l'historique et les événements sont des ajouts depuis
+/// le snapshot précédent, le chemin est la meilleure solution courante.
+pub struct RunSnapshot {
+    pub run_id: usize,
+    pub status: RunStatus,
+    pub best_path: Option<Vec<u32>>,
+    pub history: Vec<LogEntry>,
+    pub events: Vec<AlgoEvent>,
+    pub population: Option<PopulationSnapshot>,
+}
+
+/// État de la population courante, pour diagnostiquer la convergence du run affiché en détail
+pub struct PopulationSnapshot {
+    pub iteration: usize,
+    // Part des solutions empruntant chaque arête (non orientée, matrice symétrique)
+    pub edge_frequency: Array2<f32>,
+    pub fitnesses: Vec<Fitness>,
+    pub pheromone: Option<Array2<f32>>,
+}
+
+impl PopulationSnapshot {
+    fn capture(runner: &Runner, population: &[Solution], fitnesses: &[Fitness], size: usize) -> Self {
+        let mut edge_frequency = Array2::zeros((size, size));
+        let weight = 1.0 / population.len().max(1) as f32;
+        for solution in population {
+            for (i, &from) in solution.iter().enumerate() {
+                let to = solution[(i + 1) % solution.len()];
+                let (from, to) = (from as usize, to as usize);
+                edge_frequency[[from, to]] += weight;
+                if from != to {
+                    edge_frequency[[to, from]] += weight;
+                }
+            }
+        }
+        Self {
+            iteration: runner.current_iteration(),
+            edge_frequency,
+            fitnesses: fitnesses.to_vec(),
+            pheromone: runner.pheromone_matrix(),
+        }
+    }
+}
+
+enum PoolMessage {
+    Add(Box<SolverTask>),
+    Command(usize, RunCommand),
+    Inspect(Option<usize>),
+    Clear,
+}
+
+// --- 2. Tâche de calcul (un run) ---
+
+/// Tout ce qui est nécessaire pour faire avancer un run, possédé par le thread de calcul
+pub struct SolverTask {
+    run_id: usize,
+    runner: Runner,
+    population: Vec<Solution>,
+    fitnesses: Vec<Fitness>,
+    instance: Arc<Instance>,
+    steps_per_batch: usize,
+    max_steps: usize,
+    status: RunStatus,
+    // Itérations restantes d'un pas-à-pas demandé en pause
+    steps_remaining: usize,
+    // Temps passé à calculer, les pauses ne comptent pas
+    active_time: Duration,
+    recorder: HistoryRecorder,
+    last_recorded_iteration: Option<usize>,
+    // Run affiché en détail : ses snapshots incluent la population
+    inspected: bool,
+
+    // Ajouts en attente de publication
+    pending_history: Vec<LogEntry>,
+    pending_events: Vec<AlgoEvent>,
+    last_publish: Instant,
+}
+
+impl SolverTask {
+    pub fn new(
+        run_id: usize,
+        runner: Runner,
+        population: Vec<Solution>,
+        fitnesses: Vec<Fitness>,
+        instance: Arc<Instance>,
+        spec: &RunSpec,
+    ) -> Self {
+        Self {
+            run_id,
+            runner,
+            population,
+            fitnesses,
+            instance,
+            steps_per_batch: spec.steps_per_batch,
+            max_steps: spec.algo_config.max_steps,
+            status: RunStatus::Running,
+            steps_remaining: 0,
+            active_time: Duration::ZERO,
+            recorder: HistoryRecorder::new(spec.record_policy),
+            last_recorded_iteration: None,
+            inspected: false,
+            pending_history: Vec::new(),
+            pending_events: Vec::new(),
+            last_publish: Instant::now(),
+        }
+    }
+
+    /// Vrai si la tâche a des itérations à exécuter
+    fn is_busy(&self) -> bool {
+        match self.status {
+            RunStatus::Running => true,
+            RunStatus::Paused => self.steps_remaining > 0,
+            RunStatus::Finished | RunStatus::Stopped | RunStatus::Loaded => false,
+        }
+    }
+
+    /// Exécute au plus `steps_per_batch` itérations, en s'arrêtant dès que `budget` est écoulé
+    /// (au moins une itération est toujours faite)
+    fn run_batch(&mut self, budget: Duration, snapshot_tx: &Sender<RunSnapshot>) {
+        if !self.is_busy() {
+            return;
+        }
+
+        let steps = match self.status {
+            RunStatus::Paused => self.steps_remaining.min(self.steps_per_batch),
+            _ => self.steps_per_batch,
+        };
+
+        let start = Instant::now();
+        let mut finished = false;
+        let mut done = 0;
+        while done < steps {
+            finished = self.runner.step(&mut self.population, &mut self.fitnesses, &self.instance, self.max_steps);
+            done += 1;
+            // La dernière itération est enregistrée une seule fois, après la boucle
+            if finished {
+                break;
+            }
+            let iteration = self.runner.current_iteration();
+            if self.recorder.should_record(iteration, self.runner.best_fitness()) {
+                self.record(self.active_time + start.elapsed());
+            }
+            // Le reste du lot est repris au tour suivant
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+        if self.status == RunStatus::Paused {
+            self.steps_remaining -= done;
+        }
+        self.active_time += start.elapsed();
+        self.pending_events.extend(self.runner.take_events());
+
+        if finished {
+            self.status = RunStatus::Finished;
+            self.steps_remaining = 0;
+            self.record(self.active_time);
+        }
+        // La fin d'un pas-à-pas est publiée immédiatement
+        let step_done = self.status == RunStatus::Paused && self.steps_remaining == 0;
+        self.publish(snapshot_tx, finished || step_done);
+    }
+
+    fn apply(&mut self, command: RunCommand, snapshot_tx: &Sender<RunSnapshot>) {
+        self.status = match (command, self.status) {
+            (RunCommand::Pause, RunStatus::Running) => RunStatus::Paused,
+            (RunCommand::Resume, RunStatus::Paused) => RunStatus::Running,
+            (RunCommand::Step(steps), RunStatus::Running | RunStatus::Paused) => {
+                self.steps_remaining += steps;
+                RunStatus::Paused
+            }
+            (RunCommand::Stop, status) if status.is_active() => RunStatus::Stopped,
+            (_, status) => status,
+        };
+        // L'état final ou mis en pause figure toujours dans l'historique
+        if matches!(self.status, RunStatus::Paused | RunStatus::Stopped) {
+            self.record(self.active_time);
+        }
+        // Un changement d'état est toujours publié immédiatement
+        self.publish(snapshot_tx, true);
+    }
+
+    // Ajoute l'état courant à l'historique, une seule fois par itération
+    fn record(&mut self, elapsed: Duration) {
+        let iteration = self.runner.current_iteration();
+        if self.last_recorded_iteration == Some(iteration) {
+            return;
+        }
+        if let Some(entry) = self.runner.capture(elapsed) {
+            self.last_recorded_iteration = Some(iteration);
+            self.pending_history.push(entry);
+        }
+    }
+
+    fn publish(&mut self, snapshot_tx: &Sender<RunSnapshot>, force: bool) {
+        if !force && self.last_publish.elapsed() < PUBLISH_INTERVAL {
+            return;
+        }
+        self.last_publish = Instant::now();
+
+        // Le GUI a pu être fermé entre-temps, l'erreur d'envoi est ignorée
+        let _ = snapshot_tx.send(RunSnapshot {
+            run_id: self.run_id,
+            status: self.status,
+            best_path: self.runner.get_best_solution().map(|elite| elite.solution),
+            history: std::mem::take(&mut self.pending_history),
+            events: std::mem::take(&mut self.pending_events),
+            population: self.inspected.then(|| {
+                PopulationSnapshot::capture(&self.runner, &self.population, &self.fitnesses, self.instance.size())
+            }),
+        });
+    }
+}
+
+// --- 3. Pool de calcul ---
+
+/// Fait tourner les runs sur un thread dédié (et le pool rayon) hors du thread de rendu.
+/// Le GUI envoie des tâches et des commandes, et lit les snapshots publiés.
+pub struct SolverPool {
+    message_tx: Sender<PoolMessage>,
+    snapshot_rx: Receiver<RunSnapshot>,
+    inspected: Option<usize>,
+}
+
+impl SolverPool {
+    pub fn new() -> Self {
+        let (message_tx, message_rx) = mpsc::channel();
+        let (snapshot_tx, snapshot_rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("solver-pool".to_string())
+            .spawn(move || Self::run(message_rx, snapshot_tx))
+            .expect("Failed to spawn solver thread");
+
+        Self { message_tx, snapshot_rx, inspected: None }
+    }
+
+    pub fn add(&self, task: SolverTask) {
+        let _ = self.message_tx.send(PoolMessage::Add(Box::new(task)));
+    }
+
+    pub fn send(&self, run_id: usize, command: RunCommand) {
+        let _ = self.message_tx.send(PoolMessage::Command(run_id, command));
+    }
+
+    /// Désigne le run dont la population est publiée (au plus un, pour limiter les copies)
+    pub fn inspect(&mut self, run_id: Option<usize>) {
+        if self.inspected != run_id {
+            self.inspected = run_id;
+            let _ = self.message_tx.send(PoolMessage::Inspect(run_id));
+        }
+    }
+
+    /// Abandonne toutes les tâches en cours
+    pub fn clear(&self) {
+        let _ = self.message_tx.send(PoolMessage::Clear);
+    }
+
+    /// Snapshots publiés depuis le dernier appel
+    pub fn drain(&self) -> impl Iterator<Item = RunSnapshot> + '_ {
+        self.snapshot_rx.try_iter()
+    }
+
+    // Boucle du thread de calcul, qui s'arrête lorsque le SolverPool est détruit
+    fn run(message_rx: Receiver<PoolMessage>, snapshot_tx: Sender<RunSnapshot>) {
+        let mut tasks: Vec<SolverTask> = Vec::new();
+        let mut inspected = None;
+
+        loop {
+            // Sans run actif, on attend le prochain message plutôt que de boucler à vide
+            if !tasks.iter().any(|task| task.is_busy()) {
+                match message_rx.recv() {
+                    Ok(message) => Self::handle(message, &mut tasks, &mut inspected, &snapshot_tx),
+                    Err(_) => return,
+                }
+            }
+            loop {
+                match message_rx.try_recv() {
+                    Ok(message) => Self::handle(message, &mut tasks, &mut inspected, &snapshot_tx),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            }
+
+            // Le budget d'un tour est partagé entre les runs actifs, répartis sur les threads du pool rayon
+            let busy = tasks.iter().filter(|task| task.is_busy()).count().max(1);
+            let share = rayon::current_num_threads() as f64 / busy as f64;
+            let budget = ROUND_TIME_BUDGET.mul_f64(share.min(1.0));
+            tasks.par_iter_mut().for_each(|task| task.run_batch(budget, &snapshot_tx));
+            tasks.retain(|task| task.status.is_active());
+        }
+    }
+
+    fn handle(
+        message: PoolMessage,
+        tasks: &mut Vec<SolverTask>,
+        inspected: &mut Option<usize>,
+        snapshot_tx: &Sender<RunSnapshot>,
+    ) {
+        match message {
+            PoolMessage::Add(mut task) => {
+                // Le run affiché en détail a pu être désigné avant l'arrivée de sa tâche
+                task.inspected = Some(task.run_id) == *inspected;
+                tasks.push(*task);
+            }
+            PoolMessage::Command(run_id, command) => {
+                if let Some(task) = tasks.iter_mut().find(|task| task.run_id == run_id) {
+                    task.apply(command, snapshot_tx);
+                }
+            }
+            PoolMessage::Inspect(run_id) => {
+                *inspected = run_id;
+                for task in tasks.iter_mut() {
+                    task.inspected = Some(task.run_id) == run_id;
+                    // Un run en pause ne publie plus : sa population est envoyée tout de suite
+                    if task.inspected {
+                        task.publish(snapshot_tx, true);
+                    }
+                }
+            }
+            PoolMessage::Clear => tasks.clear(),
+        }
+    }
+}
+
+impl Default for SolverPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
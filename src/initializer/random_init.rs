@@ -1,6 +1,7 @@
 
 use super::Initializer;
 use crate::shared::{Instance, Solution};
+use crate::utils::rng::new_rng;
 
 use rand::seq::SliceRandom;
 
@@ -10,7 +11,7 @@ pub struct RandomInitializer;
 impl Initializer for RandomInitializer {
     fn initialize(&mut self, instance: &Instance) -> Solution {
         let node_number = instance.size();
-        let mut rng = rand::rng();
+        let mut rng = new_rng();
         let mut solution: Solution = (0..node_number as u32).collect();
         solution.shuffle(&mut rng);
         solution
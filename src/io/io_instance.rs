@@ -1,56 +1,325 @@
 use std::fs;
 use std::io;
-use std::io::BufRead;
+use std::str::FromStr;
 
 use ndarray::Array2;
 
-use crate::shared::{Instance, GraphInstance, Node, Window};
+use crate::shared::{Instance, GraphInstance, Node, TimeWindows, Window, WindowKind, WindowPolicy};
+
+// Power iterations used to lay out instances given only by a distance matrix
+const LAYOUT_ITERATIONS: usize = 200;
+
+// Keyword introducing the priority weights and window types of the Dumas and TSPLIB formats
+const WINDOW_POLICY_SECTION: &str = "WINDOW_POLICY_SECTION";
 
 struct Position {
     x: f32,
     y: f32,
 }
 
+/// Instance file formats understood by `load_instance`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InstanceFormat {
+    /// Solomon-like columns (CUST NO., XCOORD., YCOORD., DEMAND, READY TIME, DUE DATE, SERVICE TIME)
+    /// terminated by a `999` line, with truncated Euclidean distances. Three optional columns may follow:
+    /// PRIORITY (weight, 1 by default), WINDOW TYPE (hard, linear or quadratic) and EARLY RATE
+    /// (cost per time unit of waiting, 0 by default). A customer with several time windows is listed
+    /// on consecutive rows with the same CUST NO., one window per row, the other columns unchanged
+    Solomon,
+    /// Same columns, with service times: the service time of a node is added to every trip leaving it
+    SolomonPotvinBengio,
+    /// Dumas, Gendreau, Ohlmann-Thomas and da Silva-Urrutia benchmarks: the number of nodes,
+    /// the full distance matrix, then one `ready due` line per node. An optional `WINDOW_POLICY_SECTION`
+    /// line may follow, then one `priority [type [early_rate]]` line per node, as the Solomon optional columns
+    Dumas,
+    /// TSPLIB `.tsp` file with an `EDGE_WEIGHT_SECTION` (or `NODE_COORD_SECTION`),
+    /// an optional `TIME_WINDOW_SECTION` (`id start end`, with more pairs or lines for several windows)
+    /// and an optional `WINDOW_POLICY_SECTION` (`id priority [type [early_rate]]`, unlisted nodes keep the defaults)
+    Tsplib,
+}
 
+impl InstanceFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InstanceFormat::Solomon => "Solomon",
+            InstanceFormat::SolomonPotvinBengio => "SolomonPotvinBengio",
+            InstanceFormat::Dumas => "Dumas",
+            InstanceFormat::Tsplib => "TSPLIB",
+        }
+    }
 
-fn calculate_distance_matrix(positions: &Vec<Position>) -> Array2<f32> {
-    let node_number = positions.len();
-    let mut distance_matrix = vec![vec![0.0; node_number]; node_number];
+    /// Guess the format of an instance file from its content
+    pub fn detect(content: &str) -> io::Result<InstanceFormat> {
+        let is_tsplib = content.lines().map(str::trim).any(|line| {
+            line.starts_with("NAME") || line.starts_with("DIMENSION") || line == "EDGE_WEIGHT_SECTION"
+        });
+        if is_tsplib {
+            return Ok(InstanceFormat::Tsplib);
+        }
+
+        // A node count followed by exactly a matrix and a pair of bounds per node
+        let tokens = numeric_tokens(content);
+        if let Some(&first) = tokens.first()
+            && first.fract() == 0.0
+            && first >= 1.0
+        {
+            let size = first as usize;
+            if tokens.len() == 1 + size * size + 2 * size {
+                return Ok(InstanceFormat::Dumas);
+            }
+        }
+
+        let rows = solomon_rows(content)?;
+        if rows.is_empty() {
+            return Err(invalid_data("Unrecognized instance format".to_string()));
+        }
+        if rows.iter().any(|row| row.service > 0.0) {
+            Ok(InstanceFormat::SolomonPotvinBengio)
+        } else {
+            Ok(InstanceFormat::Solomon)
+        }
+    }
+}
+
+// Text forms: "solomon", "spb", "dumas", "tsplib"
+impl FromStr for InstanceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "solomon" => Ok(InstanceFormat::Solomon),
+            "spb" | "solomonpotvinbengio" => Ok(InstanceFormat::SolomonPotvinBengio),
+            "dumas" | "gendreau" | "ohlmann" | "dasilva" => Ok(InstanceFormat::Dumas),
+            "tsplib" => Ok(InstanceFormat::Tsplib),
+            _ => Err(format!("Unknown instance format '{}' (expected solomon, spb, dumas or tsplib)", s)),
+        }
+    }
+}
+
+/// Rounding of distances computed from coordinates
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rounding {
+    Floor,
+    /// Nearest integer (TSPLIB `nint`)
+    Round,
+    Ceil,
+    Exact,
+    /// Rounded to a multiple of 1/factor, i.e. an integer once scaled by `factor`
+    Scaled(f32),
+}
+
+impl Rounding {
+    pub fn apply(&self, distance: f32) -> f32 {
+        match self {
+            Rounding::Floor => distance.floor(),
+            Rounding::Round => distance.round(),
+            Rounding::Ceil => distance.ceil(),
+            Rounding::Exact => distance,
+            Rounding::Scaled(factor) => (distance * factor).round() / factor,
+        }
+    }
+}
+
+// Text forms: "floor", "round", "ceil", "exact", "scaled:FACTOR"
+impl FromStr for Rounding {
+    type Err = String;
 
-    for i in 0..positions.len() {
-        for j in 0..positions.len() {
-            let node1 = &positions[i];
-            let node2 = &positions[j];
-            let dist = ((node1.x - node2.x).powi(2) + (node1.y - node2.y).powi(2)).sqrt().floor();
-            distance_matrix[i][j] = dist;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "floor" => Ok(Rounding::Floor),
+            None if s == "round" => Ok(Rounding::Round),
+            None if s == "ceil" => Ok(Rounding::Ceil),
+            None if s == "exact" => Ok(Rounding::Exact),
+            Some(("scaled", factor)) => match factor.parse::<f32>() {
+                Ok(factor) if factor > 0.0 => Ok(Rounding::Scaled(factor)),
+                _ => Err(format!("Invalid scale factor '{}'", factor)),
+            },
+            _ => Err(format!("Unknown rounding '{}' (expected floor, round, ceil, exact or scaled:FACTOR)", s)),
         }
     }
-    // relax distances through intermediate nodes to match the Python reference implementation
+}
+
+/// How an instance file is turned into an `Instance`. Unset fields follow the convention of the format.
+#[derive(Clone, Default)]
+pub struct LoadOptions {
+    pub format: Option<InstanceFormat>,
+    pub rounding: Option<Rounding>,
+    /// Shorten every distance and travel time to its shortest path through other nodes (Floyd-Warshall)
+    pub relax_triangle: Option<bool>,
+    /// Matrix file (CSV or dense text) replacing the distances, and the travel times unless `time_matrix` is set
+    pub distance_matrix: Option<String>,
+    /// Matrix file replacing the travel times only
+    pub time_matrix: Option<String>,
+}
+
+// Content of an instance file, before options are applied
+struct ParsedInstance {
+    windows: Vec<TimeWindows>,
+    positions: Option<Vec<Position>>,
+    distance_matrix: Array2<f32>,
+    // None when travel times equal distances
+    time_matrix: Option<Array2<f32>>,
+    // Convention of the format when the options do not say otherwise
+    relax_triangle: bool,
+    // Priority weights and window policies, when the format gives them
+    weights: Option<Vec<f32>>,
+    policies: Option<Vec<WindowPolicy>>,
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Every whitespace-separated number of the file, comment lines (starting with ! or #) excluded
+fn numeric_tokens(content: &str) -> Vec<f32> {
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('!') && !line.starts_with('#'))
+        .flat_map(str::split_whitespace)
+        .map_while(|token| token.parse::<f32>().ok())
+        .collect()
+}
+
+fn euclidean_matrix(positions: &[Position], rounding: Rounding) -> Array2<f32> {
+    let node_number = positions.len();
+    Array2::from_shape_fn((node_number, node_number), |(i, j)| {
+        let (node1, node2) = (&positions[i], &positions[j]);
+        rounding.apply(((node1.x - node2.x).powi(2) + (node1.y - node2.y).powi(2)).sqrt())
+    })
+}
+
+// relax distances through intermediate nodes (the Python reference implementation does it on Solomon files)
+fn relax_triangle(matrix: &mut Array2<f32>) {
+    let node_number = matrix.nrows();
     for k in 0..node_number {
         for i in 0..node_number {
             for j in 0..node_number {
-                let current = distance_matrix[i][j];
-                let via_k = distance_matrix[i][k] + distance_matrix[k][j];
-                if via_k <  current {
-                    distance_matrix[i][j] = via_k;
+                let via_k = matrix[[i, k]] + matrix[[k, j]];
+                if via_k < matrix[[i, j]] {
+                    matrix[[i, j]] = via_k;
                 }
             }
         }
     }
-    Array2::from_shape_vec((node_number, node_number), distance_matrix.into_iter().flatten().collect()).unwrap()
 }
 
+/// Read a square matrix from a CSV file (`,` or `;` separators) or a dense text file
+/// (whitespace separators). A first line holding only the number of rows is skipped.
+pub fn load_matrix(path: &str) -> io::Result<Array2<f32>> {
+    let content = fs::read_to_string(path)?;
+    let mut rows: Vec<Vec<f32>> = Vec::new();
+    for (line_idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+        let row = line.split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.parse::<f32>()
+                .map_err(|_| invalid_data(format!("Invalid number '{}' at line {} of {}", token, line_idx + 1, path))))
+            .collect::<io::Result<Vec<f32>>>()?;
+        rows.push(row);
+    }
+    if rows.len() > 1 && rows[0].len() == 1 && rows[0][0] == (rows.len() - 1) as f32 {
+        rows.remove(0);
+    }
 
-pub fn load_instance(path: &str) -> io::Result<(Instance, GraphInstance)> {
-    let file = fs::File::open(path)?;
-    let reader = io::BufReader::new(file);
+    let size = rows.len();
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != size) {
+        return Err(invalid_data(format!(
+            "{} is not a square matrix: row {} has {} values, {} expected", path, i + 1, row.len(), size
+        )));
+    }
+    Array2::from_shape_vec((size, size), rows.into_iter().flatten().collect())
+        .map_err(|e| invalid_data(e.to_string()))
+}
 
-    let mut windows = Vec::new();
-    let mut positions = Vec::new();
-    let mut graph = Vec::new();
+/// Two-dimensional layout approximating the distances (classical multidimensional scaling),
+/// used to draw instances that come without coordinates
+fn layout_from_distances(distance_matrix: &Array2<f32>) -> Vec<Position> {
+    let size = distance_matrix.nrows();
+    if size == 0 {
+        return Vec::new();
+    }
+
+    // Double-centered matrix of squared (symmetrized) distances
+    let mut gram = Array2::<f64>::zeros((size, size));
+    for i in 0..size {
+        for j in 0..size {
+            let d = (distance_matrix[[i, j]] as f64 + distance_matrix[[j, i]] as f64) / 2.0;
+            gram[[i, j]] = -0.5 * d * d;
+        }
+    }
+    let row_means: Vec<f64> = (0..size).map(|i| gram.row(i).sum() / size as f64).collect();
+    let mean = row_means.iter().sum::<f64>() / size as f64;
+    for i in 0..size {
+        for j in 0..size {
+            gram[[i, j]] += mean - row_means[i] - row_means[j];
+        }
+    }
+
+    // The two leading eigenvectors, by power iteration with deflation
+    let mut axes = Vec::with_capacity(2);
+    for axis in 0..2 {
+        let mut vector: Vec<f64> = (0..size).map(|i| ((i * (axis + 2)) % 7) as f64 - 3.0 + 0.1 * i as f64).collect();
+        let mut eigenvalue = 0.0;
+        for _ in 0..LAYOUT_ITERATIONS {
+            let next: Vec<f64> = (0..size).map(|i| (0..size).map(|j| gram[[i, j]] * vector[j]).sum()).collect();
+            let norm = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if norm <= f64::EPSILON {
+                break;
+            }
+            eigenvalue = norm;
+            vector = next.into_iter().map(|v| v / norm).collect();
+        }
+        for i in 0..size {
+            for j in 0..size {
+                gram[[i, j]] -= eigenvalue * vector[i] * vector[j];
+            }
+        }
+        let scale = eigenvalue.max(0.0).sqrt();
+        axes.push(vector.into_iter().map(|v| (v * scale) as f32).collect::<Vec<f32>>());
+    }
+    (0..size).map(|i| Position { x: axes[0][i], y: axes[1][i] }).collect()
+}
+
+
+struct SolomonRow {
+    id: String,
+    x: f32,
+    y: f32,
+    windows: Vec<Window>,
+    service: f32,
+    priority: f32,
+    policy: WindowPolicy,
+}
 
-    for (line_idx, line_result) in reader.lines().enumerate() {
-        let line = line_result?;  // si erreur lecture → retourne Err
+// Optional `PRIORITY [WINDOW TYPE [EARLY RATE]]` columns shared by every format: priority weight (1 by default),
+// window type (hard, linear or quadratic, hard by default) and early-arrival cost rate (0 by default)
+fn window_policy<'a>(parts: &mut impl Iterator<Item = &'a str>, line_idx: usize) -> io::Result<(f32, WindowPolicy)> {
+    let priority = match parts.next() {
+        Some(token) => token.parse::<f32>()
+            .map_err(|_| invalid_data(format!("Invalid priority weight at line {}", line_idx)))?,
+        None => 1.0,
+    };
+    let kind = match parts.next() {
+        Some(token) => token.parse::<WindowKind>()
+            .map_err(|e| invalid_data(format!("{} at line {}", e, line_idx)))?,
+        None => WindowKind::Hard,
+    };
+    let early_rate = match parts.next() {
+        Some(token) => token.parse::<f32>()
+            .map_err(|_| invalid_data(format!("Invalid early-arrival rate at line {}", line_idx)))?,
+        None => 0.0,
+    };
+    Ok((priority, WindowPolicy { kind, early_rate }))
+}
+
+// Rows of a Solomon-like file, up to the `999` sentinel
+fn solomon_rows(content: &str) -> io::Result<Vec<SolomonRow>> {
+    let mut rows: Vec<SolomonRow> = Vec::new();
+
+    for (line_idx, line) in content.lines().enumerate() {
         let line = line.trim();
 
         // Skip empty lines and comment lines (starting with !)
@@ -60,57 +329,593 @@ pub fn load_instance(path: &str) -> io::Result<(Instance, GraphInstance)> {
 
         let mut parts = line.split_whitespace();
 
-        let _id = parts.next();
+        let id = parts.next().unwrap_or_default();
 
-        if _id == Some("999") {
+        if id == "999" {
             break; // End of instance data
         }
 
-        let x = parts
-            .next()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Missing x at line {}", line_idx)))?
-            .parse::<f32>()
-            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Invalid float x at line {}", line_idx)))?;
-
-        let y = parts
-            .next()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Missing y at line {}", line_idx)))?
-            .parse::<f32>()
-            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Invalid float y at line {}", line_idx)))?;
-        
-        let _dmd = parts.next();
-
-        let wstart = parts
-            .next()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Missing wstart at line {}", line_idx)))?
-            .parse::<f32>()
-            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Invalid float wstart at line {}", line_idx)))?;
-
-        let wend = parts
-            .next()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Missing wend at line {}", line_idx)))?
-            .parse::<f32>()
-            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData,
-                format!("Invalid float wend at line {}", line_idx)))?;
-
-        windows.push(Window { wstart, wend });
-        positions.push(Position { x, y });
-        graph.push(Node { x, y, wstart, wend });
-    }
-
-    let distance_matrix = calculate_distance_matrix(&positions);
-    Ok((
-        Instance {
-            windows,
-            distance_matrix,
-        },
-        GraphInstance { graph }
-    ))
-}
\ No newline at end of file
+        let mut field = |name: &str| -> io::Result<f32> {
+            parts
+                .next()
+                .ok_or_else(|| invalid_data(format!("Missing {} at line {}", name, line_idx)))?
+                .parse::<f32>()
+                .map_err(|_| invalid_data(format!("Invalid float {} at line {}", name, line_idx)))
+        };
+
+        let x = field("x")?;
+        let y = field("y")?;
+        let _dmd = field("demand")?;
+        let wstart = field("wstart")?;
+        let wend = field("wend")?;
+
+        // The service time column is optional, but a present value must parse
+        let service = match parts.next() {
+            Some(token) => token.parse::<f32>()
+                .map_err(|_| invalid_data(format!("Invalid float service time at line {}", line_idx)))?,
+            None => 0.0,
+        };
+
+        let (priority, policy) = window_policy(&mut parts, line_idx)?;
+
+        // A customer repeated on the next row gets one more time window, the other columns must not change
+        if let Some(previous) = rows.last_mut()
+            && previous.id == id
+        {
+            if (previous.x, previous.y, previous.service, previous.priority, previous.policy) != (x, y, service, priority, policy) {
+                return Err(invalid_data(format!(
+                    "Customer {} at line {} repeats its row with different values: only the time window may change",
+                    id, line_idx
+                )));
+            }
+            previous.windows.push(Window { wstart, wend });
+            continue;
+        }
+
+        rows.push(SolomonRow {
+            id: id.to_string(),
+            x,
+            y,
+            windows: vec![Window { wstart, wend }],
+            service,
+            priority,
+            policy,
+        });
+    }
+    Ok(rows)
+}
+
+fn parse_solomon(content: &str, with_service_times: bool, rounding: Option<Rounding>) -> io::Result<ParsedInstance> {
+    let rows = solomon_rows(content)?;
+    let windows: Vec<TimeWindows> = rows.iter().map(|r| TimeWindows::new(r.windows.clone())).collect();
+    let positions: Vec<Position> = rows.iter().map(|r| Position { x: r.x, y: r.y }).collect();
+    let weights = Some(rows.iter().map(|r| r.priority).collect());
+    let policies = Some(rows.iter().map(|r| r.policy).collect());
+
+    if with_service_times {
+        // Potvin-Bengio convention: exact Euclidean distances, and the service time at the origin
+        // added to the travel time
+        let distance_matrix = euclidean_matrix(&positions, rounding.unwrap_or(Rounding::Exact));
+        let time_matrix = Array2::from_shape_fn(distance_matrix.dim(), |(i, j)| {
+            if i == j { 0.0 } else { distance_matrix[[i, j]] + rows[i].service }
+        });
+        Ok(ParsedInstance { windows, positions: Some(positions), distance_matrix, time_matrix: Some(time_matrix), relax_triangle: false, weights, policies })
+    } else {
+        let distance_matrix = euclidean_matrix(&positions, rounding.unwrap_or(Rounding::Floor));
+        Ok(ParsedInstance { windows, positions: Some(positions), distance_matrix, time_matrix: None, relax_triangle: true, weights, policies })
+    }
+}
+
+fn parse_dumas(content: &str) -> io::Result<ParsedInstance> {
+    let tokens = numeric_tokens(content);
+    let size = tokens.first().copied()
+        .filter(|n| n.fract() == 0.0 && *n >= 1.0)
+        .ok_or_else(|| invalid_data("Missing node count on the first line".to_string()))? as usize;
+    let expected = 1 + size * size + 2 * size;
+    if tokens.len() < expected {
+        return Err(invalid_data(format!(
+            "Expected a {0}x{0} distance matrix and {0} time windows ({1} numbers), found {2} numbers",
+            size, expected - 1, tokens.len() - 1
+        )));
+    }
+
+    let distance_matrix = Array2::from_shape_vec((size, size), tokens[1..1 + size * size].to_vec())
+        .map_err(|e| invalid_data(e.to_string()))?;
+    let windows: Vec<TimeWindows> = tokens[1 + size * size..expected]
+        .chunks_exact(2)
+        .map(|w| TimeWindows::single(Window { wstart: w[0], wend: w[1] }))
+        .collect();
+
+    // The keyword ends the numeric tokens, the policies are read line by line after it
+    let (mut weights, mut policies) = (None, None);
+    if let Some(start) = content.lines().position(|line| line.trim() == WINDOW_POLICY_SECTION) {
+        let mut node_weights = Vec::with_capacity(size);
+        let mut node_policies = Vec::with_capacity(size);
+        for (line_idx, line) in content.lines().enumerate().skip(start + 1) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+                continue;
+            }
+            let (priority, policy) = window_policy(&mut line.split_whitespace(), line_idx)?;
+            node_weights.push(priority);
+            node_policies.push(policy);
+        }
+        if node_weights.len() != size {
+            return Err(invalid_data(format!(
+                "{} must have one line per node: {} expected, found {}",
+                WINDOW_POLICY_SECTION, size, node_weights.len()
+            )));
+        }
+        weights = Some(node_weights);
+        policies = Some(node_policies);
+    }
+    Ok(ParsedInstance { windows, positions: None, distance_matrix, time_matrix: None, relax_triangle: false, weights, policies })
+}
+
+// Fills a square matrix from the values of a TSPLIB EDGE_WEIGHT_SECTION
+fn tsplib_matrix(values: &[f32], size: usize, format: &str) -> io::Result<Array2<f32>> {
+    let mut matrix = Array2::<f32>::zeros((size, size));
+    // (row, column) pairs in the order the values are listed
+    let cells: Vec<(usize, usize)> = match format {
+        "FULL_MATRIX" => (0..size).flat_map(|i| (0..size).map(move |j| (i, j))).collect(),
+        "UPPER_ROW" => (0..size).flat_map(|i| (i + 1..size).map(move |j| (i, j))).collect(),
+        "UPPER_DIAG_ROW" => (0..size).flat_map(|i| (i..size).map(move |j| (i, j))).collect(),
+        "LOWER_ROW" => (0..size).flat_map(|i| (0..i).map(move |j| (i, j))).collect(),
+        "LOWER_DIAG_ROW" => (0..size).flat_map(|i| (0..=i).map(move |j| (i, j))).collect(),
+        _ => return Err(invalid_data(format!("Unsupported EDGE_WEIGHT_FORMAT '{}'", format))),
+    };
+    if values.len() < cells.len() {
+        return Err(invalid_data(format!(
+            "EDGE_WEIGHT_SECTION has {} values, {} expected for {}",
+            values.len(), cells.len(), format
+        )));
+    }
+    for (&(i, j), &value) in cells.iter().zip(values) {
+        matrix[[i, j]] = value;
+        if format != "FULL_MATRIX" {
+            matrix[[j, i]] = value;
+        }
+    }
+    Ok(matrix)
+}
+
+fn parse_tsplib(content: &str, rounding: Option<Rounding>) -> io::Result<ParsedInstance> {
+    let mut size = None;
+    let mut weight_type = String::from("EXPLICIT");
+    let mut weight_format = String::from("FULL_MATRIX");
+    let mut section = "";
+    let mut weights = Vec::new();
+    let mut coordinates: Vec<(usize, Position)> = Vec::new();
+    let mut time_windows: Vec<(usize, Window)> = Vec::new();
+    let mut window_policies: Vec<(usize, f32, WindowPolicy)> = Vec::new();
+
+    for (line_idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "DIMENSION" => size = Some(value.parse::<usize>()
+                    .map_err(|_| invalid_data(format!("Invalid DIMENSION at line {}", line_idx)))?),
+                "EDGE_WEIGHT_TYPE" => weight_type = value.to_string(),
+                "EDGE_WEIGHT_FORMAT" => weight_format = value.to_string(),
+                _ => {}
+            }
+            section = "";
+            continue;
+        }
+        if line.chars().next().is_some_and(|c| c.is_ascii_alphabetic()) {
+            section = match line {
+                "EDGE_WEIGHT_SECTION" | "NODE_COORD_SECTION" | "DISPLAY_DATA_SECTION" | "TIME_WINDOW_SECTION"
+                | WINDOW_POLICY_SECTION => line,
+                "EOF" => break,
+                _ => "",
+            };
+            continue;
+        }
+        // The window type is a word, the line is not numeric
+        if section == WINDOW_POLICY_SECTION {
+            let mut parts = line.split_whitespace();
+            let id = parts.next().unwrap_or_default().parse::<usize>()
+                .map_err(|_| invalid_data(format!("Invalid node id at line {}", line_idx)))?;
+            let (priority, policy) = window_policy(&mut parts, line_idx)?;
+            window_policies.push((id, priority, policy));
+            continue;
+        }
+
+        let numbers: Vec<f32> = line.split_whitespace()
+            .map(|token| token.parse::<f32>()
+                .map_err(|_| invalid_data(format!("Invalid number '{}' at line {}", token, line_idx))))
+            .collect::<io::Result<_>>()?;
+        match (section, numbers.as_slice()) {
+            ("EDGE_WEIGHT_SECTION", _) => weights.extend(numbers),
+            ("NODE_COORD_SECTION" | "DISPLAY_DATA_SECTION", [id, x, y, ..]) => {
+                coordinates.push((*id as usize, Position { x: *x, y: *y }));
+            }
+            // One or more `start end` pairs per line, a node may also be listed on several lines
+            ("TIME_WINDOW_SECTION", [id, bounds @ ..]) if bounds.len() >= 2 => {
+                for pair in bounds.chunks_exact(2) {
+                    time_windows.push((*id as usize, Window { wstart: pair[0], wend: pair[1] }));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let size = size.ok_or_else(|| invalid_data("Missing DIMENSION".to_string()))?;
+    // Node ids are 1-based
+    let positions: Option<Vec<Position>> = (coordinates.len() == size).then(|| {
+        coordinates.sort_by_key(|(id, _)| *id);
+        coordinates.into_iter().map(|(_, p)| p).collect()
+    });
+
+    let distance_matrix = match weight_type.as_str() {
+        "EXPLICIT" => tsplib_matrix(&weights, size, &weight_format)?,
+        "EUC_2D" | "CEIL_2D" => {
+            let points = positions.as_ref()
+                .ok_or_else(|| invalid_data(format!("NODE_COORD_SECTION must list {} nodes", size)))?;
+            let default = if weight_type == "CEIL_2D" { Rounding::Ceil } else { Rounding::Round };
+            euclidean_matrix(points, rounding.unwrap_or(default))
+        }
+        other => return Err(invalid_data(format!("Unsupported EDGE_WEIGHT_TYPE '{}'", other))),
+    };
+
+    // Without time windows every node can be visited at any time before a tour could possibly end
+    let horizon: f32 = distance_matrix.rows().into_iter()
+        .map(|row| row.iter().copied().fold(0.0, f32::max))
+        .sum();
+    let mut slots: Vec<Vec<Window>> = vec![Vec::new(); size];
+    for (id, window) in time_windows {
+        slots.get_mut(id.wrapping_sub(1))
+            .ok_or_else(|| invalid_data(format!("Time window for unknown node {}", id)))?
+            .push(window);
+    }
+    let windows = slots.into_iter()
+        .map(|node_slots| {
+            if node_slots.is_empty() {
+                TimeWindows::single(Window { wstart: 0.0, wend: horizon })
+            } else {
+                TimeWindows::new(node_slots)
+            }
+        })
+        .collect();
+
+    let (mut weights, mut policies) = (None, None);
+    if !window_policies.is_empty() {
+        let mut node_weights = vec![1.0; size];
+        let mut node_policies = vec![WindowPolicy::default(); size];
+        for (id, priority, policy) in window_policies {
+            let node = id.wrapping_sub(1);
+            if node >= size {
+                return Err(invalid_data(format!("Window policy for unknown node {}", id)));
+            }
+            node_weights[node] = priority;
+            node_policies[node] = policy;
+        }
+        weights = Some(node_weights);
+        policies = Some(node_policies);
+    }
+
+    Ok(ParsedInstance { windows, positions, distance_matrix, time_matrix: None, relax_triangle: false, weights, policies })
+}
+
+/// Load an instance with the given options
+pub fn load_instance_with(path: &str, options: &LoadOptions) -> io::Result<(Instance, GraphInstance)> {
+    let content = fs::read_to_string(path)?;
+    let format = match options.format {
+        Some(format) => format,
+        None => InstanceFormat::detect(&content)?,
+    };
+    let mut parsed = match format {
+        InstanceFormat::Solomon => parse_solomon(&content, false, options.rounding)?,
+        InstanceFormat::SolomonPotvinBengio => parse_solomon(&content, true, options.rounding)?,
+        InstanceFormat::Dumas => parse_dumas(&content)?,
+        InstanceFormat::Tsplib => parse_tsplib(&content, options.rounding)?,
+    };
+    let size = parsed.windows.len();
+
+    // Matrices given separately replace those of the file; the format convention no longer applies
+    let read_matrix = |matrix_path: &str| -> io::Result<Array2<f32>> {
+        let matrix = load_matrix(matrix_path)?;
+        if matrix.nrows() != size {
+            return Err(invalid_data(format!(
+                "{} is a {}x{} matrix, the instance has {} nodes", matrix_path, matrix.nrows(), matrix.nrows(), size
+            )));
+        }
+        Ok(matrix)
+    };
+    if let Some(matrix_path) = &options.distance_matrix {
+        parsed.distance_matrix = read_matrix(matrix_path)?;
+        parsed.time_matrix = None;
+        parsed.relax_triangle = false;
+    }
+    if let Some(matrix_path) = &options.time_matrix {
+        parsed.time_matrix = Some(read_matrix(matrix_path)?);
+        parsed.relax_triangle = false;
+    }
+
+    if options.relax_triangle.unwrap_or(parsed.relax_triangle) {
+        relax_triangle(&mut parsed.distance_matrix);
+        if let Some(time_matrix) = &mut parsed.time_matrix {
+            relax_triangle(time_matrix);
+        }
+    }
+
+    let positions = parsed.positions.unwrap_or_else(|| layout_from_distances(&parsed.distance_matrix));
+    let graph = positions.iter().zip(&parsed.windows)
+        .map(|(p, w)| Node { x: p.x, y: p.y, windows: w.clone() })
+        .collect();
+    let mut instance = match parsed.time_matrix {
+        Some(time_matrix) => Instance::with_time_matrix(parsed.windows, parsed.distance_matrix, time_matrix),
+        None => Instance::new(parsed.windows, parsed.distance_matrix),
+    };
+    if let Some(weights) = parsed.weights {
+        instance.weights = weights;
+    }
+    if let Some(policies) = parsed.policies {
+        instance.policies = policies;
+    }
+    Ok((instance, GraphInstance { graph }))
+}
+
+/// Load an instance, detecting its format
+pub fn load_instance(path: &str) -> io::Result<(Instance, GraphInstance)> {
+    load_instance_with(path, &LoadOptions::default())
+}
+
+/// Write nodes in the Solomon-like format of the bundled instances (no demand nor service time),
+/// one row per time window, readable back by `load_instance`
+pub fn save_instance(path: &str, name: &str, nodes: &[Node]) -> io::Result<()> {
+    let mut content = format!("!! {}\n\n\n", name);
+    content.push_str("CUST NO.   XCOORD.   YCOORD.    DEMAND   READY TIME   DUE DATE   SERVICE TIME\n\n\n");
+    // One row per time window, repeating the customer number
+    for (i, node) in nodes.iter().enumerate() {
+        for window in node.windows.slots() {
+            content.push_str(&format!(
+                "{:5} {:10.2} {:10.2} {:10.2} {:10.2} {:10.2} {:10.2}\n",
+                i + 1, node.x, node.y, 0.0, window.wstart, window.wend, 0.0
+            ));
+        }
+    }
+    content.push_str(&format!("{:5} {:10.2} {:10.2} {:10.2} {:10.2} {:10.2} {:10.2}\n", 999, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0));
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLOMON: &str = "\
+!! test
+CUST NO.   XCOORD.   YCOORD.    DEMAND   READY TIME   DUE DATE   SERVICE TIME
+    1      0.00      0.00      0.00      0.00    100.00      0.00
+    2      3.00      4.00      0.00     10.00     20.00      0.00
+    3      6.00      8.00      0.00     30.00     40.00      0.00
+  999      0.00      0.00      0.00      0.00      0.00      0.00
+";
+
+    const DUMAS: &str = "\
+3
+0 5 10
+5 0 5
+10 5 0
+0 100
+10 20
+30 40
+";
+
+    const TSPLIB: &str = "\
+NAME: test
+TYPE: TSPTW
+DIMENSION: 3
+EDGE_WEIGHT_TYPE: EXPLICIT
+EDGE_WEIGHT_FORMAT: UPPER_ROW
+EDGE_WEIGHT_SECTION
+5 10
+5
+TIME_WINDOW_SECTION
+1 0 100
+2 10 20 30 40
+EOF
+";
+
+    fn window(wstart: f32, wend: f32) -> Window {
+        Window { wstart, wend }
+    }
+
+    #[test]
+    fn detects_formats() {
+        assert_eq!(InstanceFormat::detect(SOLOMON).unwrap(), InstanceFormat::Solomon);
+        let with_service = SOLOMON.replace("40.00      0.00", "40.00      5.00");
+        assert_eq!(InstanceFormat::detect(&with_service).unwrap(), InstanceFormat::SolomonPotvinBengio);
+        assert_eq!(InstanceFormat::detect(DUMAS).unwrap(), InstanceFormat::Dumas);
+        assert_eq!(InstanceFormat::detect(TSPLIB).unwrap(), InstanceFormat::Tsplib);
+        assert!(InstanceFormat::detect("").is_err());
+        assert!(InstanceFormat::detect("3\n0 5\n").is_err());
+    }
+
+    #[test]
+    fn parses_format_names() {
+        assert_eq!("SPB".parse(), Ok(InstanceFormat::SolomonPotvinBengio));
+        assert_eq!("gendreau".parse(), Ok(InstanceFormat::Dumas));
+        assert!("csv".parse::<InstanceFormat>().is_err());
+        assert_eq!("scaled:10".parse(), Ok(Rounding::Scaled(10.0)));
+        assert!("scaled:0".parse::<Rounding>().is_err());
+        assert_eq!(Rounding::Scaled(10.0).apply(1.234), 1.2);
+    }
+
+    #[test]
+    fn parses_solomon() {
+        let parsed = parse_solomon(SOLOMON, false, None).unwrap();
+        assert_eq!(parsed.windows.len(), 3);
+        assert_eq!(parsed.windows[1].slots(), [window(10.0, 20.0)]);
+        assert_eq!(parsed.distance_matrix[[0, 1]], 5.0);
+        assert_eq!(parsed.distance_matrix[[0, 2]], 10.0);
+        assert!(parsed.time_matrix.is_none());
+        assert!(parsed.relax_triangle);
+    }
+
+    #[test]
+    fn adds_service_times_to_travel_times() {
+        let content = SOLOMON.replace("20.00      0.00", "20.00      7.00");
+        let parsed = parse_solomon(&content, true, None).unwrap();
+        let time_matrix = parsed.time_matrix.unwrap();
+        assert_eq!(time_matrix[[1, 0]], 5.0 + 7.0);
+        assert_eq!(time_matrix[[0, 1]], 5.0);
+        assert_eq!(time_matrix[[1, 1]], 0.0);
+        assert!(!parsed.relax_triangle);
+    }
+
+    #[test]
+    fn rejects_malformed_solomon_rows() {
+        let missing_column = SOLOMON.replace("30.00     40.00      0.00", "30.00");
+        assert!(solomon_rows(&missing_column).is_err());
+        let bad_number = SOLOMON.replace("6.00", "six");
+        assert!(solomon_rows(&bad_number).is_err());
+        let bad_service = SOLOMON.replace("20.00      0.00", "20.00      abc");
+        assert!(solomon_rows(&bad_service).is_err());
+        let no_service = SOLOMON.replace("20.00      0.00", "20.00");
+        assert_eq!(solomon_rows(&no_service).unwrap()[1].service, 0.0);
+    }
+
+    #[test]
+    fn parses_dumas() {
+        let parsed = parse_dumas(DUMAS).unwrap();
+        assert_eq!(parsed.distance_matrix[[0, 2]], 10.0);
+        assert_eq!(parsed.distance_matrix[[2, 1]], 5.0);
+        assert_eq!(parsed.windows[2].slots(), [window(30.0, 40.0)]);
+        assert!(parsed.positions.is_none());
+        assert!(parsed.weights.is_none() && parsed.policies.is_none());
+
+        let truncated = DUMAS.trim_end().rsplit_once('\n').unwrap().0;
+        assert!(parse_dumas(truncated).is_err());
+    }
+
+    #[test]
+    fn parses_explicit_tsplib() {
+        let parsed = parse_tsplib(TSPLIB, None).unwrap();
+        assert_eq!(parsed.distance_matrix[[0, 1]], 5.0);
+        assert_eq!(parsed.distance_matrix[[2, 0]], 10.0);
+        assert_eq!(parsed.windows[1].slots(), [window(10.0, 20.0), window(30.0, 40.0)]);
+        // Node 3 has no window: open until the sum of the row maxima
+        assert_eq!(parsed.windows[2].slots(), [window(0.0, 25.0)]);
+    }
+
+    #[test]
+    fn parses_euclidean_tsplib() {
+        let content = "\
+DIMENSION: 3
+EDGE_WEIGHT_TYPE: EUC_2D
+NODE_COORD_SECTION
+3 0 1.5
+1 0 0
+2 3 4
+EOF
+";
+        let parsed = parse_tsplib(content, None).unwrap();
+        assert_eq!(parsed.distance_matrix[[0, 1]], 5.0);
+        assert_eq!(parsed.distance_matrix[[0, 2]], 2.0);
+        assert_eq!(parsed.distance_matrix[[1, 2]], 4.0);
+        assert_eq!(parse_tsplib(content, Some(Rounding::Floor)).unwrap().distance_matrix[[0, 2]], 1.0);
+
+        let missing_node = content.replace("2 3 4\n", "");
+        assert!(parse_tsplib(&missing_node, None).is_err());
+        assert!(parse_tsplib("EDGE_WEIGHT_SECTION\n1 2 3\n", None).is_err());
+    }
+
+    #[test]
+    fn reads_solomon_policy_columns() {
+        let content = SOLOMON.replace("20.00      0.00", "20.00      0.00   2   linear   0.5");
+        let parsed = parse_solomon(&content, false, None).unwrap();
+        assert_eq!(parsed.weights.unwrap(), [1.0, 2.0, 1.0]);
+        let policies = parsed.policies.unwrap();
+        assert_eq!(policies[1], WindowPolicy { kind: WindowKind::SoftLinear, early_rate: 0.5 });
+        assert_eq!(policies[2], WindowPolicy::default());
+
+        let bad_kind = SOLOMON.replace("20.00      0.00", "20.00      0.00   2   strict");
+        assert!(solomon_rows(&bad_kind).is_err());
+    }
+
+    #[test]
+    fn reads_dumas_policy_section() {
+        let content = format!("{}{}\n1\n2 linear 0.5\n3 quadratic\n", DUMAS, WINDOW_POLICY_SECTION);
+        assert_eq!(InstanceFormat::detect(&content).unwrap(), InstanceFormat::Dumas);
+        let parsed = parse_dumas(&content).unwrap();
+        assert_eq!(parsed.weights.unwrap(), [1.0, 2.0, 3.0]);
+        assert_eq!(parsed.policies.unwrap(), [
+            WindowPolicy::default(),
+            WindowPolicy { kind: WindowKind::SoftLinear, early_rate: 0.5 },
+            WindowPolicy { kind: WindowKind::SoftQuadratic, early_rate: 0.0 },
+        ]);
+
+        // One line per node, no more, no less
+        let missing_line = format!("{}{}\n1\n2 linear 0.5\n", DUMAS, WINDOW_POLICY_SECTION);
+        assert!(parse_dumas(&missing_line).is_err());
+        let bad_kind = format!("{}{}\n1\n2 strict\n3\n", DUMAS, WINDOW_POLICY_SECTION);
+        assert!(parse_dumas(&bad_kind).is_err());
+    }
+
+    #[test]
+    fn reads_tsplib_policy_section() {
+        let section = format!("{}\n2 3 q 1\n", WINDOW_POLICY_SECTION);
+        let content = TSPLIB.replace("EOF\n", &format!("{}EOF\n", section));
+        let parsed = parse_tsplib(&content, None).unwrap();
+        // Unlisted nodes keep the defaults
+        assert_eq!(parsed.weights.unwrap(), [1.0, 3.0, 1.0]);
+        assert_eq!(parsed.policies.unwrap()[1], WindowPolicy { kind: WindowKind::SoftQuadratic, early_rate: 1.0 });
+        assert!(parse_tsplib(TSPLIB, None).unwrap().policies.is_none());
+
+        let unknown_node = TSPLIB.replace("EOF\n", &format!("{}\n4 2\nEOF\n", WINDOW_POLICY_SECTION));
+        assert!(parse_tsplib(&unknown_node, None).is_err());
+    }
+
+    #[test]
+    fn repeated_solomon_rows_add_windows() {
+        let extra = "    2      3.00      4.00      0.00     50.00     60.00      0.00\n";
+        let content = SOLOMON.replace("    3 ", &format!("{}    3 ", extra));
+        let parsed = parse_solomon(&content, false, None).unwrap();
+        assert_eq!(parsed.windows.len(), 3);
+        assert_eq!(parsed.windows[1].slots(), [window(10.0, 20.0), window(50.0, 60.0)]);
+
+        // Overlapping windows are merged
+        let overlapping = extra.replace("50.00     60.00", "15.00     30.00");
+        let content = SOLOMON.replace("    3 ", &format!("{}    3 ", overlapping));
+        let parsed = parse_solomon(&content, false, None).unwrap();
+        assert_eq!(parsed.windows[1].slots(), [window(10.0, 30.0)]);
+    }
+
+    #[test]
+    fn rejects_repeated_rows_with_other_changes() {
+        let moved = "    2      3.00      5.00      0.00     50.00     60.00      0.00\n";
+        let content = SOLOMON.replace("    3 ", &format!("{}    3 ", moved));
+        assert!(solomon_rows(&content).is_err());
+
+        let reweighted = "    2      3.00      4.00      0.00     50.00     60.00      0.00   2\n";
+        let content = SOLOMON.replace("    3 ", &format!("{}    3 ", reweighted));
+        assert!(solomon_rows(&content).is_err());
+    }
+
+    #[test]
+    fn saved_instances_load_back() {
+        let nodes = vec![
+            Node { x: 0.0, y: 0.0, windows: TimeWindows::single(window(0.0, 100.0)) },
+            Node { x: 3.0, y: 4.0, windows: TimeWindows::new(vec![window(10.0, 20.0), window(50.0, 60.0)]) },
+            Node { x: 6.0, y: 8.0, windows: TimeWindows::single(window(30.0, 40.0)) },
+        ];
+        let path = std::env::temp_dir().join(format!("mh-tsptw-roundtrip-{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_instance(path, "roundtrip", &nodes).unwrap();
+        let loaded = load_instance(path);
+        fs::remove_file(path).unwrap();
+
+        let (instance, graph) = loaded.unwrap();
+        assert_eq!(instance.size(), 3);
+        for (node, loaded) in nodes.iter().zip(&graph.graph) {
+            assert_eq!((node.x, node.y), (loaded.x, loaded.y));
+            assert_eq!(node.windows, loaded.windows);
+        }
+        assert_eq!(instance.distance_matrix[[1, 2]], 5.0);
+    }
+}
@@ -34,19 +34,25 @@ pub fn load_solution(path: &String) -> io::Result<BestSolution> {
     let mut sol_val_line = String::new();
     reader.read_line(&mut sol_val_line)?;
 
-    let sol_val: u32 = sol_val_line
-        .trim()
-        .parse()
-        .map_err(|e| {
-            io::Error::new(
-                io::ErrorKind::InvalidData,
-                format!("Failed to parse solution value: {}", e),
-                )
-            })?;
+    // The value may be written as a float (e.g. "268.0") or left blank
+    let sol_val_line = sol_val_line.trim();
+    let sol_val = if sol_val_line.is_empty() {
+        None
+    } else {
+        let value: f32 = sol_val_line
+            .parse()
+            .map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Failed to parse solution value: {}", e),
+                    )
+                })?;
+        Some(value.round() as u32)
+    };
 
     Ok(BestSolution {
         path: path,
-        duree: Some(sol_val),
+        duree: sol_val,
     })
 }
 
@@ -73,3 +79,32 @@ pub fn save_solution(path: &String, sol_list: &[u32], score: Option<u32>) -> io:
     }
     Ok(())
 }
+
+/// Check that a solution is a permutation of the `size` nodes of the instance.
+/// Returns the list of problems found (empty if the solution is valid).
+pub fn validate_solution(sol_list: &[u32], size: usize) -> Vec<String> {
+    let mut issues = Vec::new();
+    if sol_list.len() != size {
+        issues.push(format!("Solution visits {} nodes, instance has {}", sol_list.len(), size));
+    }
+
+    let mut seen = vec![false; size];
+    for &node in sol_list {
+        match seen.get_mut(node as usize) {
+            None => issues.push(format!("Node {} is out of range", node)),
+            Some(true) => issues.push(format!("Node {} is visited more than once", node)),
+            Some(visited) => *visited = true,
+        }
+    }
+    let missing: Vec<usize> = seen.iter().enumerate()
+        .filter(|(_, visited)| !**visited)
+        .map(|(node, _)| node)
+        .collect();
+    if !missing.is_empty() {
+        // Only the first missing nodes are listed
+        let listed: Vec<String> = missing.iter().take(20).map(|node| node.to_string()).collect();
+        let more = if missing.len() > listed.len() { ", ..." } else { "" };
+        issues.push(format!("{} nodes never visited: {}{}", missing.len(), listed.join(", "), more));
+    }
+    issues
+}
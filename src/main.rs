@@ -1,11 +1,17 @@
-use clap::Parser;
-use mh_tsptw::algorithms::{CompetitionType, CrossoverType};
-use mh_tsptw::eval::{Evaluation, Weighted, utils::run_solution};
+use std::path::Path;
+use std::time::Instant;
+
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use mh_tsptw::algorithms::{CompetitionType, CrossoverType, Elite, Metaheuristic};
+use mh_tsptw::eval::{Counted, Evaluation, Lexicographic, Objective, Weighted, parse_order, parse_weights, utils::run_solution};
 use mh_tsptw::factories::{AlgoParams, AlgoType};
 use mh_tsptw::initializer::{Initializer, RandomInitializer};
-use mh_tsptw::io::{io_instance::load_instance, io_solution::load_solution};
+use mh_tsptw::io::{io_instance::{InstanceFormat, LoadOptions, Rounding, load_instance_with, save_instance}, io_solution::{load_solution, save_solution}};
 use mh_tsptw::neighborhood::{NeighborhoodType, LocalSearchType};
-use mh_tsptw::shared::{Instance, Solution};
+use mh_tsptw::shared::{Instance, InstanceAnalysis, Solution};
+use mh_tsptw::shared::generator::{CoordinateDistribution, GeneratorConfig, generate};
+use mh_tsptw::utils::rng::next_seed;
+use mh_tsptw::utils::history::{History, HistoryLimit, HistoryRecorder, LogEntry, RecordPolicy, write_history_csv};
 
 type Population = Vec<Solution>;
 
@@ -15,17 +21,114 @@ const EXAMPLE_SOLUTION_PATHS: [&str; 3] = ["data/inst1.sol", "data/inst2.sol", "
 
 const CHALLENGE_NB: usize = 1;
 
+// Alias pour que clap lise la liste d'un seul tenant plutôt que valeur par valeur
+type ObjectiveWeights = Vec<(Objective, f32)>;
+type ObjectiveOrder = Vec<Objective>;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Run in GUI mode
     #[arg(long, default_value_t = false)]
     gui: bool,
+
+    /// Instance file to solve
+    #[arg(long, default_value = CHALLENGE_PATHS[CHALLENGE_NB - 1])]
+    instance: String,
+
+    /// Instance format: solomon, spb, dumas or tsplib (detected from the content by default)
+    #[arg(long)]
+    format: Option<InstanceFormat>,
+
+    /// Rounding of distances computed from coordinates: floor, round, ceil, exact or scaled:FACTOR
+    /// (the format convention by default)
+    #[arg(long)]
+    rounding: Option<Rounding>,
+
+    /// Replace every distance by the shortest path through other nodes: true or false
+    /// (the format convention by default)
+    #[arg(long)]
+    relax_triangle: Option<bool>,
+
+    /// Matrix file (CSV or dense text) replacing the distances, and the travel times unless --time-matrix is given
+    #[arg(long)]
+    distance_matrix: Option<String>,
+
+    /// Matrix file (CSV or dense text) replacing the travel times
+    #[arg(long)]
+    time_matrix: Option<String>,
+
+    /// Weighted objectives as objective=weight pairs. Objectives: distance, makespan, completion,
+    /// waiting, tardiness, late, weighted-lateness, penalty
+    #[arg(long, value_parser = parse_weights, default_value = "distance=1,tardiness=10000,penalty=1")]
+    objectives: ObjectiveWeights,
+
+    /// Compare solutions objective by objective in this order (e.g. tardiness,makespan)
+    /// instead of using the weighted objectives
+    #[arg(long, value_parser = parse_order)]
+    lexicographic: Option<ObjectiveOrder>,
+
+    /// Iterations recorded in the run history: improvement, every:N or log:K (K points per decade)
+    #[arg(long, default_value = "every:10")]
+    record: RecordPolicy,
+
+    /// Bound on the recorded history: unbounded, downsample:N or ring:N
+    #[arg(long, default_value = "unbounded")]
+    history_limit: HistoryLimit,
+
+    /// Directory where the history of each run is written as CSV
+    #[arg(long)]
+    history_dir: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate a random instance, feasible by construction, in the Solomon-like format
+    Generate(GenerateArgs),
+}
+
+#[derive(ClapArgs, Debug)]
+struct GenerateArgs {
+    /// File the instance is written to
+    #[arg(long)]
+    output: String,
+
+    /// Number of nodes, depot included
+    #[arg(long, default_value_t = 20)]
+    nodes: usize,
+
+    /// Coordinate distribution: uniform, clustered or clustered:K (K clusters)
+    #[arg(long, default_value = "uniform")]
+    distribution: CoordinateDistribution,
+
+    /// Side of the square holding the nodes
+    #[arg(long, default_value_t = 100.0)]
+    coordinate_range: f32,
+
+    /// Width of the customer time windows (smaller is tighter)
+    #[arg(long, default_value_t = 20.0)]
+    window_width: f32,
+
+    /// Seed making the instance reproducible (random by default)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Also write the hidden tour the windows were built around, as a solution file
+    #[arg(long)]
+    solution: Option<String>,
 }
 
 fn main() {
     let args = Args::parse();
 
+    if let Some(Command::Generate(generate_args)) = &args.command {
+        run_generate(generate_args);
+        return;
+    }
+
     if args.gui {
         let native_options = eframe::NativeOptions::default();
         eframe::run_native(
@@ -37,14 +140,28 @@ fn main() {
         return;
     }
 
-    let (instance, _graph_instance) = load_instance(CHALLENGE_PATHS[CHALLENGE_NB - 1]).unwrap();
-    let evaluation = Weighted {
-        total_distance_weight: 1.0,
-        violation_time_weight: 10000.0,
-        total_time_weight: 0.0,
-        delay_weight: 0.0,
+    let load_options = LoadOptions {
+        format: args.format,
+        rounding: args.rounding,
+        relax_triangle: args.relax_triangle,
+        distance_matrix: args.distance_matrix.clone(),
+        time_matrix: args.time_matrix.clone(),
     };
+    let (instance, _graph_instance) = match load_instance_with(&args.instance, &load_options) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("Failed to load instance {}: {}", args.instance, e);
+            return;
+        }
+    };
+    match &args.lexicographic {
+        Some(order) => run_benchmarks(&args, &instance, Counted::new(Lexicographic::with_objectives(order.clone()))),
+        None => run_benchmarks(&args, &instance, Counted::new(Weighted::new(args.objectives.clone()))),
+    }
+}
 
+// Recuit simulé puis algorithme génétique sur l'instance, avec l'évaluation choisie
+fn run_benchmarks<E: Evaluation>(args: &Args, instance: &Instance, evaluation: Counted<E>) {
     // Simulated Annealing with factory pattern
     let sa_params = AlgoParams::new()
         .initial_temperature(1000.0)
@@ -57,30 +174,25 @@ fn main() {
 
     let sa_config = sa_params.build_config(AlgoType::SimulatedAnnealing).unwrap();
     let sa_factory = sa_config.into_factory();
-    let mut sa_algorithm = sa_factory.build(&instance);
+    let mut sa_algorithm = sa_factory.build(instance);
 
     let max_iterations = 10000;
-    let mut sa_population = build_initial_population(&instance);
+    let mut sa_population = build_initial_population(instance);
+    let mut sa_recorder = HistoryRecorder::new(args.record);
+    let mut sa_history = History::new(args.history_limit);
+    let sa_start = Instant::now();
 
     for _ in 0..max_iterations {
         let mut fitnesss: Vec<f32> = sa_population
             .iter()
-            .map(|sol| evaluation.score(&instance, sol))
+            .map(|sol| evaluation.score(instance, sol))
             .collect();
-        sa_algorithm.step(&mut sa_population, &mut fitnesss, &instance, &evaluation);
+        sa_algorithm.step(&mut sa_population, &mut fitnesss, instance, &evaluation);
+        record(sa_algorithm.as_ref(), &mut sa_recorder, &mut sa_history, sa_start, &evaluation, false);
     }
+    record(sa_algorithm.as_ref(), &mut sa_recorder, &mut sa_history, sa_start, &evaluation, true);
 
-    let sa_best = sa_population
-        .iter()
-        .enumerate()
-        .min_by(|(_, a), (_, b)| {
-            evaluation
-                .score(&instance, a)
-                .partial_cmp(&evaluation.score(&instance, b))
-                .unwrap()
-        })
-        .map(|(idx, _)| idx);
-    report_result("Simulated Annealing", &instance, &sa_population, sa_best);
+    report_result("Simulated Annealing", sa_algorithm.get_best_solution());
 
     // Genetic Algorithm with factory pattern
     let ga_population_size = 100;
@@ -92,43 +204,55 @@ fn main() {
         .competition_type(CompetitionType::Tournament)
         .population_size(ga_population_size)
         .max_iter(1000)
-        .mutation_rate(0.1)
+        .inversion_rate(0.1)
+        .insertion_rate(0.05)
+        .local_search_rate(0.1)
         .local_search_type(LocalSearchType::Swap);
 
     let ga_config = ga_params.build_config(AlgoType::GeneticAlgorithm).unwrap();
     let ga_factory = ga_config.into_factory();
-    let mut ga_algorithm = ga_factory.build(&instance);
+    let mut ga_algorithm = ga_factory.build(instance);
 
     let mut random_init = RandomInitializer;
     let mut ga_population: Vec<Solution> = (0..ga_population_size)
-        .map(|_| random_init.initialize(&instance))
+        .map(|_| random_init.initialize(instance))
         .collect();
 
     let ga_max_iterations = 1000;
+    let mut ga_recorder = HistoryRecorder::new(args.record);
+    let mut ga_history = History::new(args.history_limit);
+    let ga_start = Instant::now();
     for _ in 0..ga_max_iterations {
         let mut ga_fitnesss: Vec<f32> = ga_population
             .iter()
-            .map(|sol| evaluation.score(&instance, sol))
+            .map(|sol| evaluation.score(instance, sol))
             .collect();
-        ga_algorithm.step(&mut ga_population, &mut ga_fitnesss, &instance, &evaluation);
+        ga_algorithm.step(&mut ga_population, &mut ga_fitnesss, instance, &evaluation);
+        record(ga_algorithm.as_ref(), &mut ga_recorder, &mut ga_history, ga_start, &evaluation, false);
     }
+    record(ga_algorithm.as_ref(), &mut ga_recorder, &mut ga_history, ga_start, &evaluation, true);
 
-    let ga_best = ga_population
-        .iter()
-        .enumerate()
-        .min_by(|(_, a), (_, b)| {
-            evaluation
-                .score(&instance, a)
-                .partial_cmp(&evaluation.score(&instance, b))
-                .unwrap()
-        })
-        .map(|(idx, _)| idx);
-    report_result("Genetic Algorithm", &instance, &ga_population, ga_best);
+    report_result("Genetic Algorithm", ga_algorithm.get_best_solution());
+
+    if let Some(dir) = &args.history_dir {
+        let dir = Path::new(dir);
+        let written = std::fs::create_dir_all(dir)
+            .and_then(|_| write_history_csv(&sa_history, &sa_algorithm.get_metric_names(), &dir.join("sa_history.csv")))
+            .and_then(|_| write_history_csv(&ga_history, &ga_algorithm.get_metric_names(), &dir.join("ga_history.csv")));
+        match written {
+            Ok(()) => println!("Histories written to {}", dir.display()),
+            Err(e) => eprintln!("Failed to write histories: {}", e),
+        }
+    }
 
+    // The example solution only makes sense for the challenge instance
+    if args.instance != CHALLENGE_PATHS[CHALLENGE_NB - 1] {
+        return;
+    }
     let example_solution = load_solution(&EXAMPLE_SOLUTION_PATHS[CHALLENGE_NB - 1].to_string());
     match example_solution {
         Ok(sol) => {
-            let eval_result = run_solution(&instance, &sol.path);
+            let eval_result = run_solution(instance, &sol.path);
             println!(
                 "Example solution performance: total_distance={}, total_violation={}",
                 eval_result.total_distance, eval_result.violation_time
@@ -138,33 +262,97 @@ fn main() {
     }
 }
 
+fn run_generate(args: &GenerateArgs) {
+    let seed = args.seed.unwrap_or_else(next_seed);
+    let config = GeneratorConfig {
+        size: args.nodes,
+        distribution: args.distribution,
+        coordinate_range: args.coordinate_range,
+        window_width: args.window_width,
+        seed,
+    };
+    let generated = generate(&config);
+    // Le nom ne dépend que de la configuration : deux générations identiques donnent le même fichier
+    let name = format!("generated n={} w={} seed={}", args.nodes, args.window_width, seed);
+    if let Err(e) = save_instance(&args.output, &name, &generated.nodes) {
+        eprintln!("Failed to write instance {}: {}", args.output, e);
+        return;
+    }
+    println!(
+        "Instance written to {} ({} nodes, seed {}, hidden tour distance {})",
+        args.output, generated.nodes.len(), seed, generated.hidden_distance
+    );
+
+    if let Some(path) = &args.solution {
+        // Villes numérotées à partir de 1, comme les autres fichiers .sol
+        let tour: Vec<u32> = generated.hidden_tour.iter().map(|&v| v + 1).collect();
+        match save_solution(path, &tour, Some(generated.hidden_distance as u32)) {
+            Ok(()) => println!("Hidden tour written to {}", path),
+            Err(e) => eprintln!("Failed to write hidden tour {}: {}", path, e),
+        }
+    }
+
+    // Relecture du fichier écrit : statistiques telles que les verront les algorithmes
+    match load_instance_with(&args.output, &LoadOptions::default()) {
+        Ok((instance, _)) => {
+            let analysis = InstanceAnalysis::new(&instance);
+            let eval = run_solution(&instance, &generated.hidden_tour);
+            println!(
+                "Tightness {:.3}, mean window width {:.1}, horizon {:.1}, forced precedences {}, hidden tour violations {}",
+                analysis.tightness, analysis.mean_width, analysis.horizon,
+                analysis.forced_precedences.len(), eval.nb_violations
+            );
+        }
+        Err(e) => eprintln!("Failed to read back instance {}: {}", args.output, e),
+    }
+}
+
 fn build_initial_population(instance: &Instance) -> Population {
     let route: Vec<u32> = (0..instance.size() as u32).collect();
     vec![route]
 }
 
-fn report_result(
-    name: &str,
-    instance: &Instance,
-    population: &Population,
-    best_idx: Option<usize>,
+// Ajoute l'état de `algo` à l'historique si la politique l'exige, ou systématiquement en fin de run
+fn record<E: Evaluation>(
+    algo: &dyn Metaheuristic<Counted<E>>,
+    recorder: &mut HistoryRecorder,
+    history: &mut History,
+    start: Instant,
+    evaluation: &Counted<E>,
+    last: bool,
 ) {
-    match best_idx {
-        Some(idx) => {
-            let best = &population[idx];
+    let iteration = algo.get_iteration();
+    let best_fitness = algo.get_best_solution().map(|elite| elite.fitness);
+    let due = recorder.should_record(iteration, best_fitness);
+    let already_recorded = history.last().is_some_and(|entry| entry.iteration == iteration);
+    if (due || last) && !already_recorded
+        && let Some(entry) = LogEntry::capture(algo, start.elapsed(), evaluation.count())
+    {
+        history.push(entry);
+    }
+}
+
+fn report_result(name: &str, elite: Option<&Elite>) {
+    match elite {
+        Some(elite) => {
             println!(
-                "{} finished. Best solution visits {} nodes. Full solution {}",
+                "{} finished. Best solution visits {} nodes (found at iteration {} after {:.2?}). Full solution {}",
                 name,
-                best.len(),
-                best.iter()
+                elite.solution.len(),
+                elite.iteration,
+                elite.elapsed,
+                elite.solution.iter()
                     .map(|n| n.to_string())
                     .collect::<Vec<String>>()
                     .join(" -> ")
             );
-            let eval_result = run_solution(instance, best);
             println!(
-                "{} performance: total_distance={}, total_violation={} ",
-                name, eval_result.total_distance, eval_result.violation_time
+                "{} performance: total_distance={}, total_violation={}, nb_violations={} ",
+                name, elite.eval.total_distance, elite.eval.violation_time, elite.eval.nb_violations
+            );
+            println!(
+                "{} schedule: makespan={}, completion_time={}, waiting={}, weighted_lateness={}, penalty={}",
+                name, elite.eval.total_time, elite.eval.completion_time, elite.eval.delay, elite.eval.weighted_lateness, elite.eval.penalty
             );
         }
         None => eprintln!("{} aborted: population is empty.", name),
@@ -1,21 +1,51 @@
 use crate::shared::Instance;
 use crate::shared::Solution;
 use crate::shared::Fitness;
+use crate::shared::CandidateLists;
 use crate::eval::Evaluation;
 use crate::algorithms::{LocalSearch, SimulatedAnnealing, HillClimbing};
 
 mod swap;
 mod twoopt;
+mod oropt;
 
 pub use swap::Swap;
 pub use twoopt::TwoOpt;
+pub use oropt::OrOpt;
 //pub use utils::NeighborFnMixer;
 
+use rand::Rng;
+use rand::rngs::StdRng;
+
+/// Proportion des voisins tirés à partir des listes de candidats, les autres étant tirés uniformément
+const CANDIDATE_MOVE_RATE: f32 = 0.5;
+
+/// Tire une ville au hasard et l'un de ses candidats, et retourne leurs positions (i, j) dans la tournée.
+/// Retourne None pour revenir au tirage uniforme (tirage uniforme choisi, liste vide ou villes déjà voisines).
+fn candidate_positions(solution: &Solution, candidates: &CandidateLists, rng: &mut StdRng) -> Option<(usize, usize)> {
+    let len = solution.len();
+    if len < 4 || rng.random::<f32>() >= CANDIDATE_MOVE_RATE {
+        return None;
+    }
+    let i = rng.random_range(0..len);
+    let list = candidates.combined(solution[i]);
+    if list.is_empty() {
+        return None;
+    }
+    let candidate = list[rng.random_range(0..list.len())];
+    let j = solution.iter().position(|&node| node == candidate)?;
+    if j == (i + 1) % len || i == (j + 1) % len {
+        return None;
+    }
+    Some((i, j))
+}
+
 
 #[derive(PartialEq, Clone, Copy)]
 pub enum NeighborhoodType {
     Swap,
     TwoOpt,
+    OrOpt,
 }
 
 impl Default for NeighborhoodType {
@@ -28,6 +58,7 @@ impl Default for NeighborhoodType {
 pub enum Neighborhood {
     Swap(Swap),
     TwoOpt(TwoOpt),
+    OrOpt(OrOpt),
 }
 
 impl Neighborhood {
@@ -35,6 +66,7 @@ impl Neighborhood {
         match neighborhood_type {
             NeighborhoodType::Swap => Neighborhood::Swap(Swap::new(instance)),
             NeighborhoodType::TwoOpt => Neighborhood::TwoOpt(TwoOpt::new(instance)),
+            NeighborhoodType::OrOpt => Neighborhood::OrOpt(OrOpt::new(instance)),
         }
     }
 }
@@ -43,6 +75,7 @@ impl Neighborhood {
 pub enum LocalSearchType {
     Swap,
     TwoOpt,
+    OrOpt,
     SimulatedAnnealing,
     HillClimbing,
 }
@@ -57,8 +90,10 @@ impl Default for LocalSearchType {
 pub enum LocalSearchImpl {
     Swap(Swap),
     TwoOpt(TwoOpt),
-    SimulatedAnnealing(SimulatedAnnealing),
-    HillClimbing(HillClimbing),
+    OrOpt(OrOpt),
+    // Boxés : ils embarquent un voisinage complet et leurs propres buffers
+    SimulatedAnnealing(Box<SimulatedAnnealing>),
+    HillClimbing(Box<HillClimbing>),
 }
 
 impl LocalSearchImpl {
@@ -66,10 +101,11 @@ impl LocalSearchImpl {
         match ls_type {
             LocalSearchType::Swap => LocalSearchImpl::Swap(Swap::new(instance)),
             LocalSearchType::TwoOpt => LocalSearchImpl::TwoOpt(TwoOpt::new(instance)),
+            LocalSearchType::OrOpt => LocalSearchImpl::OrOpt(OrOpt::new(instance)),
             LocalSearchType::SimulatedAnnealing => {
                 // Default parameters for SA as local search
                 let neighborhood = Neighborhood::from_type(NeighborhoodType::Swap, instance);
-                LocalSearchImpl::SimulatedAnnealing(SimulatedAnnealing::new(
+                LocalSearchImpl::SimulatedAnnealing(Box::new(SimulatedAnnealing::new(
                     100.0,  // initial_temperature
                     0.95,   // cooling_rate
                     0.01,   // stopping_temperature
@@ -78,16 +114,16 @@ impl LocalSearchImpl {
                     0.9,    // delta_fitness_smoothing_factor
                     neighborhood,
                     0,      // backtracking_interval
-                ))
+                )))
             },
             LocalSearchType::HillClimbing => {
                 // Default parameters for HC as local search
                 let neighborhood = Neighborhood::from_type(NeighborhoodType::Swap, instance);
-                LocalSearchImpl::HillClimbing(HillClimbing::new(
+                LocalSearchImpl::HillClimbing(Box::new(HillClimbing::new(
                     10,     // step
                     100,    // max_steps
                     neighborhood,
-                ))
+                )))
             },
         }
     }
@@ -104,6 +140,7 @@ impl<Eval: Evaluation> LocalSearch<Eval> for LocalSearchImpl {
         match self {
             LocalSearchImpl::Swap(ls) => ls.search(solution, fitness, instance, evaluation),
             LocalSearchImpl::TwoOpt(ls) => ls.search(solution, fitness, instance, evaluation),
+            LocalSearchImpl::OrOpt(ls) => ls.search(solution, fitness, instance, evaluation),
             LocalSearchImpl::SimulatedAnnealing(ls) => ls.search(solution, fitness, instance, evaluation),
             LocalSearchImpl::HillClimbing(ls) => ls.search(solution, fitness, instance, evaluation),
         }
@@ -132,6 +169,7 @@ impl NeighborFn for Neighborhood {
         match self {
             Neighborhood::Swap(n) => n.get_neighbor(solution),
             Neighborhood::TwoOpt(n) => n.get_neighbor(solution),
+            Neighborhood::OrOpt(n) => n.get_neighbor(solution),
         }
     }
 }
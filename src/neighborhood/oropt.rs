@@ -0,0 +1,189 @@
+use crate::shared::{CandidateLists, Solution, Instance, Fitness};
+use crate::algorithms::LocalSearch;
+use crate::eval::Evaluation;
+use crate::neighborhood::Neighborhood;
+use crate::utils::rng::new_rng;
+
+use super::{NeighborFn, candidate_positions};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::sync::Arc;
+
+/// Longueur maximale d'un segment déplacé
+const MAX_SEGMENT_LEN: usize = 3;
+
+/// Or-opt : déplace un segment de 1 à 3 villes consécutives ailleurs dans la tournée, sans l'inverser.
+/// Aucune arête n'est parcourue à l'envers, ce qui en fait un voisinage sûr sur une instance asymétrique.
+#[derive(Clone)]
+pub struct OrOpt {
+    rand: StdRng,
+    buffer: Solution,
+    candidates: Arc<CandidateLists>,
+}
+
+impl OrOpt {
+    pub fn new(instance: &Instance) -> Self {
+        OrOpt {
+            rand: new_rng(),
+            buffer: Vec::with_capacity(instance.size()),
+            candidates: instance.candidates.clone(),
+        }
+    }
+
+    /// Reconstruit la tournée en insérant le segment [start, start + len) juste après la position `after`
+    fn move_segment(&mut self, solution: &Solution, start: usize, len: usize, after: usize) {
+        let segment = start..start + len;
+        self.buffer.clear();
+        for (k, &node) in solution.iter().enumerate() {
+            if segment.contains(&k) {
+                continue;
+            }
+            self.buffer.push(node);
+            if k == after {
+                self.buffer.extend_from_slice(&solution[segment.clone()]);
+            }
+        }
+    }
+}
+
+impl NeighborFn for OrOpt {
+    fn get_neighbor(&mut self, solution: &Solution) -> &Solution {
+        let len = solution.len();
+        if len < 3 {
+            self.buffer.clone_from(solution);
+            return &self.buffer;
+        }
+
+        // Mouvement guidé : le segment commençant au candidat devient le successeur de la ville tirée
+        if let Some((i, j)) = candidate_positions(solution, &self.candidates, &mut self.rand) {
+            // Le segment ne déborde pas de la fin du vecteur et ne contient pas la ville tirée
+            let mut segment_len = self.rand.random_range(1..=MAX_SEGMENT_LEN).min(len - j);
+            if i > j {
+                segment_len = segment_len.min(i - j);
+            }
+            self.move_segment(solution, j, segment_len, i);
+            return &self.buffer;
+        }
+
+        let segment_len = self.rand.random_range(1..=MAX_SEGMENT_LEN.min(len - 2));
+        let start = self.rand.random_range(0..=len - segment_len);
+        // Position d'insertion hors du segment, autre que son prédécesseur actuel (mouvement nul)
+        let mut after = self.rand.random_range(0..len - segment_len);
+        if after >= start {
+            after += segment_len;
+        }
+        if after + 1 == start {
+            after = if start + segment_len < len { start + segment_len } else { 0 };
+        }
+        self.move_segment(solution, start, segment_len, after);
+        &self.buffer
+    }
+}
+
+impl<Eval: Evaluation> LocalSearch<Eval> for OrOpt {
+    fn search(
+        &mut self,
+        solution: &mut Solution,
+        fitness: &mut Fitness,
+        instance: &Instance,
+        evaluation: &Eval,
+    ) {
+        let neighbor = self.get_neighbor(solution);
+        let neighbor_fitness = evaluation.score(instance, neighbor);
+
+        if neighbor_fitness < *fitness {
+            solution.clone_from_slice(neighbor);
+            *fitness = neighbor_fitness;
+        }
+    }
+
+    fn reset(&mut self) {
+        // Nothing to reset for OrOpt
+    }
+
+    fn change_neighborhood(&mut self, _neighborhood: Neighborhood) {
+        // OrOpt doesn't support changing neighborhood
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{TimeWindows, Window, Ville};
+
+    use ndarray::Array2;
+
+    const SIZE: usize = 10;
+
+    fn line_instance() -> Instance {
+        let distances = Array2::from_shape_fn((SIZE, SIZE), |(i, j)| (i as f32 - j as f32).abs());
+        let windows = (0..SIZE).map(|_| TimeWindows::single(Window { wstart: 0.0, wend: 1000.0 })).collect();
+        Instance::new(windows, distances)
+    }
+
+    // Vrai si `neighbor` s'obtient en déplaçant un bloc de 1 à MAX_SEGMENT_LEN villes de `solution`
+    fn is_segment_move(solution: &Solution, neighbor: &Solution) -> bool {
+        (1..=MAX_SEGMENT_LEN).any(|len| {
+            (0..=solution.len().saturating_sub(len)).any(|start| {
+                let segment = &solution[start..start + len];
+                let Some(position) = neighbor.windows(len).position(|w| w == segment) else { return false };
+                let rest = |route: &Solution, at: usize| -> Vec<Ville> {
+                    route[..at].iter().chain(&route[at + len..]).copied().collect()
+                };
+                rest(solution, start) == rest(neighbor, position)
+            })
+        })
+    }
+
+    #[test]
+    fn move_segment_inserts_after_position() {
+        let mut or_opt = OrOpt::new(&line_instance());
+        let solution: Solution = vec![1, 2, 3, 4, 5, 6];
+
+        or_opt.move_segment(&solution, 1, 2, 4);
+        assert_eq!(or_opt.buffer, vec![1, 4, 5, 2, 3, 6]);
+
+        or_opt.move_segment(&solution, 3, 2, 0);
+        assert_eq!(or_opt.buffer, vec![1, 4, 5, 2, 3, 6]);
+
+        or_opt.move_segment(&solution, 0, 3, 5);
+        assert_eq!(or_opt.buffer, vec![4, 5, 6, 1, 2, 3]);
+    }
+
+    #[test]
+    fn neighbors_move_one_segment() {
+        let instance = line_instance();
+        let mut or_opt = OrOpt::new(&instance);
+        let solution: Solution = (1..SIZE as Ville).collect();
+        for _ in 0..1000 {
+            let neighbor = or_opt.get_neighbor(&solution).clone();
+            assert_ne!(neighbor, solution);
+            assert!(is_segment_move(&solution, &neighbor), "{:?}", neighbor);
+        }
+    }
+
+    #[test]
+    fn tiny_routes_are_unchanged() {
+        let mut or_opt = OrOpt::new(&line_instance());
+        let solution: Solution = vec![2, 1];
+        assert_eq!(or_opt.get_neighbor(&solution), &solution);
+    }
+
+    #[test]
+    fn segments_keep_their_direction_on_asymmetric_instances() {
+        let mut instance = line_instance();
+        instance.distance_matrix[[1, 2]] = 100.0;
+        instance.time_matrix[[1, 2]] = 100.0;
+        let instance = Instance::with_time_matrix(instance.windows, instance.distance_matrix, instance.time_matrix);
+        assert!(!instance.symmetric);
+
+        let mut or_opt = OrOpt::new(&instance);
+        let solution: Solution = (1..SIZE as Ville).collect();
+        for _ in 0..1000 {
+            let neighbor = or_opt.get_neighbor(&solution).clone();
+            // Le segment déplacé garde son sens de parcours
+            assert!(is_segment_move(&solution, &neighbor), "{:?}", neighbor);
+        }
+    }
+}
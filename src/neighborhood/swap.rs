@@ -1,24 +1,27 @@
-use super::NeighborFn;
-use crate::shared::{Solution, Instance, Fitness};
+use super::{NeighborFn, candidate_positions};
+use crate::shared::{CandidateLists, Solution, Instance, Fitness};
 use crate::algorithms::LocalSearch;
 use crate::eval::Evaluation;
 use crate::neighborhood::Neighborhood;
+use crate::utils::rng::new_rng;
 
-use rand::SeedableRng;
 use rand::prelude::*;
 use rand::rngs::StdRng;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Swap {
     rand: StdRng,
     buffer: Solution,
+    candidates: Arc<CandidateLists>,
 }
 
 impl Swap {
     pub fn new(instance: &Instance) -> Self {
         Swap {
-            rand: StdRng::from_os_rng(),
+            rand: new_rng(),
             buffer: vec![0; instance.size()],
+            candidates: instance.candidates.clone(),
         }
     }
 }
@@ -27,6 +30,18 @@ impl NeighborFn for Swap {
     fn get_neighbor(&mut self, solution: &Solution) -> &Solution {
         // Implementation of the swap neighbor generation
         let len = solution.len();
+
+        if self.buffer.len() != len {
+            self.buffer.resize(len, 0);
+        }
+
+        // Mouvement guidé : le candidat devient le successeur de la ville tirée
+        if let Some((i, j)) = candidate_positions(solution, &self.candidates, &mut self.rand) {
+            self.buffer.clone_from_slice(&solution[..]);
+            self.buffer.swap((i + 1) % len, j);
+            return &self.buffer;
+        }
+
         let mut i = self.rand.random_range(0..len);
         let mut j;
         if i == len - 1 {
@@ -36,10 +51,6 @@ impl NeighborFn for Swap {
             j = self.rand.random_range(i + 1..len);
         }
 
-        if self.buffer.len() != len {
-            self.buffer.resize(len, 0);
-        }
-
         self.buffer.clone_from_slice(&solution[..]);
         self.buffer.swap(i, j);
         &self.buffer
@@ -1,24 +1,30 @@
-use crate::shared::{Solution, Instance, Fitness};
+use crate::shared::{CandidateLists, Solution, Instance, Fitness};
 use crate::algorithms::LocalSearch;
 use crate::eval::Evaluation;
 use crate::neighborhood::Neighborhood;
+use crate::utils::rng::new_rng;
 
-use super::NeighborFn;
+use super::{NeighborFn, candidate_positions};
 
 use rand::prelude::*;
 use rand::rngs::StdRng;
+use std::sync::Arc;
 
+/// 2-opt : inverse un segment de la tournée. Sur une instance asymétrique, le segment inversé est
+/// parcouru dans l'autre sens : son coût orienté et ses horaires sont mesurés par l'évaluation du voisin.
 #[derive(Clone)]
 pub struct TwoOpt {
     rand: StdRng,
     buffer: Solution,
+    candidates: Arc<CandidateLists>,
 }
 
 impl TwoOpt {
     pub fn new(instance: &Instance) -> Self {
         TwoOpt {
-            rand: StdRng::from_os_rng(),
+            rand: new_rng(),
             buffer: vec![0; instance.size()],
+            candidates: instance.candidates.clone(),
         }
     }
 }
@@ -28,6 +34,22 @@ impl NeighborFn for TwoOpt {
         // Implementation of the 2-opt neighbor generation
 
         let len = solution.len();
+
+        if self.buffer.len() != len {
+            self.buffer.resize(len, 0);
+        }
+
+        // Mouvement guidé : l'inversion rend la ville tirée et son candidat adjacents
+        if let Some((i, j)) = candidate_positions(solution, &self.candidates, &mut self.rand) {
+            self.buffer.clone_from_slice(&solution[..]);
+            if i < j {
+                self.buffer[i + 1..=j].reverse();
+            } else {
+                self.buffer[j..i].reverse();
+            }
+            return &self.buffer;
+        }
+
         let mut i = self.rand.random_range(0..len);
         let mut j;
 
@@ -38,10 +60,6 @@ impl NeighborFn for TwoOpt {
             j = self.rand.random_range(i + 1..len);
         }
 
-        if self.buffer.len() != len {
-            self.buffer.resize(len, 0);
-        }
-
         self.buffer.clone_from_slice(&solution[..]);
         self.buffer[i..=j].reverse();
         &self.buffer
@@ -73,3 +91,45 @@ impl<Eval: Evaluation> LocalSearch<Eval> for TwoOpt {
         // TwoOpt doesn't support changing neighborhood
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::{TimeWindows, Window};
+
+    use ndarray::Array2;
+
+    const SIZE: usize = 6;
+
+    // Trajets vers une ville d'indice plus faible bon marché, très chers dans l'autre sens ;
+    // les fenêtres suivent l'ordre décroissant [0, 5, 4, 3, 2, 1]
+    fn asymmetric_instance() -> Instance {
+        let distances = Array2::from_shape_fn((SIZE, SIZE), |(i, j)| {
+            if i == j { 0.0 } else if j < i || (i == 0 && j == SIZE - 1) { 1.0 } else { 50.0 }
+        });
+        let windows = (0..SIZE)
+            .map(|i| {
+                let arrival = if i == 0 { 0.0 } else { (SIZE - i) as f32 };
+                TimeWindows::single(Window { wstart: arrival, wend: if i == 0 { 1000.0 } else { arrival + 2.0 } })
+            })
+            .collect();
+        Instance::new(windows, distances)
+    }
+
+    #[test]
+    fn neighbor_is_the_sampled_reversal() {
+        let instance = asymmetric_instance();
+        assert!(!instance.symmetric);
+        let mut two_opt = TwoOpt::new(&instance);
+        let solution: Solution = vec![0, 5, 4, 3, 2, 1];
+        for _ in 0..1000 {
+            let neighbor = two_opt.get_neighbor(&solution).clone();
+            let Some(first) = (0..SIZE).find(|&k| neighbor[k] != solution[k]) else { continue };
+            let last = (0..SIZE).rfind(|&k| neighbor[k] != solution[k]).unwrap();
+            // Un seul segment inversé, le reste de la tournée à sa place
+            let mut reversed = solution[first..=last].to_vec();
+            reversed.reverse();
+            assert_eq!(neighbor[first..=last], reversed[..], "{:?}", neighbor);
+        }
+    }
+}
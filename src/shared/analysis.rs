@@ -0,0 +1,123 @@
+use super::Instance;
+
+/// Nombre maximal de villes ou de paires citées dans un message d'anomalie
+const MAX_LISTED: usize = 10;
+
+/// Analyse des fenêtres de temps d'une instance, calculée une fois au chargement.
+///
+/// Le dépôt est la ville 0, quittée à l'ouverture de sa fenêtre. Les tests de faisabilité sont
+/// des conditions nécessaires : une instance signalée est infaisable, une instance non signalée
+/// peut l'être quand même. Ils supposent l'inégalité triangulaire sur les temps de trajet, et ignorent
+/// les fenêtres souples, qu'un retard ne rend pas irréalisables.
+pub struct InstanceAnalysis {
+    pub size: usize,
+    // Fin de la fenêtre la plus tardive
+    pub horizon: f32,
+    // Durée d'ouverture de chaque ville, toutes fenêtres confondues
+    pub widths: Vec<f32>,
+    // Moyenne sur les clients : la fenêtre du dépôt couvre en général tout l'horizon
+    pub mean_width: f32,
+    /// Largeur moyenne des fenêtres rapportée à l'horizon : proche de 0, les fenêtres sont serrées
+    pub tightness: f32,
+    // Date d'arrivée au plus tôt de chaque ville, en partant directement du dépôt
+    pub earliest_arrival: Vec<f32>,
+    /// Paires (i, j) où j ne peut pas être visité après i : i vient forcément après j
+    pub forced_precedences: Vec<(usize, usize)>,
+    /// Paires qui ne peuvent être visitées ni dans un ordre ni dans l'autre
+    pub conflicting_pairs: Vec<(usize, usize)>,
+    pub empty_windows: Vec<usize>,
+    /// Villes dont la fenêtre se ferme avant qu'on puisse y arriver depuis le dépôt
+    pub unreachable: Vec<usize>,
+    /// Villes depuis lesquelles on ne peut pas rentrer au dépôt avant sa fermeture
+    pub no_return: Vec<usize>,
+}
+
+impl InstanceAnalysis {
+    pub fn new(instance: &Instance) -> Self {
+        let size = instance.size();
+        let windows = &instance.windows;
+        let travel_time = &instance.time_matrix;
+
+        let horizon = windows.iter().map(|w| w.last_end()).fold(0.0, f32::max);
+        let widths: Vec<f32> = windows.iter()
+            .map(|w| w.slots().iter().map(|slot| slot.wend - slot.wstart).sum())
+            .collect();
+        let customer_widths = widths.get(1..).unwrap_or_default();
+        let mean_width = customer_widths.iter().sum::<f32>() / customer_widths.len().max(1) as f32;
+        let tightness = if horizon > 0.0 { mean_width / horizon } else { 0.0 };
+
+        let departure = windows.first().map(|w| w.first_start()).unwrap_or(0.0);
+        let depot_close = if size > 0 { instance.deadline(0) } else { f32::INFINITY };
+        let earliest_arrival: Vec<f32> = (0..size)
+            .map(|i| if i == 0 { departure } else { windows[i].service(departure + travel_time[[0, i]]).0 })
+            .collect();
+
+        let empty_windows = (0..size)
+            .filter(|&i| windows[i].slots().iter().any(|slot| slot.wend < slot.wstart))
+            .collect();
+        let unreachable = (1..size).filter(|&i| departure + travel_time[[0, i]] > instance.deadline(i)).collect();
+        let no_return = (1..size).filter(|&i| earliest_arrival[i] + travel_time[[i, 0]] > depot_close).collect();
+
+        // i peut précéder j si, parti de i au plus tôt, on arrive en j avant sa fermeture
+        let can_precede = |i: usize, j: usize| earliest_arrival[i] + travel_time[[i, j]] <= instance.deadline(j);
+        let mut forced_precedences = Vec::new();
+        let mut conflicting_pairs = Vec::new();
+        for i in 1..size {
+            for j in i + 1..size {
+                match (can_precede(i, j), can_precede(j, i)) {
+                    (true, true) => {}
+                    (true, false) => forced_precedences.push((j, i)),
+                    (false, true) => forced_precedences.push((i, j)),
+                    (false, false) => conflicting_pairs.push((i, j)),
+                }
+            }
+        }
+
+        Self {
+            size,
+            horizon,
+            widths,
+            mean_width,
+            tightness,
+            earliest_arrival,
+            forced_precedences,
+            conflicting_pairs,
+            empty_windows,
+            unreachable,
+            no_return,
+        }
+    }
+
+    /// Vrai si une condition nécessaire de faisabilité est violée
+    pub fn is_infeasible(&self) -> bool {
+        !self.issues().is_empty()
+    }
+
+    /// Anomalies rendant l'instance infaisable, décrites pour l'utilisateur
+    pub fn issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if !self.empty_windows.is_empty() {
+            issues.push(format!("Windows closing before they open: {}", list(&self.empty_windows)));
+        }
+        if !self.unreachable.is_empty() {
+            issues.push(format!("Customers unreachable from the depot in time: {}", list(&self.unreachable)));
+        }
+        if !self.no_return.is_empty() {
+            issues.push(format!("Customers with no return to the depot before it closes: {}", list(&self.no_return)));
+        }
+        if !self.conflicting_pairs.is_empty() {
+            let pairs: Vec<String> = self.conflicting_pairs.iter().map(|(i, j)| format!("{}-{}", i, j)).collect();
+            issues.push(format!("Pairs that cannot be visited in either order: {}", list(&pairs)));
+        }
+        issues
+    }
+}
+
+// Énumère au plus MAX_LISTED éléments
+fn list<T: ToString>(items: &[T]) -> String {
+    let mut text: Vec<String> = items.iter().take(MAX_LISTED).map(T::to_string).collect();
+    if items.len() > MAX_LISTED {
+        text.push(format!("... ({} in total)", items.len()));
+    }
+    text.join(", ")
+}
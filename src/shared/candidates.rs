@@ -0,0 +1,126 @@
+use super::{TimeWindows, Ville};
+
+use ndarray::Array2;
+
+/// Nombre de candidats retenus par défaut dans chaque liste
+pub const DEFAULT_CANDIDATE_COUNT: usize = 10;
+
+/// Listes de candidats par ville, calculées une fois par instance :
+/// - `nearest`: les k villes les plus proches
+/// - `time_compatible`: les k villes dont la fenêtre s'enchaîne le mieux après celle de la ville
+///   (pas de retard en partant au plus tôt, puis attente et distance minimales)
+/// - `combined`: l'union des deux, sans doublon
+///
+/// Les listes sont stockées à plat, `k` entrées par ville (moins si l'instance est petite).
+pub struct CandidateLists {
+    count: usize,
+    nearest: Vec<Ville>,
+    time_compatible: Vec<Ville>,
+    combined: Vec<Vec<Ville>>,
+}
+
+impl CandidateLists {
+    pub fn new(windows: &[TimeWindows], distance_matrix: &Array2<f32>, time_matrix: &Array2<f32>, count: usize) -> Self {
+        let size = windows.len();
+        let count = count.min(size.saturating_sub(1));
+        let mut nearest = Vec::with_capacity(size * count);
+        let mut time_compatible = Vec::with_capacity(size * count);
+        let mut combined = Vec::with_capacity(size);
+        let mut others: Vec<Ville> = Vec::with_capacity(size);
+
+        for from in 0..size {
+            others.clear();
+            others.extend((0..size as Ville).filter(|&to| to as usize != from));
+
+            others.sort_by(|&a, &b| distance_matrix[[from, a as usize]].total_cmp(&distance_matrix[[from, b as usize]]));
+            nearest.extend_from_slice(&others[..count]);
+
+            let time_key = |to: Ville| {
+                let to = to as usize;
+                let travel_time = time_matrix[[from, to]];
+                // Les fenêtres multiples sont ramenées à leur enveloppe (première ouverture, dernière fermeture)
+                let lateness = (windows[from].first_start() + travel_time - windows[to].last_end()).max(0.0);
+                let waiting = (windows[to].first_start() - windows[from].last_end() - travel_time).max(0.0);
+                (lateness, waiting + distance_matrix[[from, to]])
+            };
+            others.sort_by(|&a, &b| {
+                let (late_a, cost_a) = time_key(a);
+                let (late_b, cost_b) = time_key(b);
+                late_a.total_cmp(&late_b).then(cost_a.total_cmp(&cost_b))
+            });
+            time_compatible.extend_from_slice(&others[..count]);
+
+            let mut union = nearest[from * count..(from + 1) * count].to_vec();
+            for &node in &others[..count] {
+                if !union.contains(&node) {
+                    union.push(node);
+                }
+            }
+            combined.push(union);
+        }
+
+        CandidateLists { count, nearest, time_compatible, combined }
+    }
+
+    pub fn nearest(&self, node: Ville) -> &[Ville] {
+        let node = node as usize;
+        &self.nearest[node * self.count..(node + 1) * self.count]
+    }
+
+    pub fn time_compatible(&self, node: Ville) -> &[Ville] {
+        let node = node as usize;
+        &self.time_compatible[node * self.count..(node + 1) * self.count]
+    }
+
+    /// Union des plus proches voisins et des voisins les plus compatibles en temps
+    pub fn combined(&self, node: Ville) -> &[Ville] {
+        &self.combined[node as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::Window;
+
+    // Villes aux abscisses 0, 10, 20, 30, 40
+    fn lists(count: usize) -> CandidateLists {
+        let windows: Vec<TimeWindows> = [(0.0, 10.0), (100.0, 110.0), (0.0, 50.0), (0.0, 5.0), (40.0, 60.0)]
+            .iter()
+            .map(|&(wstart, wend)| TimeWindows::single(Window { wstart, wend }))
+            .collect();
+        let size = windows.len();
+        let distances = Array2::from_shape_fn((size, size), |(i, j)| (i as f32 - j as f32).abs() * 10.0);
+        CandidateLists::new(&windows, &distances, &distances, count)
+    }
+
+    #[test]
+    fn lists_nearest_and_time_compatible_nodes() {
+        let lists = lists(2);
+        assert_eq!(lists.nearest(0), [1, 2]);
+        assert_eq!(lists.nearest(2), [1, 3]);
+        // Depuis 0 : 2 sans attente, puis 4, puis 1 après 80 d'attente ; 3 ferme avant qu'on l'atteigne
+        assert_eq!(lists.time_compatible(0), [2, 4]);
+        // Union sans doublon, les plus proches d'abord
+        assert_eq!(lists.combined(0), [1, 2, 4]);
+    }
+
+    #[test]
+    fn lists_are_clamped_and_exclude_the_node() {
+        let lists = lists(DEFAULT_CANDIDATE_COUNT);
+        for node in 0..5 {
+            for list in [lists.nearest(node), lists.time_compatible(node), lists.combined(node)] {
+                assert_eq!(list.len(), 4);
+                assert!(!list.contains(&node));
+                let mut sorted = list.to_vec();
+                sorted.sort_unstable();
+                sorted.dedup();
+                assert_eq!(sorted.len(), 4);
+            }
+        }
+        assert_eq!(lists.time_compatible(0), [2, 4, 1, 3]);
+
+        let single = CandidateLists::new(&[TimeWindows::single(Window { wstart: 0.0, wend: 1.0 })], &Array2::zeros((1, 1)), &Array2::zeros((1, 1)), 3);
+        assert!(single.nearest(0).is_empty() && single.combined(0).is_empty());
+    }
+}
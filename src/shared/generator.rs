@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use super::{Node, Solution, TimeWindows, Ville, Window};
+
+/// Répartition des clients dans le carré [0, coordinate_range]²
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CoordinateDistribution {
+    Uniform,
+    /// Clients groupés autour de `clusters` centres tirés uniformément
+    Clustered { clusters: usize },
+}
+
+// Formes texte : "uniform", "clustered" (5 groupes) ou "clustered:K"
+impl FromStr for CoordinateDistribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s == "uniform" {
+            return Ok(CoordinateDistribution::Uniform);
+        }
+        if s == "clustered" {
+            return Ok(CoordinateDistribution::Clustered { clusters: 5 });
+        }
+        if let Some(clusters) = s.strip_prefix("clustered:") {
+            let clusters: usize = clusters.parse().map_err(|_| format!("Invalid cluster count: {}", clusters))?;
+            if clusters == 0 {
+                return Err("The cluster count must be positive".to_string());
+            }
+            return Ok(CoordinateDistribution::Clustered { clusters });
+        }
+        Err(format!("Unknown coordinate distribution '{}' (expected uniform, clustered or clustered:K)", s))
+    }
+}
+
+/// Paramètres de génération d'une instance aléatoire
+#[derive(Clone, Debug)]
+pub struct GeneratorConfig {
+    /// Nombre de villes, dépôt compris
+    pub size: usize,
+    pub distribution: CoordinateDistribution,
+    /// Côté du carré contenant les villes
+    pub coordinate_range: f32,
+    /// Largeur des fenêtres des clients : plus elle est faible, plus l'instance est serrée
+    pub window_width: f32,
+    pub seed: u64,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            size: 20,
+            distribution: CoordinateDistribution::Uniform,
+            coordinate_range: 100.0,
+            window_width: 20.0,
+            seed: 0,
+        }
+    }
+}
+
+/// Instance générée, avec la tournée cachée autour de laquelle les fenêtres ont été construites
+pub struct GeneratedInstance {
+    pub nodes: Vec<Node>,
+    /// Tournée réalisable par construction, partant du dépôt (ville 0)
+    pub hidden_tour: Solution,
+    /// Distance de la tournée cachée, une borne supérieure de l'optimum
+    pub hidden_distance: f32,
+}
+
+// Écart-type des groupes rapporté au côté du carré
+const CLUSTER_SPREAD: f32 = 0.05;
+
+// Tirage gaussien (Box-Muller)
+fn gaussian(rng: &mut StdRng) -> f32 {
+    let u1: f32 = rng.random_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+}
+
+fn coordinates(config: &GeneratorConfig, rng: &mut StdRng) -> Vec<(f32, f32)> {
+    let range = config.coordinate_range;
+    match config.distribution {
+        CoordinateDistribution::Uniform => (0..config.size)
+            .map(|_| (rng.random_range(0.0..=range).round(), rng.random_range(0.0..=range).round()))
+            .collect(),
+        CoordinateDistribution::Clustered { clusters } => {
+            let centers: Vec<(f32, f32)> = (0..clusters)
+                .map(|_| (rng.random_range(0.0..=range), rng.random_range(0.0..=range)))
+                .collect();
+            let spread = CLUSTER_SPREAD * range;
+            (0..config.size)
+                .map(|_| {
+                    let (cx, cy) = centers[rng.random_range(0..clusters)];
+                    let x = (cx + spread * gaussian(rng)).clamp(0.0, range).round();
+                    let y = (cy + spread * gaussian(rng)).clamp(0.0, range).round();
+                    (x, y)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Génère une instance réalisable : une tournée cachée est tirée au hasard, et la fenêtre de chaque
+/// client contient sa date d'arrivée le long de cette tournée, à une position aléatoire.
+///
+/// Les coordonnées et les bornes des fenêtres sont entières, et les dates d'arrivée sont calculées avec
+/// les distances euclidiennes tronquées : la convention du format Solomon à la relecture. Remplacer les
+/// distances par des plus courts chemins ne fait qu'avancer les arrivées, la tournée reste réalisable.
+/// Une même configuration (graine comprise) produit toujours la même instance.
+pub fn generate(config: &GeneratorConfig) -> GeneratedInstance {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let size = config.size.max(1);
+    let config = GeneratorConfig { size, ..config.clone() };
+
+    let points = coordinates(&config, &mut rng);
+    let distance = |i: usize, j: usize| {
+        let (a, b) = (points[i], points[j]);
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt().floor()
+    };
+
+    let mut hidden_tour: Solution = (0..size as Ville).collect();
+    hidden_tour[1..].shuffle(&mut rng);
+
+    let width = config.window_width.max(0.0);
+    let mut windows = vec![(0.0f32, 0.0f32); size];
+    let mut time = 0.0;
+    let mut hidden_distance = 0.0;
+    for k in 1..size {
+        let (from, to) = (hidden_tour[k - 1] as usize, hidden_tour[k] as usize);
+        time += distance(from, to);
+        hidden_distance += distance(from, to);
+        let before = rng.random_range(0.0..=1.0) * width;
+        windows[to] = ((time - before).floor().max(0.0), (time + width - before).ceil());
+    }
+    let last = hidden_tour[size - 1] as usize;
+    let return_time = time + distance(last, 0);
+    hidden_distance += distance(last, 0);
+    // Le dépôt ferme après le retour de la tournée cachée, avec la même marge qu'un client
+    windows[0] = (0.0, (return_time + width).ceil());
+
+    let nodes = points.iter().zip(&windows)
+        .map(|(&(x, y), &(wstart, wend))| Node { x, y, windows: TimeWindows::single(Window { wstart, wend }) })
+        .collect();
+    GeneratedInstance { nodes, hidden_tour, hidden_distance }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(instance: &GeneratedInstance) -> Vec<(f32, f32, Vec<Window>)> {
+        instance.nodes.iter().map(|n| (n.x, n.y, n.windows.slots().to_vec())).collect()
+    }
+
+    fn configs() -> Vec<GeneratorConfig> {
+        [CoordinateDistribution::Uniform, CoordinateDistribution::Clustered { clusters: 3 }]
+            .into_iter()
+            .flat_map(|distribution| {
+                (0..5).map(move |seed| GeneratorConfig { size: 30, distribution, seed, ..Default::default() })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_distributions() {
+        assert_eq!("uniform".parse(), Ok(CoordinateDistribution::Uniform));
+        assert_eq!("Clustered".parse(), Ok(CoordinateDistribution::Clustered { clusters: 5 }));
+        assert_eq!("clustered:8".parse(), Ok(CoordinateDistribution::Clustered { clusters: 8 }));
+        assert!("clustered:0".parse::<CoordinateDistribution>().is_err());
+        assert!("grid".parse::<CoordinateDistribution>().is_err());
+    }
+
+    #[test]
+    fn same_seed_gives_same_instance() {
+        for config in configs() {
+            let (first, second) = (generate(&config), generate(&config));
+            assert_eq!(summary(&first), summary(&second));
+            assert_eq!(first.hidden_tour, second.hidden_tour);
+            assert_eq!(first.hidden_distance, second.hidden_distance);
+        }
+        let other = GeneratorConfig { seed: 1, ..Default::default() };
+        assert_ne!(summary(&generate(&GeneratorConfig::default())), summary(&generate(&other)));
+    }
+
+    #[test]
+    fn hidden_tour_is_feasible() {
+        for config in configs() {
+            let instance = generate(&config);
+            let nodes = &instance.nodes;
+            let mut sorted = instance.hidden_tour.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..config.size as Ville).collect::<Vec<_>>());
+            assert_eq!(instance.hidden_tour[0], 0);
+
+            // Distances tronquées, comme à la relecture d'un fichier Solomon
+            let distance = |i: Ville, j: Ville| {
+                let (a, b) = (&nodes[i as usize], &nodes[j as usize]);
+                ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt().floor()
+            };
+            let mut time = 0.0;
+            let mut total = 0.0;
+            for pair in instance.hidden_tour.windows(2) {
+                let leg = distance(pair[0], pair[1]);
+                time += leg;
+                total += leg;
+                let window = nodes[pair[1] as usize].windows.slots()[0];
+                assert!(window.wstart <= time && time <= window.wend, "arrival {} outside {:?}", time, window);
+            }
+            let back = distance(*instance.hidden_tour.last().unwrap(), 0);
+            assert!(time + back <= nodes[0].windows.last_end());
+            assert_eq!(total + back, instance.hidden_distance);
+        }
+    }
+}
@@ -1,9 +1,17 @@
 pub mod types;
+pub mod candidates;
+pub mod analysis;
+pub mod generator;
 
 pub use types::Ville;
 pub use types::Solution;
 pub use types::Window;
+pub use types::TimeWindows;
+pub use types::WindowKind;
+pub use types::WindowPolicy;
 pub use types::Fitness;
 pub use types::Instance;
 pub use types::Node;
 pub use types::GraphInstance;
+pub use candidates::CandidateLists;
+pub use analysis::InstanceAnalysis;
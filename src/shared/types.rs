@@ -6,35 +6,271 @@ pub type Solution = Vec<Ville>;
 
 pub type Fitness = f32;
 
-#[derive(Clone)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Window {
     pub wstart: f32,
     pub wend: f32,
 }
 
+/// Fenêtres disjointes d'une ville, triées par ouverture (au moins une).
+/// Une arrivée entre deux fenêtres attend l'ouverture de la suivante.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TimeWindows {
+    slots: Vec<Window>,
+}
+
+impl TimeWindows {
+    /// Trie les fenêtres et fusionne celles qui se chevauchent
+    pub fn new(mut slots: Vec<Window>) -> Self {
+        assert!(!slots.is_empty(), "A node needs at least one time window");
+        slots.sort_by(|a, b| a.wstart.total_cmp(&b.wstart));
+        let mut merged: Vec<Window> = Vec::with_capacity(slots.len());
+        for slot in slots {
+            match merged.last_mut() {
+                Some(last) if slot.wstart <= last.wend => last.wend = last.wend.max(slot.wend),
+                _ => merged.push(slot),
+            }
+        }
+        Self { slots: merged }
+    }
+
+    pub fn single(window: Window) -> Self {
+        Self { slots: vec![window] }
+    }
+
+    pub fn slots(&self) -> &[Window] {
+        &self.slots
+    }
+
+    /// Ouverture de la première fenêtre
+    pub fn first_start(&self) -> f32 {
+        self.slots[0].wstart
+    }
+
+    /// Fermeture de la dernière fenêtre
+    pub fn last_end(&self) -> f32 {
+        self.slots[self.slots.len() - 1].wend
+    }
+
+    /// Début de service et fenêtre utilisée pour une arrivée à `arrival` : la première fenêtre pas encore
+    /// fermée, dont on attend l'ouverture. Après la dernière fenêtre, le service commence à l'arrivée,
+    /// en retard sur celle-ci.
+    pub fn service(&self, arrival: f32) -> (f32, &Window) {
+        match self.slots.iter().find(|slot| arrival <= slot.wend) {
+            Some(slot) => (arrival.max(slot.wstart), slot),
+            None => (arrival, &self.slots[self.slots.len() - 1]),
+        }
+    }
+}
+
+/// Traitement d'une arrivée hors fenêtre : une fenêtre dure compte le retard comme une violation,
+/// une fenêtre souple le fait payer sous forme de pénalité, linéaire ou quadratique
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum WindowKind {
+    #[default]
+    Hard,
+    SoftLinear,
+    SoftQuadratic,
+}
+
+impl WindowKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindowKind::Hard => "hard",
+            WindowKind::SoftLinear => "linear",
+            WindowKind::SoftQuadratic => "quadratic",
+        }
+    }
+}
+
+// Formes texte : "hard" (ou "h"), "linear" ("soft", "l"), "quadratic" ("q")
+impl FromStr for WindowKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "hard" | "h" => Ok(WindowKind::Hard),
+            "linear" | "soft" | "l" => Ok(WindowKind::SoftLinear),
+            "quadratic" | "q" => Ok(WindowKind::SoftQuadratic),
+            other => Err(format!("Unknown window type '{}' (expected hard, linear or quadratic)", other)),
+        }
+    }
+}
+
+/// Coûts associés à la fenêtre d'une ville
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct WindowPolicy {
+    pub kind: WindowKind,
+    // Coût par unité de temps d'attente avant l'ouverture (0 : attente gratuite)
+    pub early_rate: f32,
+}
+
+impl WindowPolicy {
+    /// Pénalité d'une visite arrivée `earliness` avant l'ouverture ou servie `lateness` après la fermeture.
+    /// Le retard sur une fenêtre dure n'est pas une pénalité mais une violation, comptée à part.
+    pub fn penalty(&self, weight: f32, earliness: f32, lateness: f32) -> f32 {
+        let late_cost = match self.kind {
+            WindowKind::Hard => 0.0,
+            WindowKind::SoftLinear => lateness,
+            WindowKind::SoftQuadratic => lateness * lateness,
+        };
+        weight * late_cost + self.early_rate * earliness
+    }
+}
+
 use ndarray::Array2;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::candidates::{CandidateLists, DEFAULT_CANDIDATE_COUNT};
 
 #[derive(Clone)]
 pub struct Instance {
-    pub windows: Vec<Window>,
+    pub windows: Vec<TimeWindows>,
+    // Coût d'un trajet (distance totale de la tournée)
     pub distance_matrix: Array2<f32>,
+    // Durée d'un trajet, utilisée pour les horaires et les fenêtres de temps
+    pub time_matrix: Array2<f32>,
+    // Listes de candidats partagées avec les opérateurs qui les utilisent
+    pub candidates: Arc<CandidateLists>,
+    // Poids de priorité de chaque ville dans le retard pondéré et les pénalités (1 par défaut)
+    pub weights: Vec<f32>,
+    // Type de fenêtre et coût de l'attente de chaque ville (fenêtre dure, attente gratuite par défaut)
+    pub policies: Vec<WindowPolicy>,
+    // Vrai si distances et durées ne dépendent pas du sens de parcours :
+    // une tournée inversée a alors le même coût
+    pub symmetric: bool,
+}
+
+// Tolérance sur l'écart entre les deux sens d'un même trajet
+const SYMMETRY_TOLERANCE: f32 = 1e-4;
+
+fn is_symmetric(matrix: &Array2<f32>) -> bool {
+    matrix.indexed_iter().all(|((i, j), &value)| i >= j || (value - matrix[[j, i]]).abs() <= SYMMETRY_TOLERANCE)
 }
 
 impl Instance {
+    /// Instance dont les durées de trajet sont égales aux distances
+    pub fn new(windows: Vec<TimeWindows>, distance_matrix: Array2<f32>) -> Self {
+        let time_matrix = distance_matrix.clone();
+        Self::with_time_matrix(windows, distance_matrix, time_matrix)
+    }
+
+    pub fn with_time_matrix(windows: Vec<TimeWindows>, distance_matrix: Array2<f32>, time_matrix: Array2<f32>) -> Self {
+        let candidates = Arc::new(CandidateLists::new(&windows, &distance_matrix, &time_matrix, DEFAULT_CANDIDATE_COUNT));
+        let symmetric = is_symmetric(&distance_matrix) && is_symmetric(&time_matrix);
+        let weights = vec![1.0; windows.len()];
+        let policies = vec![WindowPolicy::default(); windows.len()];
+        Instance {
+            weights,
+            policies,
+            windows,
+            distance_matrix,
+            time_matrix,
+            candidates,
+            symmetric,
+        }
+    }
+
     pub fn size(&self) -> usize {
         self.windows.len()
     }
+
+    /// Date au-delà de laquelle une arrivée en `node` rend la tournée irréalisable :
+    /// la fin de sa dernière fenêtre si elles sont dures, jamais sinon
+    pub fn deadline(&self, node: usize) -> f32 {
+        match self.policies[node].kind {
+            WindowKind::Hard => self.windows[node].last_end(),
+            _ => f32::INFINITY,
+        }
+    }
 }
 
 // Node with position information for visualization
 pub struct Node {
     pub x: f32,
     pub y: f32,
-    pub wstart: f32,
-    pub wend: f32,
+    pub windows: TimeWindows,
 }
 
 // Instance with graph information for GUI display
 pub struct GraphInstance {
     pub graph: Vec<Node>,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(wstart: f32, wend: f32) -> Window {
+        Window { wstart, wend }
+    }
+
+    #[test]
+    fn windows_are_sorted_and_merged() {
+        let slots = vec![window(50.0, 60.0), window(0.0, 10.0), window(5.0, 20.0), window(20.0, 30.0)];
+        let windows = TimeWindows::new(slots);
+        assert_eq!(windows.slots(), [window(0.0, 30.0), window(50.0, 60.0)]);
+
+        // Une fenêtre contenue dans une autre disparaît
+        let windows = TimeWindows::new(vec![window(0.0, 100.0), window(10.0, 20.0)]);
+        assert_eq!(windows.slots(), [window(0.0, 100.0)]);
+        assert_eq!((windows.first_start(), windows.last_end()), (0.0, 100.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn windows_cannot_be_empty() {
+        TimeWindows::new(Vec::new());
+    }
+
+    #[test]
+    fn service_waits_for_the_next_open_window() {
+        let windows = TimeWindows::new(vec![window(10.0, 20.0), window(40.0, 50.0)]);
+        let service = |arrival: f32| {
+            let (start, slot) = windows.service(arrival);
+            (start, *slot)
+        };
+        assert_eq!(service(0.0), (10.0, window(10.0, 20.0)));
+        assert_eq!(service(15.0), (15.0, window(10.0, 20.0)));
+        assert_eq!(service(20.0), (20.0, window(10.0, 20.0)));
+        assert_eq!(service(25.0), (40.0, window(40.0, 50.0)));
+        assert_eq!(service(45.0), (45.0, window(40.0, 50.0)));
+        // Après la dernière fenêtre, le service commence à l'arrivée, en retard
+        assert_eq!(service(60.0), (60.0, window(40.0, 50.0)));
+    }
+
+    #[test]
+    fn parses_window_kinds() {
+        assert_eq!("hard".parse(), Ok(WindowKind::Hard));
+        assert_eq!(" Soft ".parse(), Ok(WindowKind::SoftLinear));
+        assert_eq!("q".parse(), Ok(WindowKind::SoftQuadratic));
+        assert!("strict".parse::<WindowKind>().is_err());
+    }
+
+    #[test]
+    fn penalties_follow_the_window_kind() {
+        let policy = |kind| WindowPolicy { kind, early_rate: 0.5 };
+        assert_eq!(policy(WindowKind::Hard).penalty(2.0, 4.0, 3.0), 2.0);
+        assert_eq!(policy(WindowKind::SoftLinear).penalty(2.0, 4.0, 3.0), 8.0);
+        assert_eq!(policy(WindowKind::SoftQuadratic).penalty(2.0, 4.0, 3.0), 20.0);
+    }
+
+    #[test]
+    fn detects_symmetric_instances() {
+        let windows = || vec![TimeWindows::single(window(0.0, 100.0)); 3];
+        let symmetric = ndarray::array![[0.0, 1.0, 2.0], [1.0, 0.0, 3.0], [2.0, 3.0, 0.0]];
+        assert!(Instance::new(windows(), symmetric.clone()).symmetric);
+
+        // Un écart sous la tolérance ne compte pas
+        let mut rounded = symmetric.clone();
+        rounded[[0, 1]] += SYMMETRY_TOLERANCE / 2.0;
+        assert!(Instance::new(windows(), rounded).symmetric);
+
+        let mut asymmetric = symmetric.clone();
+        asymmetric[[2, 1]] = 4.0;
+        assert!(!Instance::new(windows(), asymmetric.clone()).symmetric);
+
+        // Les durées comptent autant que les distances
+        assert!(!Instance::with_time_matrix(windows(), symmetric, asymmetric).symmetric);
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::algorithms::Metaheuristic;
+use crate::eval::{Evaluation, Fitness};
+
+// Marge au-delà de la capacité d'un tampon circulaire avant de supprimer les entrées les plus anciennes,
+// pour ne pas décaler tout le vecteur à chaque ajout
+const RING_BUFFER_SLACK: usize = 8;
+
+/// État d'un run à une itération donnée
+#[derive(Clone)]
+pub struct LogEntry {
+    pub iteration: usize,
+    // Temps de calcul écoulé (hors pauses), en secondes
+    pub elapsed: f32,
+    pub evaluations: u64,
+    pub current_dist: f32,
+    pub current_viol: f32,
+    pub metrics: HashMap<String, f32>,
+}
+
+impl LogEntry {
+    /// État courant de `algo`, ou None tant qu'il n'a pas de meilleure solution
+    pub fn capture<E: Evaluation>(algo: &dyn Metaheuristic<E>, elapsed: Duration, evaluations: u64) -> Option<Self> {
+        let elite = algo.get_best_solution()?;
+        Some(Self {
+            iteration: algo.get_iteration(),
+            elapsed: elapsed.as_secs_f32(),
+            evaluations,
+            current_dist: elite.eval.total_distance,
+            current_viol: elite.eval.violation_time,
+            metrics: algo.get_metrics(),
+        })
+    }
+}
+
+/// Itérations auxquelles un run est enregistré
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RecordPolicy {
+    /// À chaque amélioration stricte de la meilleure solution
+    EveryImprovement,
+    /// Toutes les N itérations
+    EveryN(usize),
+    /// Points régulièrement espacés en échelle logarithmique, ce nombre par décade d'itérations
+    LogSpaced(usize),
+}
+
+/// Borne sur le nombre d'entrées conservées par run
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HistoryLimit {
+    Unbounded,
+    /// Au-delà de ce nombre d'entrées, une entrée sur deux est supprimée et la résolution divisée par deux
+    Downsample(usize),
+    /// Seules les dernières entrées sont conservées
+    RingBuffer(usize),
+}
+
+// Format texte : "improvement", "every:N", "log:K"
+impl FromStr for RecordPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| value.parse::<usize>().map_err(|e| format!("Invalid number '{}': {}", value, e));
+        match s.split_once(':') {
+            None if s == "improvement" => Ok(RecordPolicy::EveryImprovement),
+            Some(("every", n)) => Ok(RecordPolicy::EveryN(parse(n)?)),
+            Some(("log", k)) => Ok(RecordPolicy::LogSpaced(parse(k)?)),
+            _ => Err(format!("Unknown record policy '{}' (expected improvement, every:N or log:K)", s)),
+        }
+    }
+}
+
+// Format texte : "unbounded", "downsample:N", "ring:N"
+impl FromStr for HistoryLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| value.parse::<usize>().map_err(|e| format!("Invalid number '{}': {}", value, e));
+        match s.split_once(':') {
+            None if s == "unbounded" => Ok(HistoryLimit::Unbounded),
+            Some(("downsample", n)) => Ok(HistoryLimit::Downsample(parse(n)?)),
+            Some(("ring", n)) => Ok(HistoryLimit::RingBuffer(parse(n)?)),
+            _ => Err(format!("Unknown history limit '{}' (expected unbounded, downsample:N or ring:N)", s)),
+        }
+    }
+}
+
+/// Décide, itération par itération, si l'état du run doit être enregistré
+#[derive(Clone)]
+pub struct HistoryRecorder {
+    policy: RecordPolicy,
+    best_fitness: Option<Fitness>,
+    next_log_iteration: f64,
+}
+
+impl HistoryRecorder {
+    pub fn new(policy: RecordPolicy) -> Self {
+        Self { policy, best_fitness: None, next_log_iteration: 1.0 }
+    }
+
+    /// Vrai si l'itération `iteration`, de meilleure fitness `best_fitness`, doit être enregistrée
+    pub fn should_record(&mut self, iteration: usize, best_fitness: Option<Fitness>) -> bool {
+        match self.policy {
+            RecordPolicy::EveryImprovement => {
+                let improved = match (best_fitness, self.best_fitness) {
+                    (Some(fitness), Some(best)) => fitness < best,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                };
+                if improved {
+                    self.best_fitness = best_fitness;
+                }
+                improved
+            }
+            RecordPolicy::EveryN(n) => iteration.is_multiple_of(n.max(1)),
+            RecordPolicy::LogSpaced(per_decade) => {
+                if (iteration as f64) < self.next_log_iteration {
+                    return false;
+                }
+                let factor = 10f64.powf(1.0 / per_decade.max(1) as f64);
+                while self.next_log_iteration <= iteration as f64 {
+                    // Les premiers points sont espacés d'au moins une itération
+                    self.next_log_iteration = (self.next_log_iteration * factor).max(self.next_log_iteration + 1.0);
+                }
+                true
+            }
+        }
+    }
+}
+
+/// Historique d'un run, borné selon `HistoryLimit`. Se manipule comme une tranche de `LogEntry`.
+#[derive(Clone)]
+pub struct History {
+    entries: Vec<LogEntry>,
+    limit: HistoryLimit,
+    // Sous-échantillonnage : seule une entrée proposée sur `stride` est conservée
+    stride: usize,
+    offered: usize,
+    // La dernière entrée est gardée provisoirement pour que l'historique se termine sur l'état le plus récent
+    provisional_tail: bool,
+}
+
+impl History {
+    pub fn new(limit: HistoryLimit) -> Self {
+        Self { entries: Vec::new(), limit, stride: 1, offered: 0, provisional_tail: false }
+    }
+
+    pub fn push(&mut self, entry: LogEntry) {
+        match self.limit {
+            HistoryLimit::Unbounded => self.entries.push(entry),
+            HistoryLimit::RingBuffer(capacity) => {
+                let capacity = capacity.max(1);
+                self.entries.push(entry);
+                if self.entries.len() > capacity + capacity / RING_BUFFER_SLACK {
+                    let excess = self.entries.len() - capacity;
+                    self.entries.drain(..excess);
+                }
+            }
+            HistoryLimit::Downsample(capacity) => {
+                if self.provisional_tail {
+                    self.entries.pop();
+                }
+                let keep = self.offered.is_multiple_of(self.stride);
+                self.offered += 1;
+                self.entries.push(entry);
+                self.provisional_tail = !keep;
+
+                if self.entries.len() > capacity.max(2) {
+                    let tail = self.entries.pop();
+                    let mut index = 0;
+                    self.entries.retain(|_| {
+                        index += 1;
+                        index % 2 == 1
+                    });
+                    self.stride *= 2;
+                    // L'entrée la plus récente reste visible même si elle n'est plus sur la grille
+                    if let Some(tail) = tail {
+                        self.provisional_tail = !(self.offered - 1).is_multiple_of(self.stride);
+                        self.entries.push(tail);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = LogEntry>) {
+        for entry in entries {
+            self.push(entry);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new(self.limit);
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(HistoryLimit::Unbounded)
+    }
+}
+
+impl Deref for History {
+    type Target = [LogEntry];
+
+    fn deref(&self) -> &[LogEntry] {
+        &self.entries
+    }
+}
+
+/// Écrit l'historique en CSV : une ligne par entrée, une colonne par métrique
+pub fn write_history_csv(history: &[LogEntry], metric_names: &[String], path: &Path) -> io::Result<()> {
+    let mut writer = csv::Writer::from_path(path).map_err(io::Error::other)?;
+
+    let mut header = ["iteration", "time_s", "evaluations", "distance", "violation"].map(String::from).to_vec();
+    header.extend(metric_names.iter().cloned());
+    writer.write_record(&header).map_err(io::Error::other)?;
+
+    for entry in history {
+        let mut record = vec![
+            entry.iteration.to_string(),
+            entry.elapsed.to_string(),
+            entry.evaluations.to_string(),
+            entry.current_dist.to_string(),
+            entry.current_viol.to_string(),
+        ];
+        record.extend(metric_names.iter().map(|name| {
+            entry.metrics.get(name).map(|v| v.to_string()).unwrap_or_default()
+        }));
+        writer.write_record(&record).map_err(io::Error::other)?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(iteration: usize) -> LogEntry {
+        LogEntry {
+            iteration,
+            elapsed: 0.0,
+            evaluations: 0,
+            current_dist: 0.0,
+            current_viol: 0.0,
+            metrics: HashMap::new(),
+        }
+    }
+
+    fn recorded(policy: RecordPolicy, iterations: std::ops::Range<usize>) -> Vec<usize> {
+        let mut recorder = HistoryRecorder::new(policy);
+        iterations.filter(|&i| recorder.should_record(i, Some(1.0))).collect()
+    }
+
+    #[test]
+    fn parses_policies_and_limits() {
+        assert_eq!("improvement".parse(), Ok(RecordPolicy::EveryImprovement));
+        assert_eq!("every:5".parse(), Ok(RecordPolicy::EveryN(5)));
+        assert_eq!("log:3".parse(), Ok(RecordPolicy::LogSpaced(3)));
+        assert!("every:x".parse::<RecordPolicy>().is_err());
+        assert!("always".parse::<RecordPolicy>().is_err());
+
+        assert_eq!("unbounded".parse(), Ok(HistoryLimit::Unbounded));
+        assert_eq!("downsample:100".parse(), Ok(HistoryLimit::Downsample(100)));
+        assert_eq!("ring:50".parse(), Ok(HistoryLimit::RingBuffer(50)));
+        assert!("ring".parse::<HistoryLimit>().is_err());
+    }
+
+    #[test]
+    fn records_strict_improvements_only() {
+        let mut recorder = HistoryRecorder::new(RecordPolicy::EveryImprovement);
+        assert!(!recorder.should_record(0, None));
+        assert!(recorder.should_record(1, Some(5.0)));
+        assert!(!recorder.should_record(2, Some(5.0)));
+        assert!(!recorder.should_record(3, Some(6.0)));
+        assert!(recorder.should_record(4, Some(4.0)));
+    }
+
+    #[test]
+    fn records_every_n_iterations() {
+        assert_eq!(recorded(RecordPolicy::EveryN(3), 0..10), vec![0, 3, 6, 9]);
+        // N = 0 est traité comme 1
+        assert_eq!(recorded(RecordPolicy::EveryN(0), 0..4), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn records_log_spaced_iterations() {
+        assert_eq!(recorded(RecordPolicy::LogSpaced(1), 0..2000), vec![1, 10, 100, 1000]);
+
+        // Les premiers points sont consécutifs, puis s'espacent
+        let points = recorded(RecordPolicy::LogSpaced(10), 0..1001);
+        assert_eq!(points[..4], [1, 2, 3, 4]);
+        assert!(points.len() >= 20 && points.len() <= 31, "{} points", points.len());
+        assert!(points.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn ring_buffer_keeps_latest_entries() {
+        let mut history = History::new(HistoryLimit::RingBuffer(16));
+        history.extend((0..100).map(entry));
+        assert!(history.len() >= 16 && history.len() <= 16 + 16 / RING_BUFFER_SLACK);
+        assert_eq!(history.last().unwrap().iteration, 99);
+        assert!(history.windows(2).all(|pair| pair[1].iteration == pair[0].iteration + 1));
+    }
+
+    #[test]
+    fn downsampling_bounds_history_and_keeps_latest_entry() {
+        let capacity = 10;
+        let mut history = History::new(HistoryLimit::Downsample(capacity));
+        for i in 0..1000 {
+            history.push(entry(i));
+            assert!(history.len() <= capacity, "{} entries after {} pushes", history.len(), i + 1);
+            assert_eq!(history.last().unwrap().iteration, i);
+        }
+        // Les entrées hors dernière forment une grille régulière partant de 0
+        let iterations: Vec<usize> = history.iter().map(|e| e.iteration).collect();
+        let grid = &iterations[..iterations.len() - 1];
+        assert_eq!(grid[0], 0);
+        let stride = grid[1] - grid[0];
+        assert!(stride > 1);
+        assert!(grid.windows(2).all(|pair| pair[1] - pair[0] == stride));
+    }
+
+    #[test]
+    fn unbounded_history_keeps_everything() {
+        let mut history = History::default();
+        history.extend((0..50).map(entry));
+        assert_eq!(history.len(), 50);
+        history.clear();
+        assert!(history.is_empty());
+    }
+}
@@ -1 +1,4 @@
 pub mod general;
+pub mod rng;
+pub mod stats;
+pub mod history;
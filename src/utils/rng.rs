@@ -0,0 +1,30 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    // Source de graines active pendant `with_seed`, None sinon
+    static SEED_SOURCE: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Exécute `f` en dérivant de `seed` toutes les graines tirées par `next_seed` / `new_rng` sur ce thread.
+/// Construire un algorithme et sa population initiale dans ce contexte rend le run reproductible.
+pub fn with_seed<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    let previous = SEED_SOURCE.with(|source| source.replace(Some(StdRng::seed_from_u64(seed))));
+    let result = f();
+    SEED_SOURCE.with(|source| source.replace(previous));
+    result
+}
+
+/// Graine tirée de la source active, ou aléatoire hors de `with_seed`
+pub fn next_seed() -> u64 {
+    SEED_SOURCE.with(|source| match source.borrow_mut().as_mut() {
+        Some(rng) => rng.random(),
+        None => rand::rng().random(),
+    })
+}
+
+/// Générateur à utiliser à la place de `StdRng::from_os_rng()` pour respecter les graines des runs
+pub fn new_rng() -> StdRng {
+    StdRng::seed_from_u64(next_seed())
+}
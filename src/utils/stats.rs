@@ -0,0 +1,138 @@
+// Tests statistiques non paramétriques pour comparer les valeurs finales de plusieurs configurations.
+// Les p-values sont bilatérales et utilisent l'approximation normale (avec correction des ex aequo
+// et de continuité), suffisante à partir d'une dizaine de runs par groupe.
+
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+// Fonction de répartition de la loi normale centrée réduite (Abramowitz & Stegun 7.1.26)
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 { 0.5 * (1.0 + erf) } else { 0.5 * (1.0 - erf) }
+}
+
+// p-value bilatérale d'une statistique de moyenne `mean` et d'écart-type `sigma` sous H0
+fn two_sided_p_value(statistic: f64, mean: f64, sigma: f64) -> f64 {
+    if sigma <= 0.0 {
+        return 1.0;
+    }
+    let z = ((statistic - mean).abs() - 0.5).max(0.0) / sigma;
+    (2.0 * (1.0 - normal_cdf(z))).clamp(0.0, 1.0)
+}
+
+// Rangs (à partir de 1, moyennés pour les ex aequo) et somme des t^3 - t sur les groupes d'ex aequo
+fn ranks(values: &[f64]) -> (Vec<f64>, f64) {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut ties = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        let t = (end - start) as f64;
+        ties += t * t * t - t;
+        start = end;
+    }
+    (ranks, ties)
+}
+
+/// Test U de Mann-Whitney sur deux échantillons indépendants
+pub fn mann_whitney_u(a: &[f32], b: &[f32]) -> Option<TestResult> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let values: Vec<f64> = a.iter().chain(b).map(|&v| v as f64).collect();
+    let (ranks, ties) = ranks(&values);
+
+    let rank_sum_a: f64 = ranks[..a.len()].iter().sum();
+    let u_a = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let u = u_a.min(n1 * n2 - u_a);
+
+    let n = n1 + n2;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)));
+    Some(TestResult { statistic: u, p_value: two_sided_p_value(u, n1 * n2 / 2.0, variance.max(0.0).sqrt()) })
+}
+
+/// Test des rangs signés de Wilcoxon sur des échantillons appariés (`a[i]` avec `b[i]`)
+pub fn wilcoxon_signed_rank(a: &[f32], b: &[f32]) -> Option<TestResult> {
+    // Les différences nulles sont écartées
+    let differences: Vec<f64> = a.iter().zip(b)
+        .map(|(&x, &y)| x as f64 - y as f64)
+        .filter(|d| *d != 0.0)
+        .collect();
+    if differences.is_empty() {
+        return None;
+    }
+
+    let magnitudes: Vec<f64> = differences.iter().map(|d| d.abs()).collect();
+    let (ranks, ties) = ranks(&magnitudes);
+    let w_plus: f64 = differences.iter().zip(&ranks).filter(|(d, _)| **d > 0.0).map(|(_, r)| r).sum();
+
+    let n = differences.len() as f64;
+    let total = n * (n + 1.0) / 2.0;
+    let w = w_plus.min(total - w_plus);
+    let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties / 48.0;
+    Some(TestResult { statistic: w, p_value: two_sided_p_value(w, total / 2.0, variance.max(0.0).sqrt()) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Valeurs de référence : wilcox.test(..., exact = FALSE, correct = TRUE) sous R
+    const TOLERANCE: f64 = 1e-4;
+
+    #[test]
+    fn mann_whitney_matches_reference() {
+        // Exemple de la documentation de wilcox.test
+        let x = [0.80, 0.83, 1.89, 1.04, 1.45, 1.38, 1.91, 1.64, 0.73, 1.46];
+        let y = [1.15, 0.88, 0.90, 0.74, 1.21];
+        let result = mann_whitney_u(&x, &y).unwrap();
+        assert_eq!(result.statistic, 15.0);
+        assert!((result.p_value - 0.244624).abs() < TOLERANCE, "{}", result.p_value);
+
+        let result = mann_whitney_u(&[1.0, 2.0, 3.0, 4.0, 5.0], &[6.0, 7.0, 8.0, 9.0, 10.0]).unwrap();
+        assert_eq!(result.statistic, 0.0);
+        assert!((result.p_value - 0.012186).abs() < TOLERANCE, "{}", result.p_value);
+    }
+
+    #[test]
+    fn mann_whitney_corrects_for_ties() {
+        let result = mann_whitney_u(&[1.0, 2.0, 2.0, 3.0], &[2.0, 3.0, 4.0, 5.0]).unwrap();
+        assert_eq!(result.statistic, 2.5);
+        assert!((result.p_value - 0.136658).abs() < TOLERANCE, "{}", result.p_value);
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)] // 3.14 est une mesure de l'exemple, pas pi
+    fn wilcoxon_matches_reference() {
+        // Exemple apparié de la documentation de wilcox.test
+        let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+        let result = wilcoxon_signed_rank(&x, &y).unwrap();
+        assert_eq!(result.statistic, 5.0);
+        assert!((result.p_value - 0.044011).abs() < TOLERANCE, "{}", result.p_value);
+    }
+
+    #[test]
+    fn identical_samples_are_not_different() {
+        let a = [3.0, 1.0, 2.0, 5.0];
+        assert!(wilcoxon_signed_rank(&a, &a).is_none());
+        assert!(mann_whitney_u(&a, &a).unwrap().p_value > 0.9);
+        assert!(mann_whitney_u(&a, &[]).is_none());
+    }
+}